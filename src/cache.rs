@@ -5,7 +5,80 @@ use std::path::{Path, PathBuf};
 use std::time::{SystemTime, UNIX_EPOCH};
 
 use crate::config::Config;
-use crate::models::ProjectList;
+use crate::models::{Project, ProjectList};
+use crate::output::trace;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Marks a file as a project cache written with a schema header
+const CACHE_MAGIC: &[u8; 4] = b"swpc";
+
+/// Layout version of cached projects. Bump it whenever `Project` changes in a
+/// way older binaries would misread, so their caches count as misses.
+pub const CACHE_SCHEMA_VERSION: u32 = 2;
+
+/// Prefix `projects` with the cache header: magic, then the schema version
+fn encode_projects(projects: &[Project]) -> Result<Vec<u8>> {
+    let mut data = CACHE_MAGIC.to_vec();
+    data.extend_from_slice(&CACHE_SCHEMA_VERSION.to_le_bytes());
+    bincode::serde::encode_into_std_write(projects, &mut data, bincode::config::standard())
+        .map_err(|e| anyhow::anyhow!("Failed to serialize cache: {}", e))?;
+    Ok(data)
+}
+
+/// Projects from a cache file written by this schema version. Files with no
+/// header, another version, or a body that doesn't decode give `None`.
+fn decode_projects(data: &[u8]) -> Option<Vec<Project>> {
+    let body = data.strip_prefix(CACHE_MAGIC.as_slice())?;
+    let (version, body) = body.split_first_chunk::<4>()?;
+    let version = u32::from_le_bytes(*version);
+    if version != CACHE_SCHEMA_VERSION {
+        trace!(
+            "Cache schema version {} does not match {}, rescanning",
+            version,
+            CACHE_SCHEMA_VERSION
+        );
+        return None;
+    }
+    bincode::serde::decode_from_slice(body, bincode::config::standard())
+        .ok()
+        .map(|(projects, _)| projects)
+}
+
+/// When one source was last scanned successfully and what it found
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SourceScan {
+    /// When the scan started; anything modified later may not be reflected
+    pub scanned_at: DateTime<Utc>,
+    pub project_count: usize,
+}
+
+/// Per-source scan history kept next to the projects cache
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScanMetadata {
+    pub sources: BTreeMap<String, SourceScan>,
+}
+
+impl ScanMetadata {
+    pub fn record(&mut self, source: &str, project_count: usize, scanned_at: DateTime<Utc>) {
+        self.sources.insert(
+            source.to_string(),
+            SourceScan {
+                scanned_at,
+                project_count,
+            },
+        );
+    }
+
+    /// How long ago `source` was last scanned, if it ever was
+    #[allow(dead_code)]
+    pub fn age(&self, source: &str, now: DateTime<Utc>) -> Option<chrono::Duration> {
+        self.sources
+            .get(source)
+            .map(|scan| now.signed_duration_since(scan.scanned_at))
+    }
+}
 
 #[derive(Debug)]
 pub struct Cache {
@@ -37,46 +110,80 @@ impl Cache {
         self.cache_dir.join("sw_github.cache")
     }
 
-    pub fn is_cache_valid<P: AsRef<Path>>(&self, cache_path: P) -> bool {
-        let path = cache_path.as_ref();
+    pub fn scan_metadata_path(&self) -> PathBuf {
+        self.cache_dir.join("sw_meta.json")
+    }
 
+    /// Per-source scan times and counts; empty if nothing was recorded yet
+    pub fn scan_metadata(&self) -> Result<ScanMetadata> {
+        let path = self.scan_metadata_path();
         if !path.exists() {
-            return false;
+            return Ok(ScanMetadata::default());
         }
 
-        if let Ok(metadata) = fs::metadata(path) {
-            if let Ok(modified) = metadata.modified() {
-                if let Ok(duration) = modified.duration_since(UNIX_EPOCH) {
-                    let now = SystemTime::now()
-                        .duration_since(UNIX_EPOCH)
-                        .unwrap_or_default();
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read scan metadata: {}", path.display()))?;
+        serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse scan metadata: {}", path.display()))
+    }
 
-                    let age_seconds = now.as_secs() - duration.as_secs();
-                    return age_seconds < self.ttl_seconds;
-                }
+    pub fn save_scan_metadata(&self, metadata: &ScanMetadata) -> Result<()> {
+        let data =
+            serde_json::to_vec_pretty(metadata).context("Failed to serialize scan metadata")?;
+        self.atomic_write(self.scan_metadata_path(), &data)
+    }
+
+    pub fn is_cache_valid<P: AsRef<Path>>(&self, cache_path: P) -> bool {
+        self.age_seconds(cache_path)
+            .is_some_and(|age_seconds| age_seconds < self.ttl_seconds)
+    }
+
+    /// Seconds since the cache file was written, if it exists
+    fn age_seconds<P: AsRef<Path>>(&self, cache_path: P) -> Option<u64> {
+        let modified = fs::metadata(cache_path).ok()?.modified().ok()?;
+        let written = modified.duration_since(UNIX_EPOCH).ok()?;
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+        Some(now.as_secs().saturating_sub(written.as_secs()))
+    }
+
+    /// Why the cache file is or isn't used, for `-vv` output
+    pub fn freshness<P: AsRef<Path>>(&self, cache_path: P) -> String {
+        match self.age_seconds(&cache_path) {
+            None => "missing".to_string(),
+            Some(age) if age < self.ttl_seconds => {
+                format!("fresh ({}s old, ttl {}s)", age, self.ttl_seconds)
             }
+            Some(age) => format!("stale ({}s old, ttl {}s)", age, self.ttl_seconds),
         }
-
-        false
     }
 
     pub fn load_projects(&self) -> Result<Option<ProjectList>> {
+        if !self.is_cache_valid(self.projects_cache_path()) {
+            return Ok(None);
+        }
+
+        self.load_stale_projects()
+    }
+
+    /// Load the projects cache regardless of its age, e.g. as a fallback
+    /// when a fresh scan can't reach a remote
+    pub fn load_stale_projects(&self) -> Result<Option<ProjectList>> {
         let cache_path = self.projects_cache_path();
 
-        if !self.is_cache_valid(&cache_path) {
+        if !cache_path.exists() {
             return Ok(None);
         }
 
         let data = fs::read(&cache_path)
             .with_context(|| format!("Failed to read cache file: {}", cache_path.display()))?;
 
-        match bincode::serde::decode_from_slice::<Vec<crate::models::Project>, _>(
-            &data,
-            bincode::config::standard(),
-        ) {
-            Ok((projects, _)) => Ok(Some(ProjectList::from_projects(projects))),
-            Err(_) => {
-                // Cache is corrupted, invalidate it and return None to trigger fresh scan
+        match decode_projects(&data) {
+            Some(projects) => Ok(Some(ProjectList::from_projects(projects))),
+            None => {
+                // Cache is corrupted or from another schema version, invalidate
+                // it and return None to trigger fresh scan
                 let _ = fs::remove_file(&cache_path);
                 Ok(None)
             }
@@ -84,66 +191,13 @@ impl Cache {
     }
 
     fn atomic_write<P: AsRef<Path>>(&self, target_path: P, data: &[u8]) -> Result<()> {
-        let target_path = target_path.as_ref();
-
-        if let Some(parent_dir) = target_path.parent() {
-            fs::create_dir_all(parent_dir).with_context(|| {
-                format!(
-                    "Failed to create parent directory: {}",
-                    parent_dir.display()
-                )
-            })?;
-        }
-
-        let now = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap_or_default();
-
-        let temp_filename = format!(
-            "{}.tmp.{}.{}",
-            target_path
-                .file_name()
-                .and_then(|n| n.to_str())
-                .unwrap_or("cache"),
-            std::process::id(),
-            now.as_nanos()
-        );
-
-        let temp_path = target_path.with_file_name(temp_filename);
-
-        {
-            let mut temp_file = fs::File::create(&temp_path).with_context(|| {
-                format!("Failed to create temporary file: {}", temp_path.display())
-            })?;
-
-            temp_file.write_all(data).with_context(|| {
-                format!("Failed to write to temporary file: {}", temp_path.display())
-            })?;
-
-            temp_file.sync_all().with_context(|| {
-                format!("Failed to sync temporary file: {}", temp_path.display())
-            })?;
-        }
-
-        if let Err(e) = fs::rename(&temp_path, target_path) {
-            let _ = fs::remove_file(&temp_path);
-            return Err(e).with_context(|| {
-                format!(
-                    "Failed to rename {} to {}",
-                    temp_path.display(),
-                    target_path.display()
-                )
-            });
-        }
-
-        Ok(())
+        atomic_write(target_path, data)
     }
 
     pub fn save_projects(&self, projects: &ProjectList) -> Result<()> {
         let cache_path = self.projects_cache_path();
 
-        let data = bincode::serde::encode_to_vec(projects.projects(), bincode::config::standard())
-            .map_err(|e| anyhow::anyhow!("Failed to serialize cache: {}", e))?;
+        let data = encode_projects(projects.projects())?;
 
         let mut last_error = None;
         for attempt in 0..3 {
@@ -177,13 +231,11 @@ impl Cache {
         let data = fs::read(&cache_path)
             .with_context(|| format!("Failed to read GitHub cache: {}", cache_path.display()))?;
 
-        match bincode::serde::decode_from_slice::<Vec<crate::models::Project>, _>(
-            &data,
-            bincode::config::standard(),
-        ) {
-            Ok((projects, _)) => Ok(Some(ProjectList::from_projects(projects))),
-            Err(_) => {
-                // Cache is corrupted, invalidate it and return None to trigger fresh scan
+        match decode_projects(&data) {
+            Some(projects) => Ok(Some(ProjectList::from_projects(projects))),
+            None => {
+                // Cache is corrupted or from another schema version, invalidate
+                // it and return None to trigger fresh scan
                 if let Err(e) = fs::remove_file(&cache_path) {
                     eprintln!(
                         "Warning: Failed to remove corrupted GitHub cache file: {}",
@@ -199,8 +251,8 @@ impl Cache {
     pub fn save_github_projects(&self, projects: &ProjectList) -> Result<()> {
         let cache_path = self.github_cache_path();
 
-        let data = bincode::serde::encode_to_vec(projects.projects(), bincode::config::standard())
-            .map_err(|e| anyhow::anyhow!("Failed to serialize GitHub cache: {}", e))?;
+        let data =
+            encode_projects(projects.projects()).context("Failed to serialize GitHub cache")?;
 
         let mut last_error = None;
         for attempt in 0..3 {
@@ -225,7 +277,11 @@ impl Cache {
 
     #[allow(dead_code)]
     pub fn invalidate_all(&self) -> Result<()> {
-        let paths = [self.projects_cache_path(), self.github_cache_path()];
+        let paths = [
+            self.projects_cache_path(),
+            self.github_cache_path(),
+            self.scan_metadata_path(),
+        ];
 
         for path in &paths {
             if path.exists() {
@@ -238,10 +294,67 @@ impl Cache {
     }
 }
 
+/// Write `data` to a temporary file next to `target_path` and rename it into
+/// place, so readers never see a partially written file
+pub(crate) fn atomic_write<P: AsRef<Path>>(target_path: P, data: &[u8]) -> Result<()> {
+    let target_path = target_path.as_ref();
+
+    if let Some(parent_dir) = target_path.parent() {
+        fs::create_dir_all(parent_dir).with_context(|| {
+            format!(
+                "Failed to create parent directory: {}",
+                parent_dir.display()
+            )
+        })?;
+    }
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+
+    let temp_filename = format!(
+        "{}.tmp.{}.{}",
+        target_path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("cache"),
+        std::process::id(),
+        now.as_nanos()
+    );
+
+    let temp_path = target_path.with_file_name(temp_filename);
+
+    {
+        let mut temp_file = fs::File::create(&temp_path)
+            .with_context(|| format!("Failed to create temporary file: {}", temp_path.display()))?;
+
+        temp_file.write_all(data).with_context(|| {
+            format!("Failed to write to temporary file: {}", temp_path.display())
+        })?;
+
+        temp_file
+            .sync_all()
+            .with_context(|| format!("Failed to sync temporary file: {}", temp_path.display()))?;
+    }
+
+    if let Err(e) = fs::rename(&temp_path, target_path) {
+        let _ = fs::remove_file(&temp_path);
+        return Err(e).with_context(|| {
+            format!(
+                "Failed to rename {} to {}",
+                temp_path.display(),
+                target_path.display()
+            )
+        });
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::models::{Project, ProjectSource};
+    use crate::models::ProjectSource;
     use std::thread;
     use std::time::Duration;
     use tempfile::TempDir;
@@ -285,12 +398,15 @@ mod tests {
         let cache_file = temp_dir.path().join("test.cache");
 
         assert!(!cache.is_cache_valid(&cache_file));
+        assert_eq!(cache.freshness(&cache_file), "missing");
 
         fs::write(&cache_file, "test").unwrap();
         assert!(cache.is_cache_valid(&cache_file));
+        assert!(cache.freshness(&cache_file).starts_with("fresh"));
 
         thread::sleep(Duration::from_secs(2));
         assert!(!cache.is_cache_valid(&cache_file));
+        assert!(cache.freshness(&cache_file).starts_with("stale"));
     }
 
     #[test]
@@ -320,6 +436,130 @@ mod tests {
         assert_eq!(loaded.projects()[1].source, ProjectSource::GitHub);
     }
 
+    #[test]
+    fn test_cache_with_unknown_source_loads() {
+        #[derive(serde::Serialize)]
+        struct FutureProject {
+            name: String,
+            path: PathBuf,
+            last_modified: Option<chrono::DateTime<chrono::Utc>>,
+            source: String,
+            github_url: Option<String>,
+            gitlab_url: Option<String>,
+            host: Option<String>,
+            project_type: Option<String>,
+            size_bytes: Option<u64>,
+            archived: bool,
+        }
+
+        let temp_dir = TempDir::new().unwrap();
+        let cache = Cache {
+            cache_dir: temp_dir.path().to_path_buf(),
+            ttl_seconds: 60,
+        };
+
+        let written = vec![
+            FutureProject {
+                name: "bb-project".to_string(),
+                path: PathBuf::from("/bb/path"),
+                last_modified: None,
+                source: "Bitbucket".to_string(),
+                github_url: None,
+                gitlab_url: None,
+                host: None,
+                project_type: None,
+                size_bytes: None,
+                archived: false,
+            },
+            FutureProject {
+                name: "local-project".to_string(),
+                path: PathBuf::from("/local/path"),
+                last_modified: None,
+                source: "Local".to_string(),
+                github_url: None,
+                gitlab_url: None,
+                host: None,
+                project_type: None,
+                size_bytes: None,
+                archived: false,
+            },
+        ];
+        let mut data = CACHE_MAGIC.to_vec();
+        data.extend_from_slice(&CACHE_SCHEMA_VERSION.to_le_bytes());
+        bincode::serde::encode_into_std_write(&written, &mut data, bincode::config::standard())
+            .unwrap();
+        fs::write(cache.projects_cache_path(), data).unwrap();
+
+        let loaded = cache.load_projects().unwrap().unwrap();
+        assert_eq!(loaded.len(), 2);
+        assert_eq!(
+            loaded.projects()[0].source,
+            ProjectSource::Unknown("Bitbucket".to_string())
+        );
+        assert_eq!(loaded.projects()[1].source, ProjectSource::Local);
+
+        cache.save_projects(&loaded).unwrap();
+        let reloaded = cache.load_projects().unwrap().unwrap();
+        assert_eq!(reloaded.projects(), loaded.projects());
+    }
+
+    #[test]
+    fn test_cache_from_other_schema_version_is_a_miss() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache = Cache {
+            cache_dir: temp_dir.path().to_path_buf(),
+            ttl_seconds: 60,
+        };
+        let projects = vec![Project::new_local("old".to_string(), "/old/path")];
+        let cache_path = cache.projects_cache_path();
+
+        // Same body, written by a binary with a different schema version
+        let mut data = CACHE_MAGIC.to_vec();
+        data.extend_from_slice(&(CACHE_SCHEMA_VERSION + 1).to_le_bytes());
+        bincode::serde::encode_into_std_write(&projects, &mut data, bincode::config::standard())
+            .unwrap();
+        fs::write(&cache_path, &data).unwrap();
+        assert!(cache.load_projects().unwrap().is_none());
+        assert!(!cache_path.exists());
+
+        // A headerless cache from before versioning decodes fine, but is a miss too
+        let data = bincode::serde::encode_to_vec(&projects, bincode::config::standard()).unwrap();
+        fs::write(&cache_path, &data).unwrap();
+        assert!(cache.load_stale_projects().unwrap().is_none());
+
+        cache
+            .save_projects(&ProjectList::from_projects(projects))
+            .unwrap();
+        assert_eq!(cache.load_projects().unwrap().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_scan_metadata_roundtrip() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache = Cache {
+            cache_dir: temp_dir.path().to_path_buf(),
+            ttl_seconds: 60,
+        };
+
+        assert_eq!(cache.scan_metadata().unwrap(), ScanMetadata::default());
+
+        let now = Utc::now();
+        let mut metadata = ScanMetadata::default();
+        metadata.record("local", 12, now - chrono::Duration::hours(1));
+        metadata.record("github", 40, now - chrono::Duration::minutes(5));
+        cache.save_scan_metadata(&metadata).unwrap();
+
+        let loaded = cache.scan_metadata().unwrap();
+        assert_eq!(loaded, metadata);
+        assert_eq!(loaded.sources["github"].project_count, 40);
+        assert_eq!(loaded.age("local", now), Some(chrono::Duration::hours(1)));
+        assert_eq!(
+            loaded.age("github", now),
+            Some(chrono::Duration::minutes(5))
+        );
+        assert_eq!(loaded.age("gitlab", now), None);
+    }
+
     #[test]
     fn test_cache_invalidation() {
         let temp_dir = TempDir::new().unwrap();
@@ -0,0 +1,43 @@
+use anyhow::{Context, Result};
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// Clipboard tools tried in order; the first one on `PATH` is used
+const CLIPBOARD_COMMANDS: &[(&str, &[&str])] = &[
+    ("pbcopy", &[]),
+    ("wl-copy", &[]),
+    ("xclip", &["-selection", "clipboard"]),
+    ("xsel", &["--clipboard", "--input"]),
+    ("clip", &[]),
+];
+
+/// Put `text` on the system clipboard by piping it to the platform's
+/// clipboard tool
+pub fn copy_to_clipboard(text: &str) -> Result<()> {
+    let (program, args) = CLIPBOARD_COMMANDS
+        .iter()
+        .find(|(program, _)| which::which(program).is_ok())
+        .context("No clipboard tool found (install pbcopy, wl-copy, xclip or xsel)")?;
+
+    let mut child = Command::new(program)
+        .args(*args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .with_context(|| format!("Failed to start {}", program))?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(text.as_bytes())
+            .with_context(|| format!("Failed to write to {}", program))?;
+    }
+
+    let status = child
+        .wait()
+        .with_context(|| format!("Failed to wait for {}", program))?;
+    if !status.success() {
+        anyhow::bail!("{} exited with {}", program, status);
+    }
+    Ok(())
+}
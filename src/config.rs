@@ -1,12 +1,145 @@
+use crate::models::{Project, ProjectSource};
 use anyhow::{Context, Result};
 use directories::ProjectDirs;
 use serde::{Deserialize, Serialize};
+use serde_json::error::Category;
+use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// Config file chosen with `--config`, overriding the platform default
+static CONFIG_FILE_OVERRIDE: OnceLock<PathBuf> = OnceLock::new();
+
+/// How fuzzy search treats letter case
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SearchCase {
+    /// Case-sensitive only when the query contains an uppercase letter
+    #[default]
+    Smart,
+    /// Always ignore case
+    Insensitive,
+    /// Always match case exactly
+    Sensitive,
+}
+
+/// Fuzzy matching algorithm used by the TUI
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MatcherKind {
+    /// Skim's fuzzy matcher
+    #[default]
+    Skim,
+    /// Built-in subsequence matcher with a contiguity bonus
+    Simple,
+}
+
+/// Which entry wins when a local checkout and a remote repository share a path
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DedupPolicy {
+    /// Keep the local entry as-is
+    #[default]
+    PreferLocal,
+    /// Keep the local entry, but take the remote's URL and timestamp
+    PreferRemoteMetadata,
+}
+
+/// What happens when one repository is cloned at several local paths
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MultiClonePolicy {
+    /// List every clone, plus the remote entry
+    #[default]
+    ShowAll,
+    /// List only the most recently modified clone
+    PreferNewest,
+}
+
+/// Which GitHub repository timestamp becomes a project's modification time
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GitHubTimestampField {
+    /// Last push only; repositories never pushed to stay undated
+    Pushed,
+    /// Last update, which also changes on stars and description edits
+    Updated,
+    /// Last push, or the last update for repositories never pushed to
+    #[default]
+    PushedThenUpdated,
+}
+
+/// How each project is laid out on an fzf line
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FzfFormat {
+    /// `{icon} {name} ({date})`
+    #[default]
+    Compact,
+    /// Aligned name, source, age and path columns, all searchable
+    Columns,
+}
+
+/// What opening a project does when the editor already has it open
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlreadyOpenAction {
+    /// Bring the existing window to the front, skipping `post_open_command`
+    Focus,
+    /// Run the open command as usual
+    #[default]
+    Reopen,
+    /// Leave the editor alone
+    Noop,
+}
+
+/// Built-in TUI palette that `theme` colors are applied on top of
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ThemePreset {
+    /// Light text on dark backgrounds
+    #[default]
+    Dark,
+    /// Dark text for light terminal backgrounds
+    Light,
+    /// Light when `COLORFGBG` reports a light background, otherwise dark
+    Auto,
+}
+
+/// Hex colors (`#rrggbb`) for the TUI's color roles; unset or invalid
+/// entries keep the built-in color
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ThemeColors {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub primary: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secondary: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub success: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub warning: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub surface: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text_primary: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text_secondary: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text_muted: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub accent: Option<String>,
+    /// Marker for projects with uncommitted changes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dirty: Option<String>,
+}
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Config {
-    /// The command to use for opening projects in an editor
+    /// The command to use for opening projects in an editor. `$EDITOR`
+    /// resolves `$EDITOR`/`$VISUAL` each time a project is opened.
     pub editor_command: String,
     /// List of directories to scan for projects
     pub project_dirs: Vec<PathBuf>,
@@ -14,8 +147,226 @@ pub struct Config {
     pub github_username: Option<String>,
     /// GitLab username for repository discovery
     pub gitlab_username: Option<String>,
-    /// Cache time-to-live in seconds
+    /// Cache time-to-live in seconds, for scanned projects and `--size` results
     pub cache_ttl_seconds: u64,
+    /// Minimum query length before fuzzy matching is applied in the TUI
+    #[serde(default = "default_min_query_len")]
+    pub min_query_len: usize,
+    /// Ask before cloning a remote project when running in a terminal
+    #[serde(default)]
+    pub confirm_clone: bool,
+    /// When confirmation is required but there is no TTY, clone anyway
+    /// (true) or refuse with an error (false)
+    #[serde(default = "default_true")]
+    pub clone_without_tty: bool,
+    /// How many remote projects are cloned at once when several are opened
+    #[serde(default = "default_clone_concurrency")]
+    pub clone_concurrency: usize,
+    /// Threads the TUI uses at startup to check which project paths exist
+    /// and to probe the GitHub and GitLab CLIs
+    #[serde(default = "default_tui_probe_threads")]
+    pub tui_probe_threads: usize,
+    /// Case handling for fuzzy search: smart, insensitive or sensitive
+    #[serde(default)]
+    pub search_case: SearchCase,
+    /// Fuzzy matching algorithm: skim or simple
+    #[serde(default)]
+    pub matcher: MatcherKind,
+    /// Added to a search score when the query matches the project name from
+    /// its start or right after a `-`, `_` or `/`
+    #[serde(default = "default_boundary_bonus")]
+    pub boundary_bonus: i64,
+    /// Most a search score is raised for a project modified just now; the
+    /// raise halves after a week, so near-ties favor recent projects
+    #[serde(default = "default_recency_weight")]
+    pub recency_weight: i64,
+    /// Friendlier names shown in place of directory names, keyed by
+    /// canonical project path
+    #[serde(default)]
+    pub display_names: HashMap<String, String>,
+    /// Command that opens projects instead of the editor; `{path}` and
+    /// `{name}` (or `$1` and `$2`) are the project path and name, each passed
+    /// as a single argument
+    #[serde(default)]
+    pub open_command: Option<String>,
+    /// Like `open_command`, but only for projects picked in the interactive
+    /// TUI or `--simple` prompt, e.g. to switch a running editor's folder
+    #[serde(default)]
+    pub editor_switch_command: Option<String>,
+    /// Command run in the project directory after the editor is launched;
+    /// `{path}` (or `$1`) is the project path, passed as a single argument
+    #[serde(default)]
+    pub post_open_command: Option<String>,
+    /// Projects whose `.sw.toml` may set their own `post_open_command`; the
+    /// file is ignored everywhere else, since any cloned repository can
+    /// ship one
+    #[serde(default)]
+    pub trusted_projects: Vec<PathBuf>,
+    /// Command run detached after each successful fresh scan; `{count}` and
+    /// the `SW_PROJECT_COUNT` environment variable hold the project count
+    #[serde(default)]
+    pub post_scan_command: Option<String>,
+    /// Seconds to wait for each scanner before skipping it
+    #[serde(default = "default_scanner_timeout_seconds")]
+    pub scanner_timeout_seconds: u64,
+    /// Add the project to the current editor window instead of opening a
+    /// new one (editors that support it only)
+    #[serde(default)]
+    pub open_in_current_window: bool,
+    /// Where remote repositories are cloned, with `{host}`, `{owner}` and
+    /// `{repo}` placeholders. Unset keeps `~/Documents/git/{repo}` for
+    /// GitHub and `~/gitlab/{owner}/{repo}` for GitLab.
+    #[serde(default)]
+    pub clone_path_template: Option<String>,
+    /// Most GitHub repositories read in one scan; the rest are skipped
+    #[serde(default = "default_github_repo_limit")]
+    pub github_repo_limit: usize,
+    /// Only list GitHub and GitLab repositories pushed to (or otherwise
+    /// active) within this many days
+    #[serde(default)]
+    pub github_active_within_days: Option<u64>,
+    /// Which GitHub timestamp a repository's modification time comes from
+    #[serde(default)]
+    pub github_timestamp_field: GitHubTimestampField,
+    /// Ask the editor to reuse its last active window rather than spawning a
+    /// new one (code and cursor)
+    #[serde(default)]
+    pub reuse_window: bool,
+    /// Open a `*.code-workspace` file in the project root instead of the
+    /// folder, restoring its multi-root layout
+    #[serde(default)]
+    pub prefer_workspace_file: bool,
+    /// Folder or file inside a project to open instead of its root, e.g.
+    /// `web` in a monorepo, keyed by canonical project path
+    #[serde(default)]
+    pub open_subpaths: HashMap<String, PathBuf>,
+    /// Single-character codes that `sw @<code>` opens, mapped to project names
+    #[serde(default)]
+    pub quick_codes: HashMap<char, String>,
+    /// What to do when the project is already open in the editor. Detection
+    /// is best-effort and only works for code and cursor (see
+    /// `opener::is_open_in_editor`); other editors always reopen.
+    #[serde(default)]
+    pub on_already_open: AlreadyOpenAction,
+    /// Pre-fill the TUI with the previous session's query and selection
+    #[serde(default)]
+    pub restore_last_query: bool,
+    /// How many levels below each project directory to look for projects;
+    /// 1 only considers its direct children
+    #[serde(default = "default_scan_depth")]
+    pub scan_depth: usize,
+    /// Further roots the local scanner covers, such as scratch areas, kept
+    /// apart from `project_dirs`. `~` and `$VAR` are expanded.
+    #[serde(default)]
+    pub extra_scan_dirs: Vec<PathBuf>,
+    /// Keep the cached timestamp of repos whose directory and `.git` are
+    /// unchanged since the last scan instead of reading their commit history.
+    /// Not applied with `PreferRemoteMetadata`, whose cached timestamps may
+    /// come from the remote.
+    #[serde(default = "default_true")]
+    pub reuse_git_timestamps: bool,
+    /// Files or directories whose presence makes a directory a project
+    #[serde(default = "default_project_markers")]
+    pub project_markers: Vec<String>,
+    /// Globs matched against each local project's path relative to its scan
+    /// root, where `*` stops at `/`; matching projects are archived, as are
+    /// projects holding an `ARCHIVED` file
+    #[serde(default = "default_archive_patterns")]
+    pub archive_patterns: Vec<String>,
+    /// How local and remote entries for the same path are merged
+    #[serde(default)]
+    pub dedup_policy: DedupPolicy,
+    /// How local clones of the same repository at different paths are listed
+    #[serde(default)]
+    pub multi_clone: MultiClonePolicy,
+    /// Scanner names in the order they are started and their results merged
+    #[serde(default = "default_scanner_order")]
+    pub scanner_order: Vec<String>,
+    /// Scanner names (e.g. `"cursor"`) that are never run
+    #[serde(default)]
+    pub disabled_scanners: Vec<String>,
+    /// Built-in palette of the interactive picker: dark, light or auto
+    #[serde(default)]
+    pub theme_preset: ThemePreset,
+    /// Colors of the interactive picker, by role, overriding the preset
+    #[serde(default)]
+    pub theme: ThemeColors,
+    /// Icon shown before the name for each detected project type
+    #[serde(default = "default_type_icons")]
+    pub type_icons: HashMap<String, String>,
+    /// Only keep Cursor workspaces located under one of `project_dirs`
+    #[serde(default)]
+    pub cursor_restrict_to_project_dirs: bool,
+    /// Show how long ago projects changed ("3d ago") instead of timestamps
+    #[serde(default)]
+    pub relative_times: bool,
+    /// Check git working trees for uncommitted changes, so they can be
+    /// marked and `--sort activity` can put repos with local work first
+    #[serde(default)]
+    pub show_git_status: bool,
+    /// Shown next to projects with uncommitted changes when
+    /// `show_git_status` is on
+    #[serde(default = "default_dirty_marker")]
+    pub dirty_marker: String,
+    /// Line layout in fzf mode
+    #[serde(default)]
+    pub fzf_format: FzfFormat,
+    /// Remote to read a local repo's host and web URL from when it has
+    /// several; unset means `origin`, then the first GitHub or GitLab remote
+    #[serde(default)]
+    pub preferred_remote: Option<String>,
+    /// Shared JSON or TOML files that contribute `project_dirs` and
+    /// `display_names`; relative paths resolve against this file
+    #[serde(default)]
+    pub include: Vec<PathBuf>,
+    /// What the included files and `SW_EXTRA_DIRS` added, so `save` writes
+    /// back only local values
+    #[serde(skip)]
+    pub included: IncludedSettings,
+}
+
+/// Identifies a document written by `sw config export`
+const BUNDLE_FORMAT: &str = "sw-config-bundle";
+/// Newest bundle layout this version reads and the one it writes
+const BUNDLE_VERSION: u32 = 1;
+
+/// Portable form of the configuration for moving it between machines. Unlike
+/// the config file it is self-contained: included values are inlined.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConfigBundle {
+    pub format: String,
+    pub version: u32,
+    pub config: Config,
+}
+
+/// Settings a config file can pull in through `include`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct IncludedSettings {
+    #[serde(default)]
+    pub project_dirs: Vec<PathBuf>,
+    #[serde(default)]
+    pub display_names: HashMap<String, String>,
+    #[serde(default)]
+    include: Vec<PathBuf>,
+    /// Roots from `SW_EXTRA_DIRS`; scans that include them skip the cache
+    #[serde(skip)]
+    pub extra_dirs: Vec<PathBuf>,
+}
+
+impl IncludedSettings {
+    /// Read an include file, as TOML when it ends in `.toml` and JSON otherwise
+    fn load_from_path(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read included config: {}", path.display()))?;
+
+        if path.extension().is_some_and(|ext| ext == "toml") {
+            toml::from_str(&content)
+                .with_context(|| format!("Failed to parse included config: {}", path.display()))
+        } else {
+            serde_json::from_str(&content)
+                .with_context(|| format!("Failed to parse included config: {}", path.display()))
+        }
+    }
 }
 
 impl Default for Config {
@@ -26,14 +377,91 @@ impl Default for Config {
             github_username: None,
             gitlab_username: None,
             cache_ttl_seconds: 1800,
+            min_query_len: default_min_query_len(),
+            boundary_bonus: default_boundary_bonus(),
+            recency_weight: default_recency_weight(),
+            confirm_clone: false,
+            clone_without_tty: true,
+            clone_concurrency: default_clone_concurrency(),
+            tui_probe_threads: default_tui_probe_threads(),
+            search_case: SearchCase::default(),
+            matcher: MatcherKind::default(),
+            display_names: HashMap::new(),
+            open_command: None,
+            editor_switch_command: None,
+            post_open_command: None,
+            trusted_projects: Vec::new(),
+            post_scan_command: None,
+            scanner_timeout_seconds: default_scanner_timeout_seconds(),
+            open_in_current_window: false,
+            clone_path_template: None,
+            github_active_within_days: None,
+            github_timestamp_field: GitHubTimestampField::default(),
+            github_repo_limit: default_github_repo_limit(),
+            reuse_window: false,
+            prefer_workspace_file: false,
+            open_subpaths: HashMap::new(),
+            on_already_open: AlreadyOpenAction::default(),
+            quick_codes: HashMap::new(),
+            restore_last_query: false,
+            scan_depth: default_scan_depth(),
+            extra_scan_dirs: Vec::new(),
+            reuse_git_timestamps: true,
+            project_markers: default_project_markers(),
+            archive_patterns: default_archive_patterns(),
+            dedup_policy: DedupPolicy::default(),
+            multi_clone: MultiClonePolicy::default(),
+            scanner_order: default_scanner_order(),
+            disabled_scanners: Vec::new(),
+            theme_preset: ThemePreset::default(),
+            theme: ThemeColors::default(),
+            type_icons: default_type_icons(),
+            cursor_restrict_to_project_dirs: false,
+            relative_times: false,
+            show_git_status: false,
+            dirty_marker: default_dirty_marker(),
+            fzf_format: FzfFormat::default(),
+            preferred_remote: None,
+            include: Vec::new(),
+            included: IncludedSettings::default(),
         }
     }
 }
 
 impl Config {
+    /// Load the config file, plus any scan roots in `SW_EXTRA_DIRS` for this
+    /// run only
     pub fn load() -> Result<Self> {
         let config_path = Self::config_file_path()?;
-        Self::load_from_path(&config_path)
+        let mut config = Self::load_from_path(&config_path)?;
+        if let Some(extra_dirs) = std::env::var_os("SW_EXTRA_DIRS") {
+            config.add_extra_dirs(&extra_dirs, |name| std::env::var(name).ok());
+        }
+        Ok(config)
+    }
+
+    /// Append the directories in a `PATH`-style list (`:`-separated, `;` on
+    /// Windows) to `project_dirs` without persisting them. `~` and `$VAR`s
+    /// are expanded through `lookup`.
+    fn add_extra_dirs(
+        &mut self,
+        extra_dirs: &std::ffi::OsStr,
+        lookup: impl Fn(&str) -> Option<String>,
+    ) {
+        for dir in std::env::split_paths(extra_dirs) {
+            let dir = expand_path(&dir.to_string_lossy(), &lookup);
+            if dir.as_os_str().is_empty() || self.project_dirs.contains(&dir) {
+                continue;
+            }
+            self.project_dirs.push(dir.clone());
+            self.included.project_dirs.push(dir.clone());
+            self.included.extra_dirs.push(dir);
+        }
+    }
+
+    /// Whether `SW_EXTRA_DIRS` added scan roots this run
+    pub fn has_extra_dirs(&self) -> bool {
+        !self.included.extra_dirs.is_empty()
     }
 
     pub fn load_from_path<P: AsRef<Path>>(path: P) -> Result<Self> {
@@ -46,10 +474,159 @@ impl Config {
         let content = fs::read_to_string(path)
             .with_context(|| format!("Failed to read config file: {}", path.display()))?;
 
-        let config: Self = serde_json::from_str(&content)
-            .with_context(|| format!("Failed to parse config file: {}", path.display()))?;
+        match serde_json::from_str::<Self>(&content) {
+            Ok(mut config) => {
+                let includes = config.include.clone();
+                config.merge_includes(path, &includes, &mut vec![canonical_or_same(path)])?;
+                Ok(config)
+            }
+            // Empty or cut-off files come from interrupted writes; well-formed
+            // JSON with bad values is left for the user to fix
+            Err(e) if matches!(e.classify(), Category::Eof | Category::Syntax) => {
+                Self::set_aside_broken_file(path, &e);
+                Ok(Self::default())
+            }
+            Err(e) => {
+                Err(e).with_context(|| format!("Failed to parse config file: {}", path.display()))
+            }
+        }
+    }
+
+    /// Merge the files in `includes` (relative to `from`) and whatever they
+    /// include in turn. Values already set win, so the local file overrides
+    /// its includes and earlier includes override later ones. `chain` holds
+    /// the files being merged, to detect cycles.
+    fn merge_includes(
+        &mut self,
+        from: &Path,
+        includes: &[PathBuf],
+        chain: &mut Vec<PathBuf>,
+    ) -> Result<()> {
+        let base = from.parent().unwrap_or(Path::new(""));
+
+        for include in includes {
+            let path = match include.strip_prefix("~") {
+                Ok(rest) => dirs::home_dir().unwrap_or_default().join(rest),
+                Err(_) => base.join(include),
+            };
+            if !path.exists() {
+                eprintln!("Warning: Included config not found: {}", path.display());
+                continue;
+            }
 
-        Ok(config)
+            let canonical = canonical_or_same(&path);
+            if chain.contains(&canonical) {
+                let cycle: Vec<String> = chain
+                    .iter()
+                    .chain(std::iter::once(&canonical))
+                    .map(|path| path.display().to_string())
+                    .collect();
+                anyhow::bail!("Config include cycle: {}", cycle.join(" -> "));
+            }
+
+            let settings = IncludedSettings::load_from_path(&path)?;
+            for dir in settings.project_dirs {
+                if !self.project_dirs.contains(&dir) {
+                    self.project_dirs.push(dir.clone());
+                    self.included.project_dirs.push(dir);
+                }
+            }
+            for (key, name) in settings.display_names {
+                if !self.display_names.contains_key(&key) {
+                    self.display_names.insert(key.clone(), name.clone());
+                    self.included.display_names.insert(key, name);
+                }
+            }
+
+            chain.push(canonical);
+            self.merge_includes(&path, &settings.include, chain)?;
+            chain.pop();
+        }
+
+        Ok(())
+    }
+
+    /// The effective config as a `sw config export` document, with included
+    /// project directories and display names folded in
+    pub fn to_bundle(&self) -> Result<String> {
+        let bundle = ConfigBundle {
+            format: BUNDLE_FORMAT.to_string(),
+            version: BUNDLE_VERSION,
+            config: Config {
+                include: Vec::new(),
+                included: IncludedSettings::default(),
+                ..self.clone()
+            },
+        };
+        serde_json::to_string_pretty(&bundle).context("Failed to serialize config bundle")
+    }
+
+    /// Read and validate a `sw config export` document
+    pub fn from_bundle(content: &str) -> Result<Self> {
+        let bundle: ConfigBundle =
+            serde_json::from_str(content).context("Failed to parse config bundle")?;
+        if bundle.format != BUNDLE_FORMAT {
+            anyhow::bail!(
+                "Not a sw config export (format '{}', expected '{}')",
+                bundle.format,
+                BUNDLE_FORMAT
+            );
+        }
+        if bundle.version > BUNDLE_VERSION {
+            anyhow::bail!(
+                "Config bundle version {} is newer than this sw supports ({})",
+                bundle.version,
+                BUNDLE_VERSION
+            );
+        }
+
+        bundle.config.validate()?;
+        Ok(bundle.config)
+    }
+
+    /// The config as written to disk: without values that came from includes
+    fn local_only(&self) -> Self {
+        let mut local = self.clone();
+        local
+            .project_dirs
+            .retain(|dir| !self.included.project_dirs.contains(dir));
+        local
+            .display_names
+            .retain(|key, _| !self.included.display_names.contains_key(key));
+        local
+    }
+
+    /// Move an unreadable config to `<name>.bak` so the defaults can be
+    /// saved in its place. Earlier backups are kept: if `<name>.bak` exists
+    /// the first free `<name>.bak.N` is used.
+    fn set_aside_broken_file(path: &Path, error: &serde_json::Error) {
+        let backup_name = |n: usize| {
+            let mut backup = path.as_os_str().to_owned();
+            backup.push(".bak");
+            if n > 0 {
+                backup.push(format!(".{}", n));
+            }
+            PathBuf::from(backup)
+        };
+        let backup = (0..)
+            .map(backup_name)
+            .find(|backup| !backup.exists())
+            .expect("some backup name is free");
+
+        match fs::rename(path, &backup) {
+            Ok(()) => eprintln!(
+                "Warning: Config file {} is empty or corrupt ({}); moved it to {} and using defaults",
+                path.display(),
+                error,
+                backup.display()
+            ),
+            Err(e) => eprintln!(
+                "Warning: Config file {} is empty or corrupt ({}); using defaults (backup failed: {})",
+                path.display(),
+                error,
+                e
+            ),
+        }
     }
 
     pub fn save(&self) -> Result<()> {
@@ -66,7 +643,8 @@ impl Config {
             })?;
         }
 
-        let content = serde_json::to_string_pretty(self).context("Failed to serialize config")?;
+        let content = serde_json::to_string_pretty(&self.local_only())
+            .context("Failed to serialize config")?;
 
         fs::write(path, content)
             .with_context(|| format!("Failed to write config file: {}", path.display()))?;
@@ -74,7 +652,17 @@ impl Config {
         Ok(())
     }
 
+    /// Use `path` instead of the platform config file for the rest of this
+    /// process, for both loading and saving. Only the first call has effect.
+    pub fn use_config_file(path: PathBuf) {
+        let _ = CONFIG_FILE_OVERRIDE.set(path);
+    }
+
     pub fn config_file_path() -> Result<PathBuf> {
+        if let Some(path) = CONFIG_FILE_OVERRIDE.get() {
+            return Ok(path.clone());
+        }
+
         let project_dirs =
             ProjectDirs::from("", "", "sw").context("Failed to determine config directory")?;
 
@@ -88,13 +676,135 @@ impl Config {
         Ok(project_dirs.cache_dir().to_path_buf())
     }
 
+    /// Directory for persistent state (usage history, last query) that must
+    /// survive cache clears. Honors `SW_DATA_DIR`, then `SW_HOME/data`.
+    pub fn data_dir_path() -> Result<PathBuf> {
+        resolve_data_dir(std::env::var_os("SW_DATA_DIR"), std::env::var_os("SW_HOME"))
+    }
+
+    pub fn usage_log_path() -> Result<PathBuf> {
+        Ok(Self::data_dir_path()?.join("usage.json"))
+    }
+
+    /// Where the TUI remembers its last query and selection
+    pub fn tui_session_path() -> Result<PathBuf> {
+        Ok(Self::data_dir_path()?.join("tui_session.json"))
+    }
+
+    /// Display name configured for the project at `path`, if any
+    pub fn display_name_for(&self, path: &Path) -> Option<&str> {
+        if self.display_names.is_empty() {
+            return None;
+        }
+        self.display_names
+            .get(&display_name_key(path))
+            .map(String::as_str)
+    }
+
+    /// Subpath configured to open in place of the project at `path`, if any
+    pub fn open_subpath_for(&self, path: &Path) -> Option<&Path> {
+        if self.open_subpaths.is_empty() {
+            return None;
+        }
+        self.open_subpaths
+            .get(&display_name_key(path))
+            .map(PathBuf::as_path)
+    }
+
+    /// Whether `path` is one of the `trusted_projects`
+    pub fn trusts_project(&self, path: &Path) -> bool {
+        let key = display_name_key(path);
+        self.trusted_projects.iter().any(|dir| {
+            let dir = expand_path(&dir.to_string_lossy(), |name| std::env::var(name).ok());
+            display_name_key(&dir) == key
+        })
+    }
+
+    /// Set the display name for `path`; an empty name removes the override
+    pub fn set_display_name(&mut self, path: &Path, name: &str) {
+        let key = display_name_key(path);
+        // Set locally now, so it is saved and overrides the include
+        self.included.display_names.remove(&key);
+        if name.is_empty() {
+            self.display_names.remove(&key);
+        } else {
+            self.display_names.insert(key, name.to_string());
+        }
+    }
+
+    /// Open projects with `editor` for this run, ahead of any configured
+    /// `open_command` or `editor_switch_command`
+    pub fn override_editor(&mut self, editor: &str) {
+        self.editor_command = editor.to_string();
+        self.open_command = None;
+        self.editor_switch_command = None;
+    }
+
+    /// The config to open a project picked interactively with:
+    /// `editor_switch_command`, when set, takes the place of `open_command`
+    pub fn for_switching(&self) -> Config {
+        let mut config = self.clone();
+        if let Some(command) = self
+            .editor_switch_command
+            .as_ref()
+            .filter(|command| !command.trim().is_empty())
+        {
+            config.open_command = Some(command.clone());
+        }
+        config
+    }
+
+    /// Whether `disabled_scanners` lists the scanner called `name`
+    pub fn is_scanner_disabled(&self, name: &str) -> bool {
+        self.disabled_scanners
+            .iter()
+            .any(|disabled| disabled.trim().eq_ignore_ascii_case(name))
+    }
+
+    /// Sources in `scanner_order`, highest priority first
+    pub fn source_priority(&self) -> Vec<ProjectSource> {
+        self.scanner_order
+            .iter()
+            .cloned()
+            .map(ProjectSource::from)
+            .collect()
+    }
+
+    /// Icon for the project's detected type, if it has one configured
+    pub fn type_icon(&self, project: &Project) -> Option<&str> {
+        let project_type = project.project_type.as_deref()?;
+        self.type_icons
+            .get(project_type)
+            .map(String::as_str)
+            .filter(|icon| !icon.is_empty())
+    }
+
+    /// Directories to scan: `project_dirs`, then `extra_scan_dirs`, with
+    /// glob entries expanded to every matching directory
+    pub fn scan_roots(&self) -> Vec<PathBuf> {
+        let extra_dirs = self
+            .extra_scan_dirs
+            .iter()
+            .map(|dir| expand_path(&dir.to_string_lossy(), |name| std::env::var(name).ok()));
+
+        let mut roots: Vec<PathBuf> = Vec::new();
+        for dir in self.project_dirs.iter().cloned().chain(extra_dirs) {
+            for root in expand_project_dir(&dir) {
+                if !roots.contains(&root) {
+                    roots.push(root);
+                }
+            }
+        }
+        roots
+    }
+
     pub fn validate(&self) -> Result<()> {
         if self.editor_command.trim().is_empty() {
             anyhow::bail!("Editor command cannot be empty");
         }
 
         for dir in &self.project_dirs {
-            if !dir.exists() {
+            if !is_glob_pattern(dir) && !dir.exists() {
                 eprintln!(
                     "Warning: Project directory does not exist: {}",
                     dir.display()
@@ -128,6 +838,16 @@ impl Config {
         }
     }
 
+    /// The editor command to run, with the `$EDITOR` sentinel resolved
+    /// against the current environment
+    pub fn resolved_editor_command(&self) -> String {
+        resolve_editor_command(
+            &self.editor_command,
+            std::env::var("EDITOR").ok(),
+            std::env::var("VISUAL").ok(),
+        )
+    }
+
     #[allow(dead_code)]
     pub fn set_editor(&mut self, editor: String) {
         self.editor_command = editor;
@@ -146,6 +866,119 @@ impl Config {
     }
 }
 
+fn resolve_data_dir(
+    data_dir_override: Option<std::ffi::OsString>,
+    sw_home: Option<std::ffi::OsString>,
+) -> Result<PathBuf> {
+    if let Some(dir) = data_dir_override.filter(|d| !d.is_empty()) {
+        return Ok(PathBuf::from(dir));
+    }
+
+    if let Some(home) = sw_home.filter(|h| !h.is_empty()) {
+        return Ok(PathBuf::from(home).join("data"));
+    }
+
+    let project_dirs =
+        ProjectDirs::from("", "", "sw").context("Failed to determine data directory")?;
+
+    Ok(project_dirs.data_dir().to_path_buf())
+}
+
+/// Canonical form of a project path used to key `display_names` and
+/// `open_subpaths`
+fn display_name_key(path: &Path) -> String {
+    fs::canonicalize(path)
+        .unwrap_or_else(|_| path.to_path_buf())
+        .to_string_lossy()
+        .into_owned()
+}
+
+fn default_clone_concurrency() -> usize {
+    2
+}
+
+fn default_tui_probe_threads() -> usize {
+    8
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_min_query_len() -> usize {
+    1
+}
+
+fn default_github_repo_limit() -> usize {
+    5000
+}
+
+fn default_boundary_bonus() -> i64 {
+    30
+}
+
+fn default_recency_weight() -> i64 {
+    20
+}
+
+fn default_dirty_marker() -> String {
+    crate::models::DEFAULT_DIRTY_MARKER.to_string()
+}
+
+fn default_scanner_timeout_seconds() -> u64 {
+    60
+}
+
+fn default_scan_depth() -> usize {
+    3
+}
+
+fn default_project_markers() -> Vec<String> {
+    [".git", "flake.nix", "shell.nix", ".envrc"]
+        .iter()
+        .map(|marker| marker.to_string())
+        .collect()
+}
+
+fn default_archive_patterns() -> Vec<String> {
+    vec!["**/archive/*".to_string()]
+}
+
+fn default_scanner_order() -> Vec<String> {
+    ["local", "cursor", "github", "gitlab"]
+        .iter()
+        .map(|name| name.to_string())
+        .collect()
+}
+
+fn default_type_icons() -> HashMap<String, String> {
+    [
+        ("rust", "🦀"),
+        ("node", "🟢"),
+        ("python", "🐍"),
+        ("go", "🐹"),
+        ("nix", "❄️"),
+    ]
+    .iter()
+    .map(|(project_type, icon)| (project_type.to_string(), icon.to_string()))
+    .collect()
+}
+
+/// Value of `editor_command` that defers to the environment at open time
+pub const ENV_EDITOR_SENTINEL: &str = "$EDITOR";
+
+fn resolve_editor_command(command: &str, editor: Option<String>, visual: Option<String>) -> String {
+    if command.trim() != ENV_EDITOR_SENTINEL {
+        return command.to_string();
+    }
+
+    [editor, visual]
+        .into_iter()
+        .flatten()
+        .find(|value| !value.trim().is_empty())
+        .unwrap_or_else(detect_installed_editor)
+}
+
 fn detect_default_editor() -> String {
     if let Ok(editor) = std::env::var("EDITOR") {
         return editor;
@@ -155,6 +988,10 @@ fn detect_default_editor() -> String {
         return visual;
     }
 
+    detect_installed_editor()
+}
+
+fn detect_installed_editor() -> String {
     let editors = ["cursor", "code", "vim", "nvim", "nano"];
     for editor in &editors {
         if which::which(editor).is_ok() {
@@ -165,6 +1002,113 @@ fn detect_default_editor() -> String {
     "vim".to_string()
 }
 
+fn is_glob_pattern(path: &Path) -> bool {
+    path.to_string_lossy().contains(['*', '?', '[', '{'])
+}
+
+/// `path` resolved through symlinks, or as given if it can't be
+fn canonical_or_same(path: &Path) -> PathBuf {
+    fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// Expand a leading `~` and `$VAR` / `${VAR}` references in `raw`. Unknown
+/// variables expand to nothing.
+fn expand_path(raw: &str, lookup: impl Fn(&str) -> Option<String>) -> PathBuf {
+    let mut expanded = String::new();
+    let mut rest = raw.trim();
+
+    if let Some(after) = rest.strip_prefix('~') {
+        if after.is_empty() || after.starts_with(['/', '\\']) {
+            if let Some(home) = dirs::home_dir() {
+                expanded.push_str(&home.to_string_lossy());
+                rest = after;
+            }
+        }
+    }
+
+    while let Some(dollar) = rest.find('$') {
+        expanded.push_str(&rest[..dollar]);
+        let after = &rest[dollar + 1..];
+        let (name, remainder) = match after.strip_prefix('{') {
+            Some(braced) => match braced.split_once('}') {
+                Some((name, remainder)) => (name, remainder),
+                None => (braced, ""),
+            },
+            None => {
+                let end = after
+                    .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                    .unwrap_or(after.len());
+                after.split_at(end)
+            }
+        };
+        if name.is_empty() {
+            expanded.push('$');
+        } else {
+            expanded.push_str(&lookup(name).unwrap_or_default());
+        }
+        rest = remainder;
+    }
+    expanded.push_str(rest);
+
+    PathBuf::from(expanded)
+}
+
+/// Directories matching a `project_dirs` entry. Literal entries pass through
+/// unchanged; globs such as `~/work/*/repos` are matched against the
+/// directories below their literal prefix.
+fn expand_project_dir(dir: &Path) -> Vec<PathBuf> {
+    if !is_glob_pattern(dir) {
+        return vec![dir.to_path_buf()];
+    }
+
+    let pattern = match (dir.strip_prefix("~"), dirs::home_dir()) {
+        (Ok(rest), Some(home)) => home.join(rest),
+        _ => dir.to_path_buf(),
+    };
+
+    let matcher = match globset::GlobBuilder::new(&pattern.to_string_lossy())
+        .literal_separator(true)
+        .build()
+    {
+        Ok(glob) => glob.compile_matcher(),
+        Err(e) => {
+            eprintln!(
+                "Warning: Invalid glob in project_dirs '{}': {}",
+                dir.display(),
+                e
+            );
+            return Vec::new();
+        }
+    };
+
+    let mut base = PathBuf::new();
+    let mut remaining = Vec::new();
+    for component in pattern.components() {
+        if remaining.is_empty() && !is_glob_pattern(Path::new(component.as_os_str())) {
+            base.push(component);
+        } else {
+            remaining.push(component);
+        }
+    }
+
+    let max_depth = if remaining.iter().any(|c| c.as_os_str() == "**") {
+        usize::MAX
+    } else {
+        remaining.len()
+    };
+
+    let mut matches: Vec<PathBuf> = walkdir::WalkDir::new(&base)
+        .min_depth(1)
+        .max_depth(max_depth)
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_dir() && matcher.is_match(entry.path()))
+        .map(|entry| entry.into_path())
+        .collect();
+    matches.sort();
+    matches
+}
+
 fn default_project_dirs() -> Vec<PathBuf> {
     let mut dirs = Vec::new();
 
@@ -223,6 +1167,63 @@ mod tests {
         assert!(config.gitlab_username.is_none());
     }
 
+    #[test]
+    fn test_glob_project_dir_expands_to_matching_directories() {
+        let temp_dir = TempDir::new().unwrap();
+        let work = temp_dir.path().join("work");
+        for client in ["acme", "globex"] {
+            fs::create_dir_all(work.join(client).join("repos")).unwrap();
+        }
+        fs::create_dir_all(work.join("initech")).unwrap();
+        fs::write(work.join("notes.txt"), "").unwrap();
+
+        let config = Config {
+            project_dirs: vec![work.join("*").join("repos")],
+            ..Config::default()
+        };
+
+        assert_eq!(
+            config.scan_roots(),
+            vec![
+                work.join("acme").join("repos"),
+                work.join("globex").join("repos"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_extra_scan_dirs_follow_project_dirs() {
+        let home = dirs::home_dir().unwrap();
+        let config = Config {
+            project_dirs: vec![PathBuf::from("/home/user/projects")],
+            extra_scan_dirs: vec![
+                PathBuf::from("~/scratch"),
+                PathBuf::from("/home/user/projects"),
+            ],
+            ..Config::default()
+        };
+
+        assert_eq!(
+            config.scan_roots(),
+            vec![PathBuf::from("/home/user/projects"), home.join("scratch")]
+        );
+        // The primary list stays as configured
+        assert_eq!(config.project_dirs.len(), 1);
+    }
+
+    #[test]
+    fn test_literal_project_dir_is_unaffected() {
+        let config = Config {
+            project_dirs: vec![
+                PathBuf::from("/does/not/exist"),
+                PathBuf::from("/home/user/projects"),
+            ],
+            ..Config::default()
+        };
+
+        assert_eq!(config.scan_roots(), config.project_dirs);
+    }
+
     #[test]
     fn test_config_serialization() {
         let config = Config {
@@ -231,6 +1232,7 @@ mod tests {
             github_username: Some("testuser".to_string()),
             gitlab_username: Some("testuser".to_string()),
             cache_ttl_seconds: 600,
+            ..Config::default()
         };
 
         let json = serde_json::to_string(&config).unwrap();
@@ -250,6 +1252,7 @@ mod tests {
             github_username: Some("testuser".to_string()),
             gitlab_username: Some("testuser".to_string()),
             cache_ttl_seconds: 900,
+            ..Config::default()
         };
 
         original_config.save_to_path(&config_path).unwrap();
@@ -322,16 +1325,341 @@ mod tests {
     }
 
     #[test]
-    fn test_config_with_invalid_json() {
+    fn test_override_editor_beats_open_command() {
+        let mut config = Config {
+            editor_command: "code".to_string(),
+            open_command: Some("open {path}".to_string()),
+            ..Config::default()
+        };
+
+        config.editor_switch_command = Some("code -r {path}".to_string());
+
+        config.override_editor("zed");
+
+        assert_eq!(config.editor_command, "zed");
+        assert_eq!(config.open_command, None);
+        assert_eq!(config.for_switching().open_command, None);
+    }
+
+    #[test]
+    fn test_switch_command_replaces_open_command_when_switching() {
+        let config = Config {
+            open_command: Some("open {path}".to_string()),
+            editor_switch_command: Some("code -r {path}".to_string()),
+            ..Config::default()
+        };
+        assert_eq!(
+            config.for_switching().open_command.as_deref(),
+            Some("code -r {path}")
+        );
+        assert_eq!(config.open_command.as_deref(), Some("open {path}"));
+
+        let without = Config {
+            open_command: Some("open {path}".to_string()),
+            editor_switch_command: Some("  ".to_string()),
+            ..Config::default()
+        };
+        assert_eq!(
+            without.for_switching().open_command.as_deref(),
+            Some("open {path}")
+        );
+    }
+
+    #[test]
+    fn test_include_merges_shared_settings() {
+        use tempfile::TempDir;
+
+        let temp_dir = TempDir::new().unwrap();
+        fs::create_dir_all(temp_dir.path().join("team")).unwrap();
+        fs::write(
+            temp_dir.path().join("team/roots.toml"),
+            "project_dirs = [\"/team/services\", \"/code\"]\ninclude = [\"more.json\"]\n\n[display_names]\n\"/code/api\" = \"Team API\"\n\"/code/web\" = \"Web\"\n",
+        )
+        .unwrap();
+        fs::write(
+            temp_dir.path().join("team/more.json"),
+            r#"{"project_dirs": ["/team/tools"]}"#,
+        )
+        .unwrap();
+        let config_path = temp_dir.path().join("config.json");
+        fs::write(
+            &config_path,
+            r#"{"editor_command": "vim", "project_dirs": ["/code"], "cache_ttl_seconds": 60,
+                "display_names": {"/code/api": "My API"}, "include": ["team/roots.toml"]}"#,
+        )
+        .unwrap();
+
+        let config = Config::load_from_path(&config_path).unwrap();
+        assert_eq!(
+            config.project_dirs,
+            vec![
+                PathBuf::from("/code"),
+                PathBuf::from("/team/services"),
+                PathBuf::from("/team/tools")
+            ]
+        );
+        assert_eq!(config.display_names["/code/api"], "My API");
+        assert_eq!(config.display_names["/code/web"], "Web");
+
+        // Saving keeps included values out of the local file
+        config.save_to_path(&config_path).unwrap();
+        let saved: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&config_path).unwrap()).unwrap();
+        assert_eq!(saved["project_dirs"], serde_json::json!(["/code"]));
+        assert_eq!(
+            saved["display_names"],
+            serde_json::json!({"/code/api": "My API"})
+        );
+        assert_eq!(Config::load_from_path(&config_path).unwrap(), config);
+    }
+
+    #[test]
+    fn test_bundle_round_trip_preserves_all_fields() {
+        let mut config = Config {
+            editor_command: "code".to_string(),
+            project_dirs: vec![PathBuf::from("/code"), PathBuf::from("~/work/*")],
+            github_username: Some("octocat".to_string()),
+            gitlab_username: Some("tanuki".to_string()),
+            cache_ttl_seconds: 42,
+            min_query_len: 2,
+            confirm_clone: true,
+            post_open_command: Some("tmux new -s {path}".to_string()),
+            open_command: Some("open {path}".to_string()),
+            scan_depth: 3,
+            dedup_policy: DedupPolicy::PreferRemoteMetadata,
+            disabled_scanners: vec!["cursor".to_string()],
+            theme_preset: ThemePreset::Light,
+            on_already_open: AlreadyOpenAction::Noop,
+            github_repo_limit: 10,
+            boundary_bonus: 5,
+            ..Config::default()
+        };
+        config.theme.primary = Some("#000000".to_string());
+        config.quick_codes.insert('a', "api".to_string());
+        config
+            .display_names
+            .insert("/code/api".to_string(), "API".to_string());
+
+        let exported = config.to_bundle().unwrap();
+        let imported = Config::from_bundle(&exported).unwrap();
+        assert_eq!(imported, config);
+
+        let value: serde_json::Value = serde_json::from_str(&exported).unwrap();
+        assert_eq!(value["format"], "sw-config-bundle");
+        assert_eq!(value["version"], 1);
+    }
+
+    #[test]
+    fn test_bundle_inlines_includes_and_rejects_other_documents() {
+        let mut config = Config {
+            project_dirs: vec![PathBuf::from("/code"), PathBuf::from("/team")],
+            include: vec![PathBuf::from("team.json")],
+            ..Config::default()
+        };
+        config.included.project_dirs.push(PathBuf::from("/team"));
+
+        let imported = Config::from_bundle(&config.to_bundle().unwrap()).unwrap();
+        assert_eq!(imported.project_dirs, config.project_dirs);
+        assert!(imported.include.is_empty());
+        assert_eq!(imported.included, IncludedSettings::default());
+
+        let plain_config = serde_json::to_string(&Config::default()).unwrap();
+        assert!(Config::from_bundle(&plain_config).is_err());
+
+        let wrong_format = r#"{"format": "other", "version": 1, "config": {"editor_command": "vim", "project_dirs": [], "cache_ttl_seconds": 60}}"#;
+        let err = Config::from_bundle(wrong_format).unwrap_err();
+        assert!(
+            err.to_string().contains("Not a sw config export"),
+            "{}",
+            err
+        );
+
+        let newer = wrong_format
+            .replace("\"other\"", "\"sw-config-bundle\"")
+            .replace("\"version\": 1", "\"version\": 99");
+        assert!(Config::from_bundle(&newer).is_err());
+
+        let invalid = wrong_format
+            .replace("\"other\"", "\"sw-config-bundle\"")
+            .replace("\"cache_ttl_seconds\": 60", "\"cache_ttl_seconds\": 0");
+        assert!(Config::from_bundle(&invalid).is_err());
+    }
+
+    #[test]
+    fn test_quick_codes_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("config.json");
+
+        let mut config = Config::default();
+        config.quick_codes.insert('a', "my-project".to_string());
+        config.save_to_path(&config_path).unwrap();
+
+        let saved: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&config_path).unwrap()).unwrap();
+        assert_eq!(saved["quick_codes"], serde_json::json!({"a": "my-project"}));
+
+        let loaded = Config::load_from_path(&config_path).unwrap();
+        assert_eq!(loaded.quick_codes[&'a'], "my-project");
+    }
+
+    #[test]
+    fn test_expand_path() {
+        let lookup = |name: &str| (name == "SCRATCH").then(|| "/tmp/scratch".to_string());
+        let home = dirs::home_dir().unwrap();
+
+        assert_eq!(expand_path("~/code", lookup), home.join("code"));
+        assert_eq!(expand_path("~", lookup), home);
+        assert_eq!(
+            expand_path("$SCRATCH/a", lookup),
+            PathBuf::from("/tmp/scratch/a")
+        );
+        assert_eq!(
+            expand_path("${SCRATCH}b", lookup),
+            PathBuf::from("/tmp/scratchb")
+        );
+        assert_eq!(expand_path("/x/$MISSING/y", lookup), PathBuf::from("/x//y"));
+        assert_eq!(expand_path("/cost$", lookup), PathBuf::from("/cost$"));
+        assert_eq!(
+            expand_path("~user/code", lookup),
+            PathBuf::from("~user/code")
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_extra_dirs_are_scanned_but_not_saved() {
+        use crate::scanner::ProjectScanner;
+
+        let temp_dir = TempDir::new().unwrap();
+        let experiments = temp_dir.path().join("experiments");
+        fs::create_dir_all(experiments.join("spike/.git")).unwrap();
+        let config_path = temp_dir.path().join("config.json");
+        fs::write(
+            &config_path,
+            r#"{"editor_command": "vim", "project_dirs": ["/code"], "cache_ttl_seconds": 60}"#,
+        )
+        .unwrap();
+
+        let mut config = Config::load_from_path(&config_path).unwrap();
+        let root = temp_dir.path().to_string_lossy().to_string();
+        let lookup = |name: &str| (name == "ROOT").then(|| root.clone());
+        config.add_extra_dirs("$ROOT/experiments::/code".as_ref(), lookup);
+
+        assert_eq!(
+            config.project_dirs,
+            vec![PathBuf::from("/code"), experiments.clone()]
+        );
+        assert_eq!(config.included.extra_dirs, vec![experiments.clone()]);
+        let projects = crate::scanner::local::LocalScanner.scan(&config).unwrap();
+        assert!(projects
+            .projects()
+            .iter()
+            .any(|project| project.path == experiments.join("spike")));
+
+        config.save_to_path(&config_path).unwrap();
+        let saved: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&config_path).unwrap()).unwrap();
+        assert_eq!(saved["project_dirs"], serde_json::json!(["/code"]));
+    }
+
+    #[test]
+    fn test_include_cycle_is_an_error() {
+        use tempfile::TempDir;
+
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("a.json"),
+            r#"{"project_dirs": ["/a"], "include": ["b.json"]}"#,
+        )
+        .unwrap();
+        fs::write(
+            temp_dir.path().join("b.json"),
+            r#"{"project_dirs": ["/b"], "include": ["a.json"]}"#,
+        )
+        .unwrap();
+        let config_path = temp_dir.path().join("config.json");
+        fs::write(
+            &config_path,
+            r#"{"editor_command": "vim", "project_dirs": [], "cache_ttl_seconds": 60,
+                "include": ["a.json"]}"#,
+        )
+        .unwrap();
+
+        let err = Config::load_from_path(&config_path).unwrap_err();
+        assert!(err.to_string().contains("include cycle"), "{}", err);
+
+        // Including yourself is the shortest cycle
+        fs::write(
+            &config_path,
+            r#"{"editor_command": "vim", "project_dirs": [], "cache_ttl_seconds": 60,
+                "include": ["config.json"]}"#,
+        )
+        .unwrap();
+        assert!(Config::load_from_path(&config_path).is_err());
+    }
+
+    #[test]
+    fn test_config_with_invalid_values_is_an_error() {
         use std::io::Write;
         use tempfile::NamedTempFile;
 
         let mut temp_file = NamedTempFile::new().unwrap();
-        writeln!(temp_file, "invalid json").unwrap();
+        writeln!(temp_file, r#"{{"cache_ttl_seconds": "soon"}}"#).unwrap();
         let temp_path = temp_file.path();
 
         let result = Config::load_from_path(temp_path);
         assert!(result.is_err());
+        assert!(temp_path.exists());
+    }
+
+    #[test]
+    fn test_broken_config_falls_back_to_defaults_with_backup() {
+        use tempfile::TempDir;
+
+        for content in [
+            "",
+            "  \n",
+            "invalid json",
+            r#"{"editor_command": "vim", "project_"#,
+        ] {
+            let temp_dir = TempDir::new().unwrap();
+            let config_path = temp_dir.path().join("config.json");
+            fs::write(&config_path, content).unwrap();
+
+            let config = Config::load_from_path(&config_path).unwrap();
+            assert_eq!(
+                config.cache_ttl_seconds,
+                Config::default().cache_ttl_seconds
+            );
+            assert_eq!(config.editor_command, Config::default().editor_command);
+
+            let backup = temp_dir.path().join("config.json.bak");
+            assert_eq!(fs::read_to_string(&backup).unwrap(), content);
+            assert!(!config_path.exists());
+        }
+    }
+
+    #[test]
+    fn test_broken_config_backup_keeps_earlier_backups() {
+        use tempfile::TempDir;
+
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("config.json");
+        for content in ["first", "second", "third"] {
+            fs::write(&config_path, content).unwrap();
+            Config::load_from_path(&config_path).unwrap();
+        }
+
+        for (name, content) in [
+            ("config.json.bak", "first"),
+            ("config.json.bak.1", "second"),
+            ("config.json.bak.2", "third"),
+        ] {
+            assert_eq!(
+                fs::read_to_string(temp_dir.path().join(name)).unwrap(),
+                content
+            );
+        }
     }
 
     #[test]
@@ -370,6 +1698,7 @@ mod tests {
             github_username: None,
             gitlab_username: None,
             cache_ttl_seconds: 1800,
+            ..Config::default()
         };
         assert!(config_without_github.should_prompt_github_setup());
 
@@ -379,6 +1708,7 @@ mod tests {
             github_username: Some("testuser".to_string()),
             gitlab_username: None,
             cache_ttl_seconds: 1800,
+            ..Config::default()
         };
         assert!(!config_with_github.should_prompt_github_setup());
     }
@@ -391,6 +1721,7 @@ mod tests {
             github_username: None,
             gitlab_username: Some("gitlab_user".to_string()),
             cache_ttl_seconds: 1800,
+            ..Config::default()
         };
 
         assert_eq!(config.gitlab_username, Some("gitlab_user".to_string()));
@@ -404,6 +1735,7 @@ mod tests {
             github_username: Some("gh_user".to_string()),
             gitlab_username: Some("gl_user".to_string()),
             cache_ttl_seconds: 3600,
+            ..Config::default()
         };
 
         let json = serde_json::to_string(&config).unwrap();
@@ -412,4 +1744,63 @@ mod tests {
         assert_eq!(config, deserialized);
         assert_eq!(deserialized.gitlab_username, Some("gl_user".to_string()));
     }
+
+    #[test]
+    fn test_data_dir_distinct_from_cache_dir() {
+        let data_dir = resolve_data_dir(None, None).unwrap();
+        let cache_dir = Config::cache_dir_path().unwrap();
+
+        assert_ne!(data_dir, cache_dir);
+        assert!(!data_dir.starts_with(&cache_dir));
+    }
+
+    #[test]
+    fn test_data_dir_overrides() {
+        let data_dir = resolve_data_dir(Some("/custom/data".into()), Some("/sw-home".into()));
+        assert_eq!(data_dir.unwrap(), PathBuf::from("/custom/data"));
+
+        let data_dir = resolve_data_dir(None, Some("/sw-home".into()));
+        assert_eq!(data_dir.unwrap(), PathBuf::from("/sw-home/data"));
+
+        let data_dir = resolve_data_dir(Some("".into()), None).unwrap();
+        assert_eq!(data_dir, resolve_data_dir(None, None).unwrap());
+    }
+
+    #[test]
+    fn test_display_names() {
+        let mut config = Config::default();
+        let path = Path::new("/nonexistent/proj-20231");
+        assert_eq!(config.display_name_for(path), None);
+
+        config.set_display_name(path, "Invoices");
+        assert_eq!(config.display_name_for(path), Some("Invoices"));
+
+        let json = serde_json::to_string(&config).unwrap();
+        let restored: Config = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.display_name_for(path), Some("Invoices"));
+
+        config.set_display_name(path, "");
+        assert_eq!(config.display_name_for(path), None);
+    }
+
+    #[test]
+    fn test_env_editor_sentinel() {
+        assert_eq!(
+            resolve_editor_command("$EDITOR", Some("nvim".to_string()), None),
+            "nvim"
+        );
+        assert_eq!(
+            resolve_editor_command("$EDITOR", Some("hx".to_string()), None),
+            "hx"
+        );
+        assert_eq!(
+            resolve_editor_command("$EDITOR", None, Some("code --wait".to_string())),
+            "code --wait"
+        );
+        assert_eq!(
+            resolve_editor_command("vim", Some("nvim".to_string()), None),
+            "vim"
+        );
+        assert!(!resolve_editor_command("$EDITOR", Some(" ".to_string()), None).is_empty());
+    }
 }
@@ -0,0 +1,194 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::cache::atomic_write;
+use crate::config::Config;
+use crate::models::ProjectList;
+
+/// How deep below a project the size walk goes; deeper files are not counted
+pub const MAX_SIZE_DEPTH: usize = 24;
+
+/// Total size of the regular files under `path`, up to `max_depth` levels
+/// down. Symlinks are not followed and unreadable entries are skipped.
+pub fn directory_size(path: &Path, max_depth: usize) -> u64 {
+    walkdir::WalkDir::new(path)
+        .max_depth(max_depth)
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file())
+        .filter_map(|entry| entry.metadata().ok())
+        .map(|metadata| metadata.len())
+        .sum()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct SizeEntry {
+    modified_secs: u64,
+    size_bytes: u64,
+    /// When the size was computed; entries from before this was recorded
+    /// read as 0 and are always recomputed
+    #[serde(default)]
+    computed_secs: u64,
+}
+
+fn unix_secs(time: SystemTime) -> Option<u64> {
+    Some(time.duration_since(UNIX_EPOCH).ok()?.as_secs())
+}
+
+/// Computed project sizes, reused while the project directory's mtime is
+/// unchanged and the entry is younger than the TTL. The mtime alone misses
+/// changes deeper in the tree, which the TTL bounds.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct SizeCache {
+    entries: HashMap<PathBuf, SizeEntry>,
+}
+
+impl SizeCache {
+    pub fn cache_path() -> Result<PathBuf> {
+        Ok(Config::cache_dir_path()?.join("sizes.json"))
+    }
+
+    pub fn load_from_path(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read size cache: {}", path.display()))?;
+        serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse size cache: {}", path.display()))
+    }
+
+    pub fn save_to_path(&self, path: &Path) -> Result<()> {
+        let data = serde_json::to_vec(self).context("Failed to serialize size cache")?;
+        atomic_write(path, &data)
+    }
+
+    /// Size of the directory at `path`, or `None` if it doesn't exist. A
+    /// cached size is reused for up to `ttl_seconds`.
+    pub fn size_of(&mut self, path: &Path, ttl_seconds: u64) -> Option<u64> {
+        let modified_secs = unix_secs(fs::metadata(path).ok()?.modified().ok()?)?;
+        let now_secs = unix_secs(SystemTime::now()).unwrap_or_default();
+
+        if let Some(entry) = self.entries.get(path) {
+            if entry.modified_secs == modified_secs
+                && now_secs.saturating_sub(entry.computed_secs) < ttl_seconds
+            {
+                return Some(entry.size_bytes);
+            }
+        }
+
+        let size_bytes = directory_size(path, MAX_SIZE_DEPTH);
+        self.entries.insert(
+            path.to_path_buf(),
+            SizeEntry {
+                modified_secs,
+                size_bytes,
+                computed_secs: now_secs,
+            },
+        );
+        Some(size_bytes)
+    }
+}
+
+/// Fill in `size_bytes` for every project that exists on disk, reusing
+/// sizes computed within `cache_ttl_seconds`
+pub fn populate_sizes(project_list: &mut ProjectList, config: &Config) -> Result<()> {
+    let cache_path = SizeCache::cache_path()?;
+    let mut cache = SizeCache::load_from_path(&cache_path).unwrap_or_default();
+
+    for project in project_list.projects_mut() {
+        project.size_bytes = cache.size_of(&project.path, config.cache_ttl_seconds);
+    }
+
+    if let Err(e) = cache.save_to_path(&cache_path) {
+        eprintln!("Warning: Failed to save size cache: {}", e);
+    }
+    Ok(())
+}
+
+/// Size in the largest unit that keeps the number at or above 1, e.g. `4.2 MB`
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KB", "MB", "GB", "TB"];
+
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", size, UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn create_tree(root: &Path) {
+        fs::create_dir_all(root.join("src/nested")).unwrap();
+        fs::write(root.join("README.md"), vec![b'a'; 100]).unwrap();
+        fs::write(root.join("src/main.rs"), vec![b'b'; 250]).unwrap();
+        fs::write(root.join("src/nested/deep.rs"), vec![b'c'; 650]).unwrap();
+    }
+
+    #[test]
+    fn test_directory_size_sums_files() {
+        let temp_dir = TempDir::new().unwrap();
+        create_tree(temp_dir.path());
+
+        assert_eq!(directory_size(temp_dir.path(), MAX_SIZE_DEPTH), 1000);
+        assert_eq!(directory_size(temp_dir.path(), 2), 350);
+    }
+
+    #[test]
+    fn test_size_cache_reuses_until_mtime_changes() {
+        let temp_dir = TempDir::new().unwrap();
+        let project = temp_dir.path().join("project");
+        create_tree(&project);
+
+        let ttl = 3600;
+        let mut cache = SizeCache::default();
+        assert_eq!(cache.size_of(&project, ttl), Some(1000));
+
+        // A stale entry with the current mtime is trusted as-is
+        cache.entries.get_mut(&project).unwrap().size_bytes = 1;
+        assert_eq!(cache.size_of(&project, ttl), Some(1));
+
+        cache.entries.get_mut(&project).unwrap().modified_secs = 0;
+        assert_eq!(cache.size_of(&project, ttl), Some(1000));
+
+        assert_eq!(cache.size_of(&temp_dir.path().join("missing"), ttl), None);
+    }
+
+    #[test]
+    fn test_size_cache_expires_after_ttl() {
+        let temp_dir = TempDir::new().unwrap();
+        let project = temp_dir.path().join("project");
+        create_tree(&project);
+
+        let mut cache = SizeCache::default();
+        assert_eq!(cache.size_of(&project, 3600), Some(1000));
+
+        // Deep changes leave the root mtime alone
+        fs::write(project.join("src/nested/deep.rs"), vec![b'c'; 150]).unwrap();
+        assert_eq!(cache.size_of(&project, 3600), Some(1000));
+        assert_eq!(cache.size_of(&project, 0), Some(500));
+    }
+
+    #[test]
+    fn test_format_size() {
+        assert_eq!(format_size(512), "512 B");
+        assert_eq!(format_size(2048), "2.0 KB");
+        assert_eq!(format_size(5 * 1024 * 1024 + 512 * 1024), "5.5 MB");
+    }
+}
@@ -1,6 +1,12 @@
 pub mod cache;
+pub mod clipboard;
 pub mod config;
+pub mod disk_usage;
 pub mod models;
 pub mod opener;
+pub mod output;
+pub mod project_settings;
 pub mod scanner;
+pub mod simple_matcher;
 pub mod tui;
+pub mod usage;
@@ -1,16 +1,28 @@
 use anyhow::Result;
-use clap::{CommandFactory, Parser, Subcommand};
+use chrono::{DateTime, Utc};
+use clap::{ArgAction, CommandFactory, Parser, Subcommand};
 use clap_complete::Shell;
 use config::Config;
+use dialoguer::Confirm;
+use models::{ProjectFilter, ProjectSource, Step};
+use project_manager::ScanOptions;
+use std::io::IsTerminal;
+use std::path::PathBuf;
 
 mod cache;
+mod clipboard;
 mod config;
+mod disk_usage;
 mod models;
 mod opener;
 mod operations;
+mod output;
 mod project_manager;
+mod project_settings;
 mod scanner;
+mod simple_matcher;
 mod tui;
+mod usage;
 
 #[derive(Parser)]
 #[command(name = "sw")]
@@ -20,20 +32,113 @@ pub struct Cli {
     #[arg(value_name = "PROJECT")]
     pub project_name: Option<String>,
 
-    #[arg(long, short, conflicts_with_all = ["list", "fzf"])]
+    #[arg(long, short, conflicts_with_all = ["list", "fzf", "simple"])]
     pub interactive: bool,
 
-    #[arg(long, short, conflicts_with_all = ["interactive", "fzf"])]
+    #[arg(long, short, conflicts_with_all = ["interactive", "fzf", "simple"])]
     pub list: bool,
 
-    #[arg(long, conflicts_with_all = ["interactive", "list"])]
+    #[arg(long, conflicts_with_all = ["interactive", "list", "simple"])]
     pub fzf: bool,
 
+    /// Open the project after the last-opened one in the project list
+    #[arg(long, conflicts_with_all = ["prev", "project_name", "interactive", "list", "fzf", "simple"])]
+    pub next: bool,
+
+    /// Open the project before the last-opened one in the project list
+    #[arg(long, conflicts_with_all = ["next", "project_name", "interactive", "list", "fzf", "simple"])]
+    pub prev: bool,
+
+    /// Only open a project whose name equals PROJECT (ignoring case)
+    #[arg(long, requires = "project_name")]
+    pub exact: bool,
+
+    /// Open the best fuzzy match for PROJECT without asking, even when
+    /// several projects match
+    #[arg(long, requires = "project_name", conflicts_with = "exact")]
+    pub first: bool,
+
+    /// Open every project whose name contains PROJECT, cloning missing
+    /// remotes `clone_concurrency` at a time
+    #[arg(long, requires = "project_name", conflicts_with_all = ["exact", "first"])]
+    pub all: bool,
+
+    /// Pick from a numbered list on stdin instead of the full-screen TUI
+    #[arg(long, alias = "plain", conflicts_with_all = ["interactive", "list", "fzf"])]
+    pub simple: bool,
+
+    /// Open the selected project with CMD instead of the configured editor
+    #[arg(long, alias = "editor", value_name = "CMD")]
+    pub open_with: Option<String>,
+
     #[arg(long, short)]
     pub refresh: bool,
 
-    #[arg(long, short)]
-    pub verbose: bool,
+    /// Print diagnostics; repeat (-vv) for command lines and cache decisions
+    #[arg(long, short, action = ArgAction::Count)]
+    pub verbose: u8,
+
+    /// Don't print confirmations or tips, only requested output and errors
+    #[arg(long, short, global = true)]
+    pub quiet: bool,
+
+    /// Print the project list as JSON
+    #[arg(long, global = true, conflicts_with = "porcelain")]
+    pub json: bool,
+
+    /// Print the project list as stable tab-separated lines
+    #[arg(long, global = true, conflicts_with = "json")]
+    pub porcelain: bool,
+
+    /// With list: show each project's size on disk
+    #[arg(long, global = true)]
+    pub size: bool,
+
+    /// With list: order projects by recent activity, name or size
+    #[arg(long, global = true, value_enum, default_value_t = operations::ListSort::Recent)]
+    pub sort: operations::ListSort,
+
+    /// With list: only show projects modified within AGE (7d, 2w, 1mo) or
+    /// since a date (2024-05-01)
+    #[arg(long, global = true, value_name = "AGE", value_parser = parse_modified_since)]
+    pub modified_since: Option<DateTime<Utc>>,
+
+    /// With list: report names shared by more than one project path
+    #[arg(long, global = true, conflicts_with_all = ["json", "porcelain"])]
+    pub show_duplicates: bool,
+
+    /// With fzf or clone: only use projects from SOURCE
+    #[arg(long, global = true, value_enum, value_name = "SOURCE")]
+    pub source: Option<SourceArg>,
+
+    /// With fzf: print the selected project's path instead of opening it
+    #[arg(long, conflicts_with_all = ["copy", "open"])]
+    pub print: bool,
+
+    /// With fzf: copy the selected project's path to the clipboard
+    #[arg(long, conflicts_with_all = ["print", "open"])]
+    pub copy: bool,
+
+    /// With fzf: open the selected project (the default)
+    #[arg(long, conflicts_with_all = ["print", "copy"])]
+    pub open: bool,
+
+    /// With fzf: list at most N projects
+    #[arg(long, global = true, value_name = "N")]
+    pub limit: Option<usize>,
+
+    /// Only consider projects whose git remote is on HOST (e.g. github.com)
+    #[arg(long, global = true, value_name = "HOST")]
+    pub host: Option<String>,
+
+    /// Show every scanner's entry for a path instead of merging duplicates
+    /// (for debugging; always rescans)
+    #[arg(long, global = true, hide = true)]
+    pub no_dedup: bool,
+
+    /// Load and save configuration from this file instead of the default
+    #[arg(long = "config", global = true, value_name = "PATH", env = "SW_CONFIG")]
+    pub config_path: Option<PathBuf>,
 
     #[command(subcommand)]
     pub command: Option<Commands>,
@@ -43,8 +148,46 @@ pub struct Cli {
 pub enum Commands {
     Setup,
     List,
-    Refresh,
-    Config,
+    Refresh {
+        /// Print per-scanner and cache timings to stderr as JSON
+        #[arg(long)]
+        profile_json: bool,
+    },
+    /// Summarize projects needing attention: uncloned quick-code projects,
+    /// uncommitted changes and vanished paths
+    Status,
+    Config {
+        #[command(subcommand)]
+        action: Option<ConfigAction>,
+    },
+
+    /// Clone every remote project that isn't checked out yet, optionally
+    /// only those from --source whose name contains PATTERN
+    Clone {
+        #[arg(long = "match", value_name = "PATTERN")]
+        pattern: Option<String>,
+    },
+
+    /// Print the path of the project NAME resolves to, without opening it
+    Which {
+        name: String,
+        /// Only accept a project whose name equals NAME (ignoring case)
+        #[arg(long)]
+        exact: bool,
+    },
+
+    /// Manage the single-character codes that `sw @CODE` opens
+    Code {
+        #[command(subcommand)]
+        action: CodeAction,
+    },
+
+    /// Refresh the cache in a detached background process and return at once
+    Prewarm {
+        /// Run the refresh in this process (used by the background process)
+        #[arg(long, hide = true)]
+        foreground: bool,
+    },
 
     Completions {
         #[arg(value_enum)]
@@ -52,6 +195,22 @@ pub enum Commands {
     },
 }
 
+#[derive(Subcommand)]
+pub enum ConfigAction {
+    /// Print the configuration as a portable JSON document
+    Export,
+    /// Replace the configuration with a document from `sw config export`
+    Import { path: PathBuf },
+}
+
+#[derive(Subcommand)]
+pub enum CodeAction {
+    /// Make `sw @CODE` open PROJECT
+    Set { code: char, project: String },
+    /// Remove a quick code
+    Unset { code: char },
+}
+
 impl Cli {
     pub fn operation_mode(&self) -> OperationMode {
         if let Some(ref project_name) = self.project_name {
@@ -61,71 +220,244 @@ impl Cli {
         match &self.command {
             Some(Commands::Setup) => OperationMode::Setup,
             Some(Commands::List) => OperationMode::List,
-            Some(Commands::Refresh) => OperationMode::Refresh,
-            Some(Commands::Config) => OperationMode::ShowConfig,
+            Some(Commands::Refresh { profile_json }) => OperationMode::Refresh {
+                profile_json: *profile_json,
+            },
+            Some(Commands::Status) => OperationMode::Status,
+            Some(Commands::Config { action: None }) => OperationMode::ShowConfig,
+            Some(Commands::Config {
+                action: Some(ConfigAction::Export),
+            }) => OperationMode::ExportConfig,
+            Some(Commands::Config {
+                action: Some(ConfigAction::Import { path }),
+            }) => OperationMode::ImportConfig(path.clone()),
+            Some(Commands::Clone { pattern }) => OperationMode::CloneAll {
+                pattern: pattern.clone(),
+            },
+            Some(Commands::Which { name, exact }) => OperationMode::Which {
+                name: name.clone(),
+                exact: *exact,
+            },
+            Some(Commands::Code { action }) => match action {
+                CodeAction::Set { code, project } => OperationMode::SetQuickCode {
+                    code: *code,
+                    project: Some(project.clone()),
+                },
+                CodeAction::Unset { code } => OperationMode::SetQuickCode {
+                    code: *code,
+                    project: None,
+                },
+            },
+            Some(Commands::Prewarm { foreground }) => OperationMode::Prewarm {
+                foreground: *foreground,
+            },
             Some(Commands::Completions { shell }) => OperationMode::Completions(*shell),
             None => {
-                if self.list {
+                if self.next {
+                    OperationMode::Cycle(Step::Next)
+                } else if self.prev {
+                    OperationMode::Cycle(Step::Prev)
+                } else if self.list {
                     OperationMode::List
                 } else if self.fzf {
                     OperationMode::Fzf
+                } else if self.simple {
+                    OperationMode::Simple
                 } else {
                     OperationMode::Interactive
                 }
             }
         }
     }
+
+    pub fn scan_options(&self) -> ScanOptions {
+        ScanOptions {
+            host_filter: self.host.clone(),
+            skip_dedup: self.no_dedup,
+        }
+    }
+
+    pub fn project_filter(&self) -> ProjectFilter {
+        ProjectFilter {
+            source: self.source.map(ProjectSource::from),
+            limit: self.limit,
+        }
+    }
+
+    pub fn selection_action(&self) -> operations::SelectionAction {
+        if self.print {
+            operations::SelectionAction::Print
+        } else if self.copy {
+            operations::SelectionAction::Copy
+        } else {
+            operations::SelectionAction::Open
+        }
+    }
+
+    pub fn list_format(&self) -> operations::ListFormat {
+        if self.json {
+            operations::ListFormat::Json
+        } else if self.porcelain {
+            operations::ListFormat::Porcelain
+        } else {
+            operations::ListFormat::Human
+        }
+    }
+}
+
+fn parse_modified_since(value: &str) -> Result<DateTime<Utc>, String> {
+    models::parse_cutoff(value, Utc::now())
+}
+
+/// Sources `--source` accepts
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum SourceArg {
+    Local,
+    Cursor,
+    Github,
+    Gitlab,
+}
+
+impl From<SourceArg> for ProjectSource {
+    fn from(source: SourceArg) -> Self {
+        match source {
+            SourceArg::Local => ProjectSource::Local,
+            SourceArg::Cursor => ProjectSource::Cursor,
+            SourceArg::Github => ProjectSource::GitHub,
+            SourceArg::Gitlab => ProjectSource::GitLab,
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Eq)]
 pub enum OperationMode {
     Direct(String),
+    /// Open a neighbour of the last-opened project
+    Cycle(Step),
     Interactive,
     List,
     Fzf,
+    Simple,
     Setup,
-    Refresh,
+    Refresh {
+        profile_json: bool,
+    },
+    Status,
     ShowConfig,
+    ExportConfig,
+    ImportConfig(PathBuf),
+    CloneAll {
+        pattern: Option<String>,
+    },
+    /// Print a project's path
+    Which {
+        name: String,
+        exact: bool,
+    },
+    /// Assign a quick code, or remove it when `project` is `None`
+    SetQuickCode {
+        code: char,
+        project: Option<String>,
+    },
+    Prewarm {
+        foreground: bool,
+    },
     Completions(Shell),
 }
 
+/// What to do when no config file exists yet
+#[derive(Debug, PartialEq, Eq)]
+enum FirstRunAction {
+    Nothing,
+    /// Ask whether to run the setup wizard
+    OfferSetup,
+    /// Save the defaults without prompting and say where they went
+    WriteDefaults,
+}
+
+fn first_run_action(is_first_time: bool, mode: &OperationMode, is_tty: bool) -> FirstRunAction {
+    if !is_first_time {
+        return FirstRunAction::Nothing;
+    }
+
+    match mode {
+        // Setup runs the wizard anyway; completions are piped into files and
+        // prewarm runs from shell startup, where nothing should be printed.
+        // Importing writes a config of its own.
+        OperationMode::Setup
+        | OperationMode::Completions(_)
+        | OperationMode::Prewarm { .. }
+        | OperationMode::ImportConfig(_) => FirstRunAction::Nothing,
+        _ if is_tty => FirstRunAction::OfferSetup,
+        _ => FirstRunAction::WriteDefaults,
+    }
+}
+
+/// Save `config` as the initial config file. Reported on stderr so piped
+/// output such as `--json` stays clean.
+fn write_default_config(config: &Config) {
+    let saved = config.save().and_then(|_| Config::config_file_path());
+    match saved {
+        Ok(_) if output::is_quiet() => {}
+        Ok(path) => {
+            eprintln!("Created default configuration at {}", path.display());
+            if config.should_prompt_github_setup() {
+                eprintln!("💡 Run 'sw setup' to configure GitHub integration");
+            }
+        }
+        Err(e) => eprintln!("Warning: Failed to save default configuration: {}", e),
+    }
+}
+
 fn main() -> Result<()> {
     let cli = Cli::parse();
-    let config = Config::load()?;
+    output::set_quiet(cli.quiet);
+    output::set_verbosity(cli.verbose);
+    let verbose = cli.verbose > 0;
+    if let Some(ref path) = cli.config_path {
+        Config::use_config_file(path.clone());
+    }
+    let mut config = Config::load()?;
 
     let is_first_time = Config::is_first_time_run().unwrap_or(false);
-    let should_setup_github = config.should_prompt_github_setup();
-
-    if is_first_time && should_setup_github {
-        match cli.operation_mode() {
-            OperationMode::Interactive | OperationMode::Fzf => {
-                if let Ok(Some(github_username)) = scanner::github::prompt_github_setup() {
-                    let updated_config = Config {
-                        github_username: Some(github_username),
-                        ..config.clone()
-                    };
-                    if let Err(e) = updated_config.save() {
-                        eprintln!("Warning: Failed to save GitHub configuration: {}", e);
-                    }
-                    println!(); // Add some spacing
-                }
-            }
-            OperationMode::List | OperationMode::ShowConfig => {
-                if cli.verbose {
-                    println!("💡 Tip: Run 'sw setup' to configure GitHub integration for repository discovery");
-                }
+    let is_tty = std::io::stdin().is_terminal() && std::io::stdout().is_terminal();
+
+    match first_run_action(is_first_time, &cli.operation_mode(), is_tty) {
+        FirstRunAction::Nothing => {}
+        FirstRunAction::OfferSetup => {
+            let run_setup = Confirm::new()
+                .with_prompt("No configuration found. Run the setup wizard now?")
+                .default(true)
+                .interact()
+                .unwrap_or(false);
+
+            if run_setup {
+                operations::handle_setup_wizard(&config, verbose)?;
+                config = Config::load()?;
+                println!(); // Add some spacing
+            } else {
+                write_default_config(&config);
             }
-            _ => {}
         }
+        FirstRunAction::WriteDefaults => write_default_config(&config),
     }
 
-    if cli.verbose {
+    if verbose {
         println!("Running sw with verbose output enabled");
     }
 
+    let options = cli.scan_options();
+
+    // Only for this run; the setup wizard saves the config it is given
+    if let Some(ref editor_command) = cli.open_with {
+        if cli.operation_mode() != OperationMode::Setup {
+            config.override_editor(editor_command);
+        }
+    }
+
     config.validate()?;
 
-    if cli.verbose {
+    if verbose {
         println!(
             "Loaded configuration: editor={}, dirs={}",
             config.editor_command,
@@ -134,14 +466,88 @@ fn main() -> Result<()> {
     }
 
     match cli.operation_mode() {
-        OperationMode::Setup => operations::handle_setup_wizard(&config, cli.verbose),
-        OperationMode::ShowConfig => operations::handle_show_config(&config, cli.verbose),
-        OperationMode::List => operations::handle_list_projects(&config, cli.verbose),
-        OperationMode::Interactive => operations::handle_interactive_mode(&config, cli.verbose),
-        OperationMode::Fzf => operations::handle_fzf_mode(&config, cli.verbose),
-        OperationMode::Refresh => operations::handle_refresh_cache(&config, cli.verbose),
+        OperationMode::Setup => operations::handle_setup_wizard(&config, verbose),
+        OperationMode::ShowConfig => operations::handle_show_config(&config, verbose),
+        OperationMode::ExportConfig => operations::handle_export_config(),
+        OperationMode::ImportConfig(path) => operations::handle_import_config(&path),
+        OperationMode::List if cli.show_duplicates => {
+            operations::handle_show_duplicates(&config, &options, verbose)
+        }
+        OperationMode::List => operations::handle_list_projects(
+            &config,
+            &options,
+            verbose,
+            cli.list_format(),
+            cli.size,
+            cli.sort,
+            cli.modified_since,
+        ),
+        OperationMode::Interactive => {
+            operations::handle_interactive_mode(&config, &options, verbose)
+        }
+        OperationMode::Cycle(step) => operations::handle_cycle(&config, &options, verbose, step),
+        OperationMode::Fzf => operations::handle_fzf_mode(
+            &config,
+            &options,
+            verbose,
+            &cli.project_filter(),
+            cli.selection_action(),
+        ),
+        OperationMode::Simple => operations::handle_simple_mode(&config, &options, verbose),
+        OperationMode::Refresh { profile_json } => {
+            operations::handle_refresh_cache(&config, &options, verbose, profile_json)
+        }
+        OperationMode::Status => operations::handle_status(&config, &options, verbose),
+        OperationMode::SetQuickCode { code, project } => {
+            operations::handle_set_quick_code(code, project.as_deref())
+        }
+        OperationMode::CloneAll { pattern } => operations::handle_clone_all(
+            &config,
+            &options,
+            verbose,
+            &cli.project_filter(),
+            pattern.as_deref(),
+        ),
+        OperationMode::Prewarm { foreground: false } => {
+            operations::handle_prewarm(cli.config_path.as_deref(), verbose)
+        }
+        OperationMode::Prewarm { foreground: true } => {
+            operations::handle_prewarm_foreground(&config, verbose)
+        }
+        OperationMode::Direct(project_name) if cli.all => {
+            match operations::handle_open_all_matching(&project_name, &config, &options, verbose) {
+                Err(e) if e.downcast_ref::<operations::ProjectNotFound>().is_some() => {
+                    println!("{}", e);
+                    std::process::exit(1);
+                }
+                result => result,
+            }
+        }
         OperationMode::Direct(project_name) => {
-            operations::handle_open_project_by_name(&project_name, &config, cli.verbose)
+            match operations::handle_open_project_by_name(
+                &project_name,
+                cli.exact,
+                cli.first,
+                &config,
+                &options,
+                verbose,
+            ) {
+                Err(e) if e.downcast_ref::<operations::ProjectNotFound>().is_some() => {
+                    println!("{}", e);
+                    std::process::exit(1);
+                }
+                result => result,
+            }
+        }
+        OperationMode::Which { name, exact } => {
+            match operations::handle_which(&name, exact, &config, &options, verbose) {
+                // stdout is usually captured by `$(sw which ...)`
+                Err(e) if e.downcast_ref::<operations::ProjectNotFound>().is_some() => {
+                    eprintln!("{}", e);
+                    std::process::exit(1);
+                }
+                result => result,
+            }
         }
         OperationMode::Completions(shell) => {
             let mut cmd = Cli::command();
@@ -164,7 +570,7 @@ mod tests {
         assert!(!cli.list);
         assert!(!cli.fzf);
         assert!(!cli.refresh);
-        assert!(!cli.verbose);
+        assert_eq!(cli.verbose, 0);
         assert!(cli.command.is_none());
     }
 
@@ -184,10 +590,28 @@ mod tests {
         let cli = Cli::try_parse_from(["sw", "--list", "--verbose"]).unwrap();
 
         assert!(cli.list);
-        assert!(cli.verbose);
+        assert_eq!(cli.verbose, 1);
         assert_eq!(cli.operation_mode(), OperationMode::List);
     }
 
+    #[test]
+    fn test_cli_verbosity_levels() {
+        assert_eq!(Cli::try_parse_from(["sw", "-v"]).unwrap().verbose, 1);
+        assert_eq!(Cli::try_parse_from(["sw", "-vv"]).unwrap().verbose, 2);
+        assert_eq!(
+            Cli::try_parse_from(["sw", "-v", "-v", "list"])
+                .unwrap()
+                .verbose,
+            2
+        );
+        assert_eq!(
+            Cli::try_parse_from(["sw", "--verbose", "--verbose"])
+                .unwrap()
+                .verbose,
+            2
+        );
+    }
+
     #[test]
     fn test_cli_interactive_flag() {
         let cli = Cli::try_parse_from(["sw", "--interactive"]).unwrap();
@@ -215,12 +639,108 @@ mod tests {
         assert_eq!(cli.operation_mode(), OperationMode::List);
 
         let cli = Cli::try_parse_from(["sw", "refresh"]).unwrap();
-        assert!(matches!(cli.command, Some(Commands::Refresh)));
-        assert_eq!(cli.operation_mode(), OperationMode::Refresh);
+        assert!(matches!(cli.command, Some(Commands::Refresh { .. })));
+        assert_eq!(
+            cli.operation_mode(),
+            OperationMode::Refresh {
+                profile_json: false
+            }
+        );
+
+        let cli = Cli::try_parse_from(["sw", "refresh", "--profile-json"]).unwrap();
+        assert_eq!(
+            cli.operation_mode(),
+            OperationMode::Refresh { profile_json: true }
+        );
+
+        let cli = Cli::try_parse_from(["sw", "status"]).unwrap();
+        assert_eq!(cli.operation_mode(), OperationMode::Status);
 
         let cli = Cli::try_parse_from(["sw", "config"]).unwrap();
-        assert!(matches!(cli.command, Some(Commands::Config)));
+        assert!(matches!(
+            cli.command,
+            Some(Commands::Config { action: None })
+        ));
         assert_eq!(cli.operation_mode(), OperationMode::ShowConfig);
+
+        let cli = Cli::try_parse_from(["sw", "config", "export"]).unwrap();
+        assert_eq!(cli.operation_mode(), OperationMode::ExportConfig);
+
+        let cli = Cli::try_parse_from(["sw", "config", "import", "sw.json"]).unwrap();
+        assert_eq!(
+            cli.operation_mode(),
+            OperationMode::ImportConfig(PathBuf::from("sw.json"))
+        );
+    }
+
+    #[test]
+    fn test_cli_which_command() {
+        let cli = Cli::try_parse_from(["sw", "which", "app"]).unwrap();
+        assert_eq!(
+            cli.operation_mode(),
+            OperationMode::Which {
+                name: "app".to_string(),
+                exact: false
+            }
+        );
+
+        let cli = Cli::try_parse_from(["sw", "which", "--exact", "app"]).unwrap();
+        assert_eq!(
+            cli.operation_mode(),
+            OperationMode::Which {
+                name: "app".to_string(),
+                exact: true
+            }
+        );
+    }
+
+    #[test]
+    fn test_cli_exact_flag() {
+        let cli = Cli::try_parse_from(["sw", "--exact", "app"]).unwrap();
+        assert!(cli.exact);
+        assert_eq!(
+            cli.operation_mode(),
+            OperationMode::Direct("app".to_string())
+        );
+
+        assert!(Cli::try_parse_from(["sw", "--exact"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_all_flag() {
+        let cli = Cli::try_parse_from(["sw", "api", "--all"]).unwrap();
+        assert!(cli.all);
+        assert_eq!(
+            cli.operation_mode(),
+            OperationMode::Direct("api".to_string())
+        );
+
+        assert!(Cli::try_parse_from(["sw", "--all"]).is_err());
+        assert!(Cli::try_parse_from(["sw", "api", "--all", "--first"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_first_flag() {
+        let cli = Cli::try_parse_from(["sw", "api", "--first"]).unwrap();
+        assert!(cli.first);
+        assert_eq!(
+            cli.operation_mode(),
+            OperationMode::Direct("api".to_string())
+        );
+
+        assert!(Cli::try_parse_from(["sw", "--first"]).is_err());
+        assert!(Cli::try_parse_from(["sw", "api", "--first", "--exact"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_simple_flag() {
+        let cli = Cli::try_parse_from(["sw", "--simple"]).unwrap();
+        assert_eq!(cli.operation_mode(), OperationMode::Simple);
+
+        let cli = Cli::try_parse_from(["sw", "--plain"]).unwrap();
+        assert_eq!(cli.operation_mode(), OperationMode::Simple);
+
+        assert!(Cli::try_parse_from(["sw", "--simple", "--fzf"]).is_err());
     }
 
     #[test]
@@ -289,4 +809,247 @@ mod tests {
             OperationMode::Completions(Shell::PowerShell)
         );
     }
+
+    #[test]
+    fn test_cli_list_formats() {
+        let cli = Cli::try_parse_from(["sw", "list", "--porcelain"]).unwrap();
+        assert_eq!(cli.list_format(), operations::ListFormat::Porcelain);
+
+        let cli = Cli::try_parse_from(["sw", "--list", "--json"]).unwrap();
+        assert_eq!(cli.list_format(), operations::ListFormat::Json);
+
+        let cli = Cli::try_parse_from(["sw", "list"]).unwrap();
+        assert_eq!(cli.list_format(), operations::ListFormat::Human);
+
+        assert!(Cli::try_parse_from(["sw", "list", "--json", "--porcelain"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_config_path() {
+        let cli = Cli::try_parse_from(["sw", "--config", "/tmp/sw.json", "list"]).unwrap();
+        assert_eq!(cli.config_path, Some(PathBuf::from("/tmp/sw.json")));
+        assert_eq!(cli.operation_mode(), OperationMode::List);
+
+        let cli = Cli::try_parse_from(["sw", "config", "--config", "/tmp/sw.json"]).unwrap();
+        assert_eq!(cli.operation_mode(), OperationMode::ShowConfig);
+        assert_eq!(cli.config_path, Some(PathBuf::from("/tmp/sw.json")));
+    }
+
+    #[test]
+    fn test_cli_open_with() {
+        let cli = Cli::try_parse_from(["sw", "--open-with", "zed", "my-project"]).unwrap();
+        assert_eq!(cli.open_with.as_deref(), Some("zed"));
+
+        let cli = Cli::try_parse_from(["sw", "--editor", "vim", "--fzf"]).unwrap();
+        assert_eq!(cli.open_with.as_deref(), Some("vim"));
+        assert_eq!(cli.operation_mode(), OperationMode::Fzf);
+
+        let cli = Cli::try_parse_from(["sw"]).unwrap();
+        assert!(cli.open_with.is_none());
+    }
+
+    #[test]
+    fn test_cli_host_filter() {
+        let cli = Cli::try_parse_from(["sw", "list", "--host", "gitlab.example.com"]).unwrap();
+        assert_eq!(cli.host.as_deref(), Some("gitlab.example.com"));
+        assert_eq!(
+            cli.scan_options().host_filter.as_deref(),
+            Some("gitlab.example.com")
+        );
+        assert_eq!(cli.operation_mode(), OperationMode::List);
+    }
+
+    #[test]
+    fn test_cli_project_filter() {
+        let cli =
+            Cli::try_parse_from(["sw", "--fzf", "--source", "github", "--limit", "50"]).unwrap();
+        assert_eq!(
+            cli.project_filter(),
+            ProjectFilter {
+                source: Some(ProjectSource::GitHub),
+                limit: Some(50),
+            }
+        );
+
+        let cli = Cli::try_parse_from(["sw", "--fzf"]).unwrap();
+        assert_eq!(cli.project_filter(), ProjectFilter::default());
+
+        let err = Cli::try_parse_from(["sw", "--fzf", "--source", "gihtub"])
+            .err()
+            .unwrap()
+            .to_string();
+        assert!(err.contains("local, cursor, github, gitlab"), "{}", err);
+    }
+
+    #[test]
+    fn test_first_run_with_tty_offers_setup() {
+        assert_eq!(
+            first_run_action(true, &OperationMode::Interactive, true),
+            FirstRunAction::OfferSetup
+        );
+        assert_eq!(
+            first_run_action(true, &OperationMode::List, true),
+            FirstRunAction::OfferSetup
+        );
+        assert_eq!(
+            first_run_action(true, &OperationMode::Setup, true),
+            FirstRunAction::Nothing
+        );
+    }
+
+    #[test]
+    fn test_first_run_without_tty_writes_defaults() {
+        assert_eq!(
+            first_run_action(true, &OperationMode::Fzf, false),
+            FirstRunAction::WriteDefaults
+        );
+        assert_eq!(
+            first_run_action(true, &OperationMode::Completions(Shell::Bash), false),
+            FirstRunAction::Nothing
+        );
+        assert_eq!(
+            first_run_action(true, &OperationMode::Prewarm { foreground: false }, false),
+            FirstRunAction::Nothing
+        );
+        assert_eq!(
+            first_run_action(false, &OperationMode::Interactive, false),
+            FirstRunAction::Nothing
+        );
+    }
+
+    #[test]
+    fn test_cli_list_size_and_sort() {
+        let cli = Cli::try_parse_from(["sw", "list", "--size", "--sort", "size"]).unwrap();
+        assert!(cli.size);
+        assert_eq!(cli.sort, operations::ListSort::Size);
+
+        let cli = Cli::try_parse_from(["sw", "list"]).unwrap();
+        assert!(!cli.size);
+        assert_eq!(cli.sort, operations::ListSort::Recent);
+    }
+
+    #[test]
+    fn test_cli_fzf_selection_action() {
+        let action = |args: &[&str]| Cli::try_parse_from(args).unwrap().selection_action();
+
+        assert_eq!(action(&["sw", "--fzf"]), operations::SelectionAction::Open);
+        assert_eq!(
+            action(&["sw", "--fzf", "--open"]),
+            operations::SelectionAction::Open
+        );
+        assert_eq!(
+            action(&["sw", "--fzf", "--print"]),
+            operations::SelectionAction::Print
+        );
+        assert_eq!(
+            action(&["sw", "--fzf", "--copy"]),
+            operations::SelectionAction::Copy
+        );
+        assert!(Cli::try_parse_from(["sw", "--fzf", "--print", "--copy"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_next_and_prev() {
+        let cli = Cli::try_parse_from(["sw", "--next"]).unwrap();
+        assert_eq!(cli.operation_mode(), OperationMode::Cycle(Step::Next));
+
+        let cli = Cli::try_parse_from(["sw", "--prev"]).unwrap();
+        assert_eq!(cli.operation_mode(), OperationMode::Cycle(Step::Prev));
+
+        assert!(Cli::try_parse_from(["sw", "--next", "--prev"]).is_err());
+        assert!(Cli::try_parse_from(["sw", "--next", "app"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_clone() {
+        let cli =
+            Cli::try_parse_from(["sw", "clone", "--source", "github", "--match", "pay"]).unwrap();
+        assert_eq!(
+            cli.operation_mode(),
+            OperationMode::CloneAll {
+                pattern: Some("pay".to_string())
+            }
+        );
+        assert_eq!(cli.project_filter().source, Some(ProjectSource::GitHub));
+
+        let cli = Cli::try_parse_from(["sw", "clone"]).unwrap();
+        assert_eq!(
+            cli.operation_mode(),
+            OperationMode::CloneAll { pattern: None }
+        );
+    }
+
+    #[test]
+    fn test_cli_quick_codes() {
+        let cli = Cli::try_parse_from(["sw", "code", "set", "a", "myproj"]).unwrap();
+        assert_eq!(
+            cli.operation_mode(),
+            OperationMode::SetQuickCode {
+                code: 'a',
+                project: Some("myproj".to_string())
+            }
+        );
+
+        let cli = Cli::try_parse_from(["sw", "code", "unset", "a"]).unwrap();
+        assert_eq!(
+            cli.operation_mode(),
+            OperationMode::SetQuickCode {
+                code: 'a',
+                project: None
+            }
+        );
+
+        assert!(Cli::try_parse_from(["sw", "code", "set", "ab", "myproj"]).is_err());
+
+        let cli = Cli::try_parse_from(["sw", "@a"]).unwrap();
+        assert_eq!(
+            cli.operation_mode(),
+            OperationMode::Direct("@a".to_string())
+        );
+    }
+
+    #[test]
+    fn test_cli_no_dedup() {
+        let cli = Cli::try_parse_from(["sw", "list", "--no-dedup"]).unwrap();
+        assert!(cli.no_dedup);
+        assert!(!Cli::try_parse_from(["sw", "list"]).unwrap().no_dedup);
+
+        let help = Cli::command().render_long_help().to_string();
+        assert!(!help.contains("--no-dedup"));
+    }
+
+    #[test]
+    fn test_cli_prewarm() {
+        let cli = Cli::try_parse_from(["sw", "prewarm"]).unwrap();
+        assert_eq!(
+            cli.operation_mode(),
+            OperationMode::Prewarm { foreground: false }
+        );
+
+        let cli = Cli::try_parse_from(["sw", "prewarm", "--foreground"]).unwrap();
+        assert_eq!(
+            cli.operation_mode(),
+            OperationMode::Prewarm { foreground: true }
+        );
+    }
+
+    #[test]
+    fn test_cli_modified_since() {
+        let cli = Cli::try_parse_from(["sw", "list", "--modified-since", "2024-05-01"]).unwrap();
+        assert_eq!(
+            cli.modified_since.map(|cutoff| cutoff.to_rfc3339()),
+            Some("2024-05-01T00:00:00+00:00".to_string())
+        );
+
+        assert!(Cli::try_parse_from(["sw", "list", "--modified-since", "soon"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_show_duplicates() {
+        let cli = Cli::try_parse_from(["sw", "list", "--show-duplicates"]).unwrap();
+        assert!(cli.show_duplicates);
+        assert_eq!(cli.operation_mode(), OperationMode::List);
+
+        assert!(Cli::try_parse_from(["sw", "list", "--show-duplicates", "--json"]).is_err());
+    }
 }
@@ -1,8 +1,16 @@
-use chrono::{DateTime, Utc};
+use crate::config::{Config, DedupPolicy};
+use chrono::{DateTime, Duration, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
 
+/// Serialized by name rather than by variant index, so a cache or JSON
+/// written by a newer version with extra sources still loads; names this
+/// version doesn't know become `Unknown`.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
 pub enum ProjectSource {
     /// Project found in local filesystem
     Local,
@@ -12,6 +20,45 @@ pub enum ProjectSource {
     GitHub,
     /// Project found in GitLab repositories
     GitLab,
+    /// Source written by a newer version of sw
+    Unknown(String),
+}
+
+impl ProjectSource {
+    /// Stable lowercase identifier, matching the scanner name
+    pub fn as_str(&self) -> &str {
+        match self {
+            ProjectSource::Local => "local",
+            ProjectSource::Cursor => "cursor",
+            ProjectSource::GitHub => "github",
+            ProjectSource::GitLab => "gitlab",
+            ProjectSource::Unknown(name) => name,
+        }
+    }
+}
+
+impl From<String> for ProjectSource {
+    fn from(name: String) -> Self {
+        match name.as_str() {
+            "Local" | "local" => ProjectSource::Local,
+            "Cursor" | "cursor" => ProjectSource::Cursor,
+            "GitHub" | "github" => ProjectSource::GitHub,
+            "GitLab" | "gitlab" => ProjectSource::GitLab,
+            _ => ProjectSource::Unknown(name),
+        }
+    }
+}
+
+impl From<ProjectSource> for String {
+    fn from(source: ProjectSource) -> Self {
+        match source {
+            ProjectSource::Local => "Local".to_string(),
+            ProjectSource::Cursor => "Cursor".to_string(),
+            ProjectSource::GitHub => "GitHub".to_string(),
+            ProjectSource::GitLab => "GitLab".to_string(),
+            ProjectSource::Unknown(name) => name,
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -28,6 +75,27 @@ pub struct Project {
     pub github_url: Option<String>,
     /// GitLab URL if this is a GitLab project
     pub gitlab_url: Option<String>,
+    /// Git remote host such as `github.com`, when known
+    #[serde(default)]
+    pub host: Option<String>,
+    /// Language or toolchain detected from marker files, e.g. `rust`
+    #[serde(default)]
+    pub project_type: Option<String>,
+    /// Bytes on disk; only filled in on demand, e.g. by `sw list --size`
+    #[serde(default)]
+    pub size_bytes: Option<u64>,
+    /// Parent directory and name, e.g. `acme/frontend`; only set while
+    /// another project in the same list shares the name
+    #[serde(skip)]
+    pub qualified_name: Option<String>,
+    /// Whether the git working tree has uncommitted changes; only checked
+    /// on demand, when `show_git_status` is on
+    #[serde(skip)]
+    pub dirty: Option<bool>,
+    /// Set for projects holding an `ARCHIVED` file or matching one of the
+    /// `archive_patterns`; shown dimmed and sorted last
+    #[serde(default)]
+    pub archived: bool,
 }
 
 impl Project {
@@ -39,6 +107,12 @@ impl Project {
             source: ProjectSource::Local,
             github_url: None,
             gitlab_url: None,
+            host: None,
+            project_type: None,
+            size_bytes: None,
+            qualified_name: None,
+            dirty: None,
+            archived: false,
         }
     }
 
@@ -50,6 +124,12 @@ impl Project {
             source: ProjectSource::Cursor,
             github_url: None,
             gitlab_url: None,
+            host: None,
+            project_type: None,
+            size_bytes: None,
+            qualified_name: None,
+            dirty: None,
+            archived: false,
         }
     }
 
@@ -59,8 +139,14 @@ impl Project {
             path: path.into(),
             last_modified: None,
             source: ProjectSource::GitHub,
+            host: remote_host(&github_url),
             github_url: Some(github_url),
             gitlab_url: None,
+            project_type: None,
+            size_bytes: None,
+            qualified_name: None,
+            dirty: None,
+            archived: false,
         }
     }
 
@@ -71,7 +157,13 @@ impl Project {
             last_modified: None,
             source: ProjectSource::GitLab,
             github_url: None,
+            host: remote_host(&gitlab_url),
             gitlab_url: Some(gitlab_url),
+            project_type: None,
+            size_bytes: None,
+            qualified_name: None,
+            dirty: None,
+            archived: false,
         }
     }
 
@@ -80,33 +172,424 @@ impl Project {
         self
     }
 
+    pub fn with_host(mut self, host: Option<String>) -> Self {
+        self.host = host;
+        self
+    }
+
+    /// Take the host from a git remote, and the GitHub or GitLab web URL
+    /// when the remote lives on one
+    pub fn with_remote_url(mut self, url: Option<&str>) -> Self {
+        let Some(url) = url else {
+            return self;
+        };
+        self.host = remote_host(url);
+        match self.host.as_deref() {
+            Some(host) if host.contains("github") => self.github_url = remote_web_url(url),
+            Some(host) if host.contains("gitlab") => self.gitlab_url = remote_web_url(url),
+            _ => {}
+        }
+        self
+    }
+
+    pub fn with_project_type(mut self, project_type: Option<String>) -> Self {
+        self.project_type = project_type;
+        self
+    }
+
+    pub fn with_archived(mut self, archived: bool) -> Self {
+        self.archived = archived;
+        self
+    }
+
+    /// What the editor is handed for this project: its configured subpath
+    /// when that exists, then with `prefer_workspace_file` the first
+    /// `*.code-workspace` file in the project folder, otherwise the path
+    /// itself
+    pub fn open_target(&self, config: &Config) -> OsString {
+        if let Some(subpath) = config.open_subpath_for(&self.path) {
+            let target = self.path.join(subpath);
+            if target.exists() {
+                return target.into_os_string();
+            }
+        }
+
+        if config.prefer_workspace_file && self.path.is_dir() {
+            let mut workspaces: Vec<PathBuf> = std::fs::read_dir(&self.path)
+                .into_iter()
+                .flatten()
+                .filter_map(|entry| entry.ok())
+                .map(|entry| entry.path())
+                .filter(|file| {
+                    file.is_file() && file.extension().is_some_and(|ext| ext == "code-workspace")
+                })
+                .collect();
+            workspaces.sort();
+            if let Some(workspace) = workspaces.into_iter().next() {
+                return workspace.into_os_string();
+            }
+        }
+
+        self.path.clone().into_os_string()
+    }
+
+    /// The repository's web URL, lowercased, so clones made from differently
+    /// written remotes of one repository compare equal
+    fn repo_key(&self) -> Option<String> {
+        let url = self.github_url.as_deref().or(self.gitlab_url.as_deref())?;
+        Some(
+            remote_web_url(url)
+                .unwrap_or_else(|| url.to_string())
+                .to_lowercase(),
+        )
+    }
+
     #[allow(dead_code)]
     pub fn exists_locally(&self) -> bool {
         self.path.exists()
     }
 
+    #[allow(dead_code)]
     pub fn display_string(&self) -> String {
+        self.display_string_with_label(None, false, DEFAULT_DIRTY_MARKER)
+    }
+
+    /// Like `display_string`, but shows `label` in front of the directory
+    /// name when a display name is configured and, with `relative_times`,
+    /// the age of the project instead of its timestamp. Projects known to
+    /// have uncommitted changes get `dirty_marker` after their name.
+    pub fn display_string_with_label(
+        &self,
+        label: Option<&str>,
+        relative_times: bool,
+        dirty_marker: &str,
+    ) -> String {
         let source_indicator = match self.source {
             ProjectSource::Local => "📁",
             ProjectSource::Cursor => "🎯",
             ProjectSource::GitHub => "🐙",
             ProjectSource::GitLab => "🦊",
+            ProjectSource::Unknown(_) => "❔",
         };
 
-        let time_str = if let Some(timestamp) = self.last_modified {
-            format!(" ({})", timestamp.format("%Y-%m-%d %H:%M"))
+        let time_str = self
+            .time_label(relative_times, "%Y-%m-%d %H:%M")
+            .map(|time| format!(" ({})", time))
+            .unwrap_or_default();
+
+        let dirty = if self.dirty == Some(true) {
+            format!(" {}", dirty_marker)
         } else {
             String::new()
         };
 
         format!(
-            "{} {}{} - {}",
+            "{} {}{}{} - {}",
             source_indicator,
-            self.name,
+            self.labeled_name(label),
+            dirty,
             time_str,
             self.path.display()
         )
     }
+
+    /// `last_modified` as a relative age, or formatted with `format`
+    pub fn time_label(&self, relative: bool, format: &str) -> Option<String> {
+        if relative {
+            self.relative_age(Utc::now())
+        } else {
+            self.last_modified
+                .map(|timestamp| timestamp.format(format).to_string())
+        }
+    }
+
+    /// How long before `now` the project was last modified, e.g. `3d ago`
+    pub fn relative_age(&self, now: DateTime<Utc>) -> Option<String> {
+        let seconds = now
+            .signed_duration_since(self.last_modified?)
+            .num_seconds()
+            .max(0);
+
+        const MINUTE: i64 = 60;
+        const HOUR: i64 = 60 * MINUTE;
+        const DAY: i64 = 24 * HOUR;
+        const WEEK: i64 = 7 * DAY;
+        const MONTH: i64 = 30 * DAY;
+        const YEAR: i64 = 365 * DAY;
+
+        let age = match seconds {
+            s if s < MINUTE => format!("{}s", s),
+            s if s < HOUR => format!("{}m", s / MINUTE),
+            s if s < DAY => format!("{}h", s / HOUR),
+            s if s < WEEK => format!("{}d", s / DAY),
+            s if s < MONTH => format!("{}w", s / WEEK),
+            s if s < YEAR => format!("{}mo", s / MONTH),
+            s => format!("{}y", s / YEAR),
+        };
+        Some(format!("{} ago", age))
+    }
+
+    /// `label [name]` when a display name is set, otherwise just the name, so
+    /// tools matching on the line text still see the original name
+    pub fn labeled_name(&self, label: Option<&str>) -> String {
+        match label {
+            Some(label) => format!("{} [{}]", label, self.shown_name()),
+            None => self.shown_name().to_string(),
+        }
+    }
+
+    /// `qualified_name` when the name is ambiguous, otherwise `name`
+    pub fn shown_name(&self) -> &str {
+        self.qualified_name.as_deref().unwrap_or(&self.name)
+    }
+
+    /// Space-separated attributes fuzzy search can match on
+    pub fn search_tokens(&self) -> String {
+        let mut tokens = format!("{} {}", self.shown_name(), self.source.as_str());
+        for attribute in [&self.host, &self.project_type].into_iter().flatten() {
+            tokens.push(' ');
+            tokens.push_str(attribute);
+        }
+        tokens
+    }
+
+    /// Tab-separated `name, source, path, last_modified_epoch` line whose
+    /// layout is kept stable across versions for scripting
+    pub fn porcelain_line(&self) -> String {
+        let epoch = self
+            .last_modified
+            .map(|timestamp| timestamp.timestamp().to_string())
+            .unwrap_or_default();
+
+        format!(
+            "{}\t{}\t{}\t{}",
+            self.name,
+            self.source.as_str(),
+            self.path.display(),
+            epoch
+        )
+    }
+}
+
+/// Narrows a project listing by source and caps how many entries it holds
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProjectFilter {
+    pub source: Option<ProjectSource>,
+    pub limit: Option<usize>,
+}
+
+impl ProjectFilter {
+    pub fn apply<'a>(&self, projects: &'a [Project]) -> Vec<&'a Project> {
+        projects
+            .iter()
+            .filter(|project| self.source.as_ref().is_none_or(|s| project.source == *s))
+            .take(self.limit.unwrap_or(usize::MAX))
+            .collect()
+    }
+}
+
+/// Host part of a git remote URL, lowercased. Handles web and `ssh://` URLs
+/// as well as scp-style remotes such as `git@github.com:owner/repo.git`.
+pub fn remote_host(url: &str) -> Option<String> {
+    let authority = match url.split_once("://") {
+        Some((_, rest)) => rest.split('/').next()?,
+        None => {
+            let (authority, path) = url.split_once(':')?;
+            if authority.contains('/') || path.is_empty() {
+                return None;
+            }
+            authority
+        }
+    };
+
+    let host = authority.rsplit('@').next()?;
+    let host = host.split(':').next()?;
+    if host.is_empty() {
+        None
+    } else {
+        Some(host.to_ascii_lowercase())
+    }
+}
+
+/// Browser URL for a git remote: `git@github.com:owner/repo.git` and
+/// `ssh://git@host:2222/owner/repo` both become `https://<host>/owner/repo`.
+/// Credentials and a trailing `.git` are dropped.
+pub fn remote_web_url(url: &str) -> Option<String> {
+    let host = remote_host(url)?;
+    let path = match url.split_once("://") {
+        Some((_, rest)) => rest.split_once('/').map_or("", |(_, path)| path),
+        None => url.split_once(':').map_or("", |(_, path)| path),
+    };
+    let path = path.trim_matches('/');
+    let path = path.strip_suffix(".git").unwrap_or(path);
+    if path.is_empty() {
+        return None;
+    }
+    Some(format!("https://{}/{}", host, path))
+}
+
+/// Turn a relative age (`12h`, `7d`, `2w`, `1mo`, `1y`) or an absolute date
+/// (`2024-05-01` or RFC 3339) into the point in time it refers to. Months
+/// and years are 30 and 365 days, as in [`Project::relative_age`].
+pub fn parse_cutoff(input: &str, now: DateTime<Utc>) -> Result<DateTime<Utc>, String> {
+    let input = input.trim();
+
+    if let Ok(date) = NaiveDate::parse_from_str(input, "%Y-%m-%d") {
+        return Ok(date.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc());
+    }
+    if let Ok(timestamp) = DateTime::parse_from_rfc3339(input) {
+        return Ok(timestamp.with_timezone(&Utc));
+    }
+
+    let split = input
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(input.len());
+    let (amount, unit) = input.split_at(split);
+    let invalid = || {
+        format!(
+            "invalid date or age '{}' (try 7d, 2w, 1mo or 2024-05-01)",
+            input
+        )
+    };
+    let amount: i64 = amount.parse().map_err(|_| invalid())?;
+    let age = match unit {
+        "h" => Duration::try_hours(amount),
+        "d" => Duration::try_days(amount),
+        "w" => amount.checked_mul(7).and_then(Duration::try_days),
+        "mo" => amount.checked_mul(30).and_then(Duration::try_days),
+        "y" => amount.checked_mul(365).and_then(Duration::try_days),
+        _ => {
+            return Err(format!(
+                "unknown unit '{}' in '{}' (use h, d, w, mo or y)",
+                unit, input
+            ))
+        }
+    };
+    age.and_then(|age| now.checked_sub_signed(age))
+        .ok_or_else(invalid)
+}
+
+/// Most recently modified first; undated projects last, by name. Archived
+/// projects follow all active ones.
+fn recency_order(a: &Project, b: &Project) -> Ordering {
+    a.archived
+        .cmp(&b.archived)
+        .then_with(|| match (a.last_modified, b.last_modified) {
+            (Some(a_time), Some(b_time)) => b_time.cmp(&a_time),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => a.name.cmp(&b.name),
+        })
+}
+
+/// Uncommitted work outranks recency: a dirty repo sorts before any clean
+/// one, and projects whose status is unknown count as clean. Archived
+/// projects still follow all active ones, dirty or not.
+pub fn activity_order(a: &Project, b: &Project) -> Ordering {
+    let a_dirty = a.dirty.unwrap_or(false);
+    let b_dirty = b.dirty.unwrap_or(false);
+    a.archived
+        .cmp(&b.archived)
+        .then_with(|| b_dirty.cmp(&a_dirty))
+        .then_with(|| recency_order(a, b))
+}
+
+/// Marks projects with uncommitted changes unless `dirty_marker` is configured
+pub const DEFAULT_DIRTY_MARKER: &str = "●";
+
+/// Look a project up by name, case-insensitively. An exact name match wins
+/// over substring matches; with `exact` only an exact match is accepted.
+pub fn find_project_by_name<'a>(
+    projects: &'a [Project],
+    query: &str,
+    exact: bool,
+) -> Option<&'a Project> {
+    let query = query.to_lowercase();
+
+    projects
+        .iter()
+        .find(|project| project.name.to_lowercase() == query)
+        .or_else(|| {
+            if exact {
+                return None;
+            }
+            projects
+                .iter()
+                .find(|project| project.name.to_lowercase().contains(&query))
+        })
+}
+
+/// Every project whose name contains `query`, ignoring case
+pub fn projects_matching_name<'a>(projects: &'a [Project], query: &str) -> Vec<&'a Project> {
+    let query = query.to_lowercase();
+    projects
+        .iter()
+        .filter(|project| project.name.to_lowercase().contains(&query))
+        .collect()
+}
+
+/// Direction for `sw --next` / `sw --prev`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Step {
+    Next,
+    Prev,
+}
+
+/// Index of the project one `step` away from `current`, wrapping around
+/// and skipping projects for which `available` is false. Without a
+/// `current` project, `Next` starts at the top and `Prev` at the bottom.
+pub fn neighbor_index(
+    projects: &[Project],
+    current: Option<usize>,
+    step: Step,
+    available: impl Fn(&Project) -> bool,
+) -> Option<usize> {
+    let len = projects.len();
+    if len == 0 {
+        return None;
+    }
+
+    let start = match (current, step) {
+        (Some(index), _) => index,
+        // One before the first / after the last, so the first hop lands on it
+        (None, Step::Next) => len - 1,
+        (None, Step::Prev) => 0,
+    };
+
+    (1..=len)
+        .map(|offset| match step {
+            Step::Next => (start + offset) % len,
+            Step::Prev => (start + len - offset % len) % len,
+        })
+        .find(|&index| available(&projects[index]))
+}
+
+/// Path with the home directory shortened to `~`
+pub fn abbreviate_home(path: &Path, home: Option<&Path>) -> String {
+    match home.and_then(|home| path.strip_prefix(home).ok()) {
+        Some(rest) if rest.as_os_str().is_empty() => "~".to_string(),
+        Some(rest) => format!("~/{}", rest.display()),
+        None => path.display().to_string(),
+    }
+}
+
+/// Find the project whose path contains `dir`, preferring the deepest
+/// (longest) match when projects are nested.
+pub fn find_project_containing(projects: &[Project], dir: &Path) -> Option<usize> {
+    projects
+        .iter()
+        .enumerate()
+        .filter(|(_, project)| dir.starts_with(&project.path))
+        .max_by_key(|(_, project)| project.path.components().count())
+        .map(|(index, _)| index)
+}
+
+/// Result of a background rescan: the fresh projects and the sources whose
+/// scanner finished, so entries from failed or timed-out sources can stay
+#[derive(Debug, Clone, Default)]
+pub struct Refresh {
+    pub projects: ProjectList,
+    pub scanned_sources: Vec<ProjectSource>,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -123,6 +606,39 @@ impl ProjectList {
         Self { projects }
     }
 
+    /// Add the projects from `other`, keeping one entry per path. On a
+    /// clash the entry whose source comes first in `priority` stays; with
+    /// equal priority the incoming entry replaces the existing one. Sources
+    /// missing from `priority` rank last.
+    pub fn merge(&mut self, other: ProjectList, priority: &[ProjectSource]) {
+        let rank = |source: &ProjectSource| {
+            priority
+                .iter()
+                .position(|candidate| candidate == source)
+                .unwrap_or(priority.len())
+        };
+        let mut index_by_path: HashMap<PathBuf, usize> = self
+            .projects
+            .iter()
+            .enumerate()
+            .map(|(index, project)| (project.path.clone(), index))
+            .collect();
+
+        for project in other.projects {
+            match index_by_path.get(&project.path) {
+                Some(&index) => {
+                    if rank(&project.source) <= rank(&self.projects[index].source) {
+                        self.projects[index] = project;
+                    }
+                }
+                None => {
+                    index_by_path.insert(project.path.clone(), self.projects.len());
+                    self.projects.push(project);
+                }
+            }
+        }
+    }
+
     pub fn add_project(&mut self, project: Project) {
         self.projects.push(project);
     }
@@ -140,16 +656,47 @@ impl ProjectList {
     }
 
     pub fn sort_by_last_modified(&mut self) {
+        self.projects.sort_by(recency_order);
+    }
+
+    /// Dirty working trees first, most recent first within each group
+    pub fn sort_by_activity(&mut self) {
+        self.projects.sort_by(activity_order);
+    }
+
+    /// Largest first; projects without a known size go last
+    pub fn sort_by_size(&mut self) {
         self.projects
-            .sort_by(|a, b| match (a.last_modified, b.last_modified) {
-                (Some(a_time), Some(b_time)) => b_time.cmp(&a_time),
-                (Some(_), None) => std::cmp::Ordering::Less,
-                (None, Some(_)) => std::cmp::Ordering::Greater,
-                (None, None) => a.name.cmp(&b.name),
-            });
+            .sort_by_key(|project| std::cmp::Reverse(project.size_bytes));
+    }
+
+    pub fn sort_by_name(&mut self) {
+        self.projects
+            .sort_by_key(|project| project.name.to_lowercase());
+    }
+
+    pub fn projects_mut(&mut self) -> &mut [Project] {
+        &mut self.projects
+    }
+
+    /// Names that map to more than one distinct path, with those paths, so
+    /// ambiguous lookups by name can be reported. Sorted by name.
+    pub fn duplicate_names(&self) -> Vec<(String, Vec<PathBuf>)> {
+        let mut by_name: BTreeMap<&str, BTreeSet<&PathBuf>> = BTreeMap::new();
+        for project in &self.projects {
+            by_name
+                .entry(project.name.as_str())
+                .or_default()
+                .insert(&project.path);
+        }
+
+        by_name
+            .into_iter()
+            .filter(|(_, paths)| paths.len() > 1)
+            .map(|(name, paths)| (name.to_string(), paths.into_iter().cloned().collect()))
+            .collect()
     }
 
-    #[allow(dead_code)]
     pub fn filter_by_source(&self, source: ProjectSource) -> Vec<&Project> {
         self.projects
             .iter()
@@ -157,24 +704,134 @@ impl ProjectList {
             .collect()
     }
 
+    /// Repositories cloned at more than one local path, by web URL
+    pub fn multi_clones(&self) -> Vec<(String, Vec<PathBuf>)> {
+        let mut by_repo: BTreeMap<String, BTreeSet<&PathBuf>> = BTreeMap::new();
+        for project in &self.projects {
+            if project.source != ProjectSource::Local {
+                continue;
+            }
+            if let Some(repo) = project.repo_key() {
+                by_repo.entry(repo).or_default().insert(&project.path);
+            }
+        }
+
+        by_repo
+            .into_iter()
+            .filter(|(_, paths)| paths.len() > 1)
+            .map(|(repo, paths)| (repo, paths.into_iter().cloned().collect()))
+            .collect()
+    }
+
+    /// Keep one entry per repository that has a local clone: the most
+    /// recently modified clone. Other clones and remote entries for the same
+    /// repository are dropped; undated clones count as oldest.
+    pub fn collapse_multi_clones(&mut self) {
+        let mut newest: HashMap<String, (Option<DateTime<Utc>>, PathBuf)> = HashMap::new();
+        for project in &self.projects {
+            if project.source != ProjectSource::Local {
+                continue;
+            }
+            let Some(repo) = project.repo_key() else {
+                continue;
+            };
+            match newest.get(&repo) {
+                Some((modified, _)) if *modified >= project.last_modified => {}
+                _ => {
+                    newest.insert(repo, (project.last_modified, project.path.clone()));
+                }
+            }
+        }
+
+        self.projects.retain(|project| match project.repo_key() {
+            Some(repo) => newest.get(&repo).is_none_or(|(_, path)| {
+                *path == project.path && project.source == ProjectSource::Local
+            }),
+            None => true,
+        });
+    }
+
+    /// Set `qualified_name` on projects whose name another path in the list
+    /// also uses, and clear it everywhere else
+    pub fn qualify_colliding_names(&mut self) {
+        let colliding: BTreeSet<String> = self
+            .duplicate_names()
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect();
+
+        for project in &mut self.projects {
+            project.qualified_name = if colliding.contains(&project.name) {
+                project
+                    .path
+                    .parent()
+                    .and_then(Path::file_name)
+                    .map(|parent| format!("{}/{}", parent.to_string_lossy(), project.name))
+            } else {
+                None
+            };
+        }
+    }
+
+    /// Keep only projects whose remote host equals `host` (ignoring case)
+    pub fn retain_host(&mut self, host: &str) {
+        self.projects.retain(|project| {
+            project
+                .host
+                .as_deref()
+                .is_some_and(|project_host| project_host.eq_ignore_ascii_case(host))
+        });
+    }
+
+    /// Keep projects modified at or after `cutoff`; undated projects are dropped
+    pub fn retain_modified_since(&mut self, cutoff: DateTime<Utc>) {
+        self.projects.retain(|project| {
+            project
+                .last_modified
+                .is_some_and(|modified| modified >= cutoff)
+        });
+    }
+
+    /// `deduplicate_with_policy` with the default `PreferLocal` policy
+    #[allow(dead_code)]
     pub fn deduplicate(&mut self) {
-        let mut to_remove = Vec::new();
+        self.deduplicate_with_policy(DedupPolicy::PreferLocal);
+    }
+
+    /// Drop GitHub entries that point at the same path as a Local entry.
+    /// With `PreferRemoteMetadata` the remote's URL and timestamp are first
+    /// copied onto the surviving local entry.
+    pub fn deduplicate_with_policy(&mut self, policy: DedupPolicy) {
+        let remote_by_path: std::collections::HashMap<PathBuf, Project> = self
+            .projects
+            .iter()
+            .filter(|p| p.source == ProjectSource::GitHub)
+            .map(|p| (p.path.clone(), p.clone()))
+            .collect();
 
-        let local_paths: std::collections::HashSet<_> = self
+        let local_paths: std::collections::HashSet<PathBuf> = self
             .projects
             .iter()
             .filter(|p| p.source == ProjectSource::Local)
-            .map(|p| &p.path)
+            .map(|p| p.path.clone())
             .collect();
 
-        for (i, project) in self.projects.iter().enumerate() {
-            if project.source == ProjectSource::GitHub && local_paths.contains(&project.path) {
-                to_remove.push(i);
-            }
-        }
+        self.projects.retain(|project| {
+            !(project.source == ProjectSource::GitHub && local_paths.contains(&project.path))
+        });
 
-        for &i in to_remove.iter().rev() {
-            self.projects.remove(i);
+        if policy == DedupPolicy::PreferRemoteMetadata {
+            for project in &mut self.projects {
+                if project.source != ProjectSource::Local {
+                    continue;
+                }
+                if let Some(remote) = remote_by_path.get(&project.path) {
+                    project.github_url = remote.github_url.clone();
+                    if remote.last_modified.is_some() {
+                        project.last_modified = remote.last_modified;
+                    }
+                }
+            }
         }
     }
 }
@@ -288,6 +945,40 @@ mod tests {
         assert!(display.contains("(2024-01-15 10:30)"));
     }
 
+    #[test]
+    fn test_relative_age_buckets() {
+        let now = Utc.with_ymd_and_hms(2024, 6, 1, 12, 0, 0).unwrap();
+        let age = |seconds: i64| {
+            Project::new_local("proj".to_string(), "/path")
+                .with_last_modified(now - chrono::Duration::seconds(seconds))
+                .relative_age(now)
+                .unwrap()
+        };
+
+        assert_eq!(age(42), "42s ago");
+        assert_eq!(age(5 * 60), "5m ago");
+        assert_eq!(age(3 * 3600), "3h ago");
+        assert_eq!(age(3 * 86400), "3d ago");
+        assert_eq!(age(15 * 86400), "2w ago");
+        assert_eq!(age(65 * 86400), "2mo ago");
+        assert_eq!(age(800 * 86400), "2y ago");
+        assert_eq!(age(-30), "0s ago");
+
+        let undated = Project::new_local("proj".to_string(), "/path");
+        assert_eq!(undated.relative_age(now), None);
+        assert_eq!(undated.time_label(true, "%Y"), None);
+    }
+
+    #[test]
+    fn test_display_string_relative_times() {
+        let project = Project::new_local("proj".to_string(), "/path")
+            .with_last_modified(Utc::now() - chrono::Duration::days(3));
+
+        assert!(project
+            .display_string_with_label(None, true, DEFAULT_DIRTY_MARKER)
+            .contains("(3d ago)"));
+    }
+
     #[test]
     fn test_project_list_operations() {
         let mut list = ProjectList::new();
@@ -326,6 +1017,164 @@ mod tests {
         assert_eq!(list.projects()[2], no_time_project);
     }
 
+    #[test]
+    fn test_merge_prefers_higher_priority_source() {
+        let priority = [ProjectSource::Local, ProjectSource::GitHub];
+        let mut list = ProjectList::from_projects(vec![
+            Project::new_github(
+                "shared".to_string(),
+                "/code/shared",
+                "https://github.com/user/shared".to_string(),
+            ),
+            Project::new_local("kept".to_string(), "/code/kept"),
+        ]);
+
+        list.merge(
+            ProjectList::from_projects(vec![
+                Project::new_local("shared".to_string(), "/code/shared"),
+                Project::new_local("new".to_string(), "/code/new"),
+            ]),
+            &priority,
+        );
+        let entries: Vec<_> = list
+            .projects()
+            .iter()
+            .map(|p| (p.name.as_str(), p.source.clone()))
+            .collect();
+        assert_eq!(
+            entries,
+            vec![
+                ("shared", ProjectSource::Local),
+                ("kept", ProjectSource::Local),
+                ("new", ProjectSource::Local),
+            ]
+        );
+
+        // A lower-priority duplicate doesn't displace the local entry
+        list.merge(
+            ProjectList::from_projects(vec![Project::new_github(
+                "shared".to_string(),
+                "/code/shared",
+                "https://github.com/user/shared".to_string(),
+            )]),
+            &priority,
+        );
+        assert_eq!(list.len(), 3);
+        assert_eq!(list.projects()[0].source, ProjectSource::Local);
+    }
+
+    #[test]
+    fn test_merge_same_source_takes_incoming_entry() {
+        let now = Utc::now();
+        let mut list =
+            ProjectList::from_projects(vec![Project::new_local("app".to_string(), "/code/app")]);
+
+        list.merge(
+            ProjectList::from_projects(vec![
+                Project::new_local("app".to_string(), "/code/app").with_last_modified(now),
+                Project::new_cursor("other".to_string(), "/code/other"),
+            ]),
+            &[],
+        );
+
+        assert_eq!(list.len(), 2);
+        assert_eq!(list.projects()[0].last_modified, Some(now));
+    }
+
+    #[test]
+    fn test_activity_order() {
+        let now = Utc::now();
+        let project = |name: &str, dirty: Option<bool>, age_days: i64| {
+            let mut project = Project::new_local(name.to_string(), format!("/path/{}", name))
+                .with_last_modified(now - Duration::days(age_days));
+            project.dirty = dirty;
+            project
+        };
+        let dirty_recent = project("dirty-recent", Some(true), 1);
+        let dirty_old = project("dirty-old", Some(true), 90);
+        let clean_recent = project("clean-recent", Some(false), 1);
+        let clean_old = project("clean-old", Some(false), 90);
+        let unknown_recent = project("unknown-recent", None, 2);
+        let dirty_archived = project("dirty-archived", Some(true), 1).with_archived(true);
+
+        assert_eq!(activity_order(&dirty_recent, &clean_recent), Ordering::Less);
+        assert_eq!(activity_order(&dirty_old, &clean_recent), Ordering::Less);
+        assert_eq!(activity_order(&dirty_recent, &dirty_old), Ordering::Less);
+        assert_eq!(activity_order(&clean_recent, &clean_old), Ordering::Less);
+        assert_eq!(activity_order(&clean_old, &dirty_old), Ordering::Greater);
+        assert_eq!(
+            activity_order(&dirty_archived, &clean_old),
+            Ordering::Greater
+        );
+
+        let mut list = ProjectList::from_projects(vec![
+            dirty_archived,
+            clean_old,
+            unknown_recent,
+            dirty_old,
+            clean_recent,
+            dirty_recent,
+        ]);
+        list.sort_by_activity();
+
+        let names: Vec<&str> = list.projects().iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "dirty-recent",
+                "dirty-old",
+                "clean-recent",
+                "unknown-recent",
+                "clean-old",
+                "dirty-archived"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_archived_projects_sort_last() {
+        let at = |day: u32| Utc.with_ymd_and_hms(2024, 1, day, 0, 0, 0).unwrap();
+        let mut list = ProjectList::from_projects(vec![
+            Project::new_local("old".to_string(), "/p/old").with_last_modified(at(1)),
+            Project::new_local("shelved".to_string(), "/p/shelved")
+                .with_last_modified(at(20))
+                .with_archived(true),
+            Project::new_local("undated".to_string(), "/p/undated"),
+            Project::new_local("recent".to_string(), "/p/recent").with_last_modified(at(10)),
+            Project::new_local("ancient".to_string(), "/p/ancient")
+                .with_last_modified(at(2))
+                .with_archived(true),
+        ]);
+
+        list.sort_by_last_modified();
+
+        let names: Vec<&str> = list.projects().iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec!["recent", "old", "undated", "shelved", "ancient"]
+        );
+    }
+
+    #[test]
+    fn test_sort_by_size() {
+        let sized = |name: &str, size: Option<u64>| {
+            let mut project = Project::new_local(name.to_string(), format!("/path/{}", name));
+            project.size_bytes = size;
+            project
+        };
+        let mut list = ProjectList::from_projects(vec![
+            sized("unknown", None),
+            sized("small", Some(10)),
+            sized("large", Some(5_000)),
+            sized("medium", Some(800)),
+        ]);
+
+        list.sort_by_size();
+
+        let names: Vec<&str> = list.projects().iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["large", "medium", "small", "unknown"]);
+    }
+
     #[test]
     fn test_filter_by_source() {
         let projects = vec![
@@ -387,6 +1236,43 @@ mod tests {
             .any(|(_, source)| **source == ProjectSource::GitHub));
     }
 
+    #[test]
+    fn test_deduplicate_policies() {
+        let shared_path = PathBuf::from("/Users/test/my-project");
+        let local_time = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let remote_time = Utc.with_ymd_and_hms(2024, 6, 1, 0, 0, 0).unwrap();
+        let projects = vec![
+            Project::new_local("my-project".to_string(), shared_path.clone())
+                .with_last_modified(local_time),
+            Project::new_github(
+                "my-project".to_string(),
+                shared_path.clone(),
+                "https://github.com/user/my-project".to_string(),
+            )
+            .with_last_modified(remote_time),
+        ];
+
+        let mut prefer_local = ProjectList::from_projects(projects.clone());
+        prefer_local.deduplicate_with_policy(DedupPolicy::PreferLocal);
+        assert_eq!(prefer_local.len(), 1);
+        let kept = &prefer_local.projects()[0];
+        assert_eq!(kept.source, ProjectSource::Local);
+        assert_eq!(kept.github_url, None);
+        assert_eq!(kept.last_modified, Some(local_time));
+
+        let mut prefer_remote = ProjectList::from_projects(projects);
+        prefer_remote.deduplicate_with_policy(DedupPolicy::PreferRemoteMetadata);
+        assert_eq!(prefer_remote.len(), 1);
+        let kept = &prefer_remote.projects()[0];
+        assert_eq!(kept.source, ProjectSource::Local);
+        assert_eq!(kept.path, shared_path);
+        assert_eq!(
+            kept.github_url.as_deref(),
+            Some("https://github.com/user/my-project")
+        );
+        assert_eq!(kept.last_modified, Some(remote_time));
+    }
+
     #[test]
     fn test_deduplicate_no_duplicates() {
         let projects = vec![
@@ -405,4 +1291,459 @@ mod tests {
         project_list.deduplicate();
         assert_eq!(project_list.len(), original_len);
     }
+
+    #[test]
+    fn test_find_project_containing_cwd() {
+        let projects = vec![
+            Project::new_local("code".to_string(), "/home/user/code"),
+            Project::new_local("app".to_string(), "/home/user/code/app"),
+            Project::new_local("other".to_string(), "/home/user/other"),
+        ];
+
+        let cwd = Path::new("/home/user/code/app/src/bin");
+        assert_eq!(find_project_containing(&projects, cwd), Some(1));
+
+        let cwd = Path::new("/home/user/code/docs");
+        assert_eq!(find_project_containing(&projects, cwd), Some(0));
+
+        let cwd = Path::new("/home/user/other");
+        assert_eq!(find_project_containing(&projects, cwd), Some(2));
+
+        let cwd = Path::new("/home/user/otherwise");
+        assert_eq!(find_project_containing(&projects, cwd), None);
+    }
+
+    #[test]
+    fn test_display_string_with_label() {
+        let project = Project::new_local("proj-20231".to_string(), "/tmp/proj-20231");
+
+        let labeled = project.display_string_with_label(Some("Invoices"), false, "*");
+        assert!(labeled.starts_with("📁 Invoices [proj-20231]"));
+        assert_eq!(
+            project.display_string_with_label(None, false, DEFAULT_DIRTY_MARKER),
+            project.display_string()
+        );
+    }
+
+    #[test]
+    fn test_display_string_marks_dirty_projects() {
+        let mut project = Project::new_local("proj".to_string(), "/tmp/proj");
+        assert_eq!(project.display_string(), "📁 proj - /tmp/proj");
+
+        project.dirty = Some(false);
+        assert_eq!(project.display_string(), "📁 proj - /tmp/proj");
+
+        project.dirty = Some(true);
+        assert_eq!(project.display_string(), "📁 proj ● - /tmp/proj");
+        assert_eq!(
+            project.display_string_with_label(None, false, "*"),
+            "📁 proj * - /tmp/proj"
+        );
+    }
+
+    #[test]
+    fn test_duplicate_names() {
+        let mut list = ProjectList::new();
+        list.add_project(Project::new_local("utils".to_string(), "/work/org-a/utils"));
+        list.add_project(Project::new_local("utils".to_string(), "/work/org-b/utils"));
+        list.add_project(Project::new_local("utils".to_string(), "/work/org-a/utils"));
+        list.add_project(Project::new_local("api".to_string(), "/work/api"));
+
+        let duplicates = list.duplicate_names();
+
+        assert_eq!(duplicates.len(), 1);
+        assert_eq!(duplicates[0].0, "utils");
+        assert_eq!(
+            duplicates[0].1,
+            vec![
+                PathBuf::from("/work/org-a/utils"),
+                PathBuf::from("/work/org-b/utils")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_project_source_serializes_by_name() {
+        assert_eq!(
+            serde_json::to_string(&ProjectSource::GitHub).unwrap(),
+            "\"GitHub\""
+        );
+        assert_eq!(
+            serde_json::from_str::<ProjectSource>("\"Bitbucket\"").unwrap(),
+            ProjectSource::Unknown("Bitbucket".to_string())
+        );
+        assert_eq!(
+            serde_json::from_str::<ProjectSource>("\"gitlab\"").unwrap(),
+            ProjectSource::GitLab
+        );
+    }
+
+    #[test]
+    fn test_find_project_by_name_prefers_exact_match() {
+        let projects = vec![
+            Project::new_local("zapplication".to_string(), "/code/zapplication"),
+            Project::new_local("App".to_string(), "/code/app"),
+        ];
+
+        assert_eq!(
+            find_project_by_name(&projects, "app", false).map(|p| p.name.as_str()),
+            Some("App")
+        );
+        assert_eq!(
+            find_project_by_name(&projects, "applic", false).map(|p| p.name.as_str()),
+            Some("zapplication")
+        );
+        assert_eq!(
+            find_project_by_name(&projects, "APP", true).map(|p| p.name.as_str()),
+            Some("App")
+        );
+        assert!(find_project_by_name(&projects, "applic", true).is_none());
+    }
+
+    #[test]
+    fn test_projects_matching_name() {
+        let projects = vec![
+            Project::new_local("api-gateway".to_string(), "/code/api-gateway"),
+            Project::new_local("web".to_string(), "/code/web"),
+            Project::new_local("My-API".to_string(), "/code/my-api"),
+        ];
+
+        let names: Vec<&str> = projects_matching_name(&projects, "api")
+            .into_iter()
+            .map(|p| p.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["api-gateway", "My-API"]);
+        assert!(projects_matching_name(&projects, "zzz").is_empty());
+    }
+
+    #[test]
+    fn test_remote_host() {
+        assert_eq!(
+            remote_host("https://github.com/user/repo"),
+            Some("github.com".to_string())
+        );
+        assert_eq!(
+            remote_host("https://GitLab.Example.com:8443/group/sub/repo"),
+            Some("gitlab.example.com".to_string())
+        );
+        assert_eq!(
+            remote_host("git@github.enterprise.io:team/repo.git"),
+            Some("github.enterprise.io".to_string())
+        );
+        assert_eq!(
+            remote_host("ssh://git@gitlab.internal:2222/team/repo.git"),
+            Some("gitlab.internal".to_string())
+        );
+        assert_eq!(remote_host("/srv/git/repo.git"), None);
+        assert_eq!(remote_host("relative/path"), None);
+    }
+
+    #[test]
+    fn test_remote_web_url() {
+        assert_eq!(
+            remote_web_url("git@github.com:user/repo.git").as_deref(),
+            Some("https://github.com/user/repo")
+        );
+        assert_eq!(
+            remote_web_url("ssh://git@gitlab.internal:2222/team/sub/repo.git").as_deref(),
+            Some("https://gitlab.internal/team/sub/repo")
+        );
+        assert_eq!(
+            remote_web_url("https://token@GitHub.com/user/repo/").as_deref(),
+            Some("https://github.com/user/repo")
+        );
+        assert_eq!(remote_web_url("https://github.com"), None);
+        assert_eq!(remote_web_url("/srv/git/repo.git"), None);
+    }
+
+    #[test]
+    fn test_host_is_searchable_and_filterable() {
+        let github = Project::new_github(
+            "repo".to_string(),
+            "/path/github",
+            "https://github.com/user/repo".to_string(),
+        );
+        let gitlab = Project::new_gitlab(
+            "repo".to_string(),
+            "/path/gitlab",
+            "https://gitlab.example.com/user/repo".to_string(),
+        );
+        let local = Project::new_local("notes".to_string(), "/path/notes");
+
+        assert_eq!(github.search_tokens(), "repo github github.com");
+        assert_eq!(local.search_tokens(), "notes local");
+
+        let mut list = ProjectList::from_projects(vec![github, gitlab, local]);
+        list.retain_host("GITLAB.example.com");
+        assert_eq!(list.len(), 1);
+        assert_eq!(list.projects()[0].path, PathBuf::from("/path/gitlab"));
+    }
+
+    #[test]
+    fn test_parse_cutoff() {
+        let now = Utc.with_ymd_and_hms(2024, 6, 30, 12, 0, 0).unwrap();
+
+        assert_eq!(parse_cutoff("7d", now), Ok(now - Duration::days(7)));
+        assert_eq!(parse_cutoff("2w", now), Ok(now - Duration::days(14)));
+        assert_eq!(parse_cutoff("1mo", now), Ok(now - Duration::days(30)));
+        assert_eq!(parse_cutoff("12h", now), Ok(now - Duration::hours(12)));
+        assert_eq!(
+            parse_cutoff("2024-05-01", now),
+            Ok(Utc.with_ymd_and_hms(2024, 5, 1, 0, 0, 0).unwrap())
+        );
+        assert_eq!(
+            parse_cutoff("2024-05-01T08:30:00+02:00", now),
+            Ok(Utc.with_ymd_and_hms(2024, 5, 1, 6, 30, 0).unwrap())
+        );
+
+        assert!(parse_cutoff("7", now).is_err());
+        assert!(parse_cutoff("7x", now).is_err());
+        assert!(parse_cutoff("recently", now).is_err());
+    }
+
+    #[test]
+    fn test_parse_cutoff_rejects_oversized_ages() {
+        let now = Utc.with_ymd_and_hms(2024, 6, 30, 12, 0, 0).unwrap();
+
+        for input in [
+            "99999999999999d",
+            "99999999999999h",
+            "9999999999999999y",
+            "9999999999999999999y",
+            "40000000w",
+        ] {
+            let error = parse_cutoff(input, now).unwrap_err();
+            assert!(error.starts_with("invalid date or age"), "{}", error);
+        }
+    }
+
+    #[test]
+    fn test_retain_modified_since() {
+        let now = Utc.with_ymd_and_hms(2024, 6, 30, 12, 0, 0).unwrap();
+        let recent = Project::new_local("recent".to_string(), "/code/recent")
+            .with_last_modified(now - Duration::days(2));
+        let old = Project::new_local("old".to_string(), "/code/old")
+            .with_last_modified(now - Duration::days(40));
+        let undated = Project::new_local("undated".to_string(), "/code/undated");
+
+        let mut list = ProjectList::from_projects(vec![recent, old, undated]);
+        list.retain_modified_since(parse_cutoff("1w", now).unwrap());
+
+        let names: Vec<_> = list.projects().iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["recent"]);
+    }
+
+    #[test]
+    fn test_colliding_names_are_qualified() {
+        let mut list = ProjectList::from_projects(vec![
+            Project::new_local("frontend".to_string(), "/code/acme/frontend"),
+            Project::new_local("frontend".to_string(), "/code/beta/frontend"),
+            Project::new_local("backend".to_string(), "/code/acme/backend"),
+            // Same path found twice is not a collision
+            Project::new_local("tools".to_string(), "/code/tools"),
+            Project::new_cursor("tools".to_string(), "/code/tools"),
+        ]);
+        list.qualify_colliding_names();
+
+        let shown: Vec<_> = list.projects().iter().map(Project::shown_name).collect();
+        assert_eq!(
+            shown,
+            vec![
+                "acme/frontend",
+                "beta/frontend",
+                "backend",
+                "tools",
+                "tools"
+            ]
+        );
+        assert!(list.projects()[1]
+            .search_tokens()
+            .starts_with("beta/frontend "));
+    }
+
+    #[test]
+    fn test_qualified_name_clears_when_collision_goes_away() {
+        let mut list = ProjectList::from_projects(vec![
+            Project::new_local("frontend".to_string(), "/code/acme/frontend"),
+            Project::new_local("frontend".to_string(), "/code/beta/frontend"),
+        ]);
+        list.qualify_colliding_names();
+        assert_eq!(list.projects()[0].labeled_name(None), "acme/frontend");
+
+        let mut list = ProjectList::from_projects(vec![list.projects()[0].clone()]);
+        list.qualify_colliding_names();
+        assert_eq!(list.projects()[0].labeled_name(None), "frontend");
+    }
+
+    #[test]
+    fn test_neighbor_index_wraps_around() {
+        let projects: Vec<Project> = ["a", "b", "c"]
+            .iter()
+            .map(|name| Project::new_local(name.to_string(), format!("/code/{}", name)))
+            .collect();
+        let all = |_: &Project| true;
+
+        assert_eq!(neighbor_index(&projects, Some(0), Step::Next, all), Some(1));
+        assert_eq!(neighbor_index(&projects, Some(2), Step::Next, all), Some(0));
+        assert_eq!(neighbor_index(&projects, Some(0), Step::Prev, all), Some(2));
+        assert_eq!(neighbor_index(&projects, Some(2), Step::Prev, all), Some(1));
+
+        assert_eq!(neighbor_index(&projects, None, Step::Next, all), Some(0));
+        assert_eq!(neighbor_index(&projects, None, Step::Prev, all), Some(2));
+        assert_eq!(neighbor_index(&[], None, Step::Next, all), None);
+    }
+
+    #[test]
+    fn test_neighbor_index_skips_unavailable_projects() {
+        let projects: Vec<Project> = ["a", "gone", "c", "also-gone"]
+            .iter()
+            .map(|name| Project::new_local(name.to_string(), format!("/code/{}", name)))
+            .collect();
+        let exists = |project: &Project| !project.name.contains("gone");
+
+        assert_eq!(
+            neighbor_index(&projects, Some(0), Step::Next, exists),
+            Some(2)
+        );
+        assert_eq!(
+            neighbor_index(&projects, Some(2), Step::Next, exists),
+            Some(0)
+        );
+        assert_eq!(
+            neighbor_index(&projects, Some(0), Step::Prev, exists),
+            Some(2)
+        );
+
+        // Only the current project is left: it is its own neighbour
+        let only_a = |project: &Project| project.name == "a";
+        assert_eq!(
+            neighbor_index(&projects, Some(0), Step::Next, only_a),
+            Some(0)
+        );
+        assert_eq!(
+            neighbor_index(&projects, Some(0), Step::Next, |_| false),
+            None
+        );
+    }
+
+    #[test]
+    fn test_porcelain_line() {
+        let timestamp = Utc.with_ymd_and_hms(2024, 1, 15, 10, 30, 0).unwrap();
+        let project = Project::new_github(
+            "repo".to_string(),
+            "/code/repo",
+            "https://github.com/user/repo".to_string(),
+        )
+        .with_last_modified(timestamp);
+
+        let line = project.porcelain_line();
+        let columns: Vec<&str> = line.split('\t').collect();
+        assert_eq!(columns, vec!["repo", "github", "/code/repo", "1705314600"]);
+
+        let untimed = Project::new_local("plain".to_string(), "/code/plain");
+        assert_eq!(untimed.porcelain_line(), "plain\tlocal\t/code/plain\t");
+    }
+
+    #[test]
+    fn test_open_target_plain_dir() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let project = Project::new_local("app".to_string(), dir.path());
+
+        let config = Config {
+            prefer_workspace_file: true,
+            ..Config::default()
+        };
+        assert_eq!(project.open_target(&config), dir.path().as_os_str());
+    }
+
+    #[test]
+    fn test_open_target_prefers_workspace_file() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let workspace = dir.path().join("app.code-workspace");
+        std::fs::write(&workspace, "{}").unwrap();
+        std::fs::write(dir.path().join("zz.code-workspace"), "{}").unwrap();
+        let project = Project::new_local("app".to_string(), dir.path());
+
+        let preferred = Config {
+            prefer_workspace_file: true,
+            ..Config::default()
+        };
+        assert_eq!(project.open_target(&preferred), workspace.as_os_str());
+        assert_eq!(
+            project.open_target(&Config::default()),
+            dir.path().as_os_str()
+        );
+    }
+
+    #[test]
+    fn test_open_target_explicit_subpath() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir(dir.path().join("web")).unwrap();
+        std::fs::write(dir.path().join("app.code-workspace"), "{}").unwrap();
+        let project = Project::new_local("app".to_string(), dir.path());
+
+        let key = std::fs::canonicalize(dir.path())
+            .unwrap()
+            .to_string_lossy()
+            .into_owned();
+        let mut config = Config {
+            prefer_workspace_file: true,
+            ..Config::default()
+        };
+        config
+            .open_subpaths
+            .insert(key.clone(), PathBuf::from("web"));
+        assert_eq!(
+            project.open_target(&config),
+            dir.path().join("web").as_os_str()
+        );
+
+        // A subpath that doesn't exist falls back to the other rules
+        config.open_subpaths.insert(key, PathBuf::from("gone"));
+        assert_eq!(
+            project.open_target(&config),
+            dir.path().join("app.code-workspace").as_os_str()
+        );
+    }
+
+    #[test]
+    fn test_multi_clones_and_collapse() {
+        let older = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let newer = Utc.with_ymd_and_hms(2024, 6, 1, 0, 0, 0).unwrap();
+        let mut list = ProjectList::from_projects(vec![
+            Project::new_local("app".to_string(), "/work/app")
+                .with_remote_url(Some("git@github.com:me/app.git"))
+                .with_last_modified(older),
+            Project::new_local("app".to_string(), "/scratch/app")
+                .with_remote_url(Some("https://github.com/Me/app"))
+                .with_last_modified(newer),
+            Project::new_github(
+                "app".to_string(),
+                "/clones/app",
+                "https://github.com/me/app".to_string(),
+            ),
+            Project::new_local("other".to_string(), "/work/other")
+                .with_remote_url(Some("git@github.com:me/other.git")),
+            Project::new_local("notes".to_string(), "/work/notes"),
+        ]);
+
+        assert_eq!(
+            list.multi_clones(),
+            vec![(
+                "https://github.com/me/app".to_string(),
+                vec![PathBuf::from("/scratch/app"), PathBuf::from("/work/app")]
+            )]
+        );
+
+        list.collapse_multi_clones();
+        let paths: Vec<&Path> = list.projects().iter().map(|p| p.path.as_path()).collect();
+        assert_eq!(
+            paths,
+            vec![
+                Path::new("/scratch/app"),
+                Path::new("/work/other"),
+                Path::new("/work/notes")
+            ]
+        );
+    }
 }
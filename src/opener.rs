@@ -1,22 +1,321 @@
-use crate::config::Config;
+use crate::config::{AlreadyOpenAction, Config};
 use crate::models::{Project, ProjectSource};
+use crate::output::{say, trace};
+use crate::project_settings::{ProjectSettings, PROJECT_SETTINGS_FILE};
 use anyhow::{Context, Result};
-use std::path::Path;
-use std::process::Command;
+use std::collections::HashSet;
+use std::ffi::OsStr;
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+/// Opens projects in the editor. `ProjectOpener` shells out; tests can
+/// substitute an implementation that records what would have been opened.
+pub trait Opener: Send + Sync {
+    /// Clone the project first if needed, then open it
+    fn open_project(&self, project: &Project, config: &Config) -> Result<()>;
+
+    /// Open a directory or file as is, without cloning
+    #[allow(dead_code)]
+    fn open_project_path(&self, path: &Path, config: &Config) -> Result<()>;
+}
 
 pub struct ProjectOpener;
 
+/// What to do when a remote project needs to be cloned before opening
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CloneDecision {
+    Clone,
+    Prompt,
+    Refuse,
+}
+
+/// What is at a remote project's clone path
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CloneState {
+    /// A checked-out repository (or something we shouldn't touch)
+    Ready,
+    Missing,
+    /// An empty directory, e.g. created before a clone failed
+    Empty,
+    /// Only a `.git` directory without a resolvable HEAD, left by a clone
+    /// of ours that never finished (see `clone_marker`)
+    Incomplete,
+}
+
+/// File next to a clone path that exists while `git clone` runs into it. A
+/// leftover marker means that clone was killed, so its `.git` is safe to
+/// remove; a bare `git init` or an empty repository has no marker.
+fn clone_marker(path: &Path) -> PathBuf {
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    path.with_file_name(format!(".{}.sw-cloning", name))
+}
+
+fn clone_state(path: &Path) -> CloneState {
+    if !path.exists() {
+        return CloneState::Missing;
+    }
+    if !path.is_dir() {
+        return CloneState::Ready;
+    }
+
+    let entries: Vec<_> = match std::fs::read_dir(path) {
+        Ok(entries) => entries.filter_map(|entry| entry.ok()).collect(),
+        Err(_) => return CloneState::Ready,
+    };
+
+    if entries.is_empty() {
+        return CloneState::Empty;
+    }
+
+    let only_git_dir = entries.len() == 1 && entries[0].file_name() == ".git";
+    let has_head = git2::Repository::open(path)
+        .and_then(|repo| repo.head().map(|_| ()))
+        .is_ok();
+
+    if only_git_dir && !has_head && clone_marker(path).exists() {
+        CloneState::Incomplete
+    } else {
+        CloneState::Ready
+    }
+}
+
+/// How to open a project, given `on_already_open`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LaunchPlan {
+    Open,
+    Focus,
+    Skip,
+}
+
+fn launch_plan(action: AlreadyOpenAction, already_open: bool) -> LaunchPlan {
+    match (action, already_open) {
+        (_, false) | (AlreadyOpenAction::Reopen, true) => LaunchPlan::Open,
+        (AlreadyOpenAction::Focus, true) => LaunchPlan::Focus,
+        (AlreadyOpenAction::Noop, true) => LaunchPlan::Skip,
+    }
+}
+
+fn clone_decision(config: &Config, is_tty: bool) -> CloneDecision {
+    if !config.confirm_clone {
+        CloneDecision::Clone
+    } else if is_tty {
+        CloneDecision::Prompt
+    } else if config.clone_without_tty {
+        CloneDecision::Clone
+    } else {
+        CloneDecision::Refuse
+    }
+}
+
 impl ProjectOpener {
     pub fn new() -> Self {
         Self
     }
 
-    pub fn open_project(&self, project: &Project, config: &Config) -> Result<()> {
-        if project.source == ProjectSource::GitHub && !project.path.exists() {
-            self.clone_github_project(project)?;
+    /// Open `project` with `open_command` when configured, otherwise hand
+    /// its open target to the editor
+    fn launch(&self, project: &Project, config: &Config) -> Result<()> {
+        let (path, name) = (project.path.as_path(), project.name.as_str());
+        if !path.exists() {
+            anyhow::bail!("Project path does not exist: {}", path.display());
         }
 
-        self.open_project_path(&project.path, config)
+        if let Some(command) = open_command(config) {
+            let status = build_open_command(command, path, name)
+                .status()
+                .with_context(|| format!("Failed to run open command: {}", command))?;
+            if !status.success() {
+                anyhow::bail!("Open command failed ({}): {}", status, command);
+            }
+            return Ok(());
+        }
+
+        if config.editor_command.trim().is_empty() {
+            anyhow::bail!("Editor command is empty");
+        }
+
+        let editor_command = config.resolved_editor_command();
+        let target = project.open_target(config);
+        let (editor, mut cmd) = build_editor_command(Path::new(&target), &editor_command, config)?;
+
+        if is_background_editor(editor) {
+            cmd.spawn()
+                .with_context(|| format!("Failed to launch editor: {}", editor_command))?;
+        } else {
+            let output = cmd
+                .output()
+                .with_context(|| format!("Failed to execute editor command: {}", editor_command))?;
+
+            if !output.status.success() {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                anyhow::bail!("Editor command failed: {}", stderr);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Whether the configured editor has `path` open already. Only checked
+    /// when `on_already_open` would act on it and no `open_command` hides
+    /// which editor is used.
+    fn is_already_open(&self, project: &Project, config: &Config) -> bool {
+        if config.on_already_open == AlreadyOpenAction::Reopen || open_command(config).is_some() {
+            return false;
+        }
+
+        let editor_command = config.resolved_editor_command();
+        let Some(editor) = editor_command.split_whitespace().next() else {
+            return false;
+        };
+        let editor = Path::new(editor)
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        is_open_in_editor(&project.path, &editor)
+    }
+
+    /// Check whether a remote project still has to be cloned, asking for
+    /// confirmation when configured and clearing out an interrupted clone
+    fn prepare_clone(&self, project: &Project, config: &Config) -> Result<bool> {
+        let clone_state = if project.source == ProjectSource::GitHub {
+            clone_state(&project.path)
+        } else {
+            CloneState::Ready
+        };
+
+        if clone_state == CloneState::Ready {
+            return Ok(false);
+        }
+
+        match clone_decision(config, std::io::stdin().is_terminal()) {
+            CloneDecision::Clone => {}
+            CloneDecision::Prompt => {
+                if !self.confirm_clone(project)? {
+                    anyhow::bail!("Clone of {} cancelled", project.name);
+                }
+            }
+            CloneDecision::Refuse => {
+                anyhow::bail!(
+                    "Refusing to clone {} without confirmation (no TTY available)",
+                    project.name
+                );
+            }
+        }
+        if clone_state == CloneState::Incomplete {
+            say!("Removing incomplete clone at {}", project.path.display());
+            std::fs::remove_dir_all(&project.path).with_context(|| {
+                format!(
+                    "Failed to remove incomplete clone: {}",
+                    project.path.display()
+                )
+            })?;
+            let _ = std::fs::remove_file(clone_marker(&project.path));
+        }
+        Ok(true)
+    }
+
+    /// Open several projects. Remotes that aren't cloned yet are cloned
+    /// first, `clone_concurrency` at a time; projects whose clone failed
+    /// are skipped and reported in the returned error.
+    pub fn open_projects(&self, projects: &[Project], config: &Config) -> Result<()> {
+        let mut to_clone = Vec::new();
+        for project in projects {
+            if self.prepare_clone(project, config)? {
+                to_clone.push(project);
+            }
+        }
+
+        let failed: Vec<_> = self
+            .clone_limited(&to_clone, config)
+            .into_iter()
+            .filter_map(|(project, result)| result.err().map(|e| (project, e)))
+            .collect();
+
+        for project in projects {
+            if failed.iter().any(|(failed, _)| failed.path == project.path) {
+                continue;
+            }
+            self.launch(project, config)?;
+            self.run_post_open_command(&project.path, config)?;
+        }
+
+        if !failed.is_empty() {
+            let details: Vec<String> = failed
+                .iter()
+                .map(|(project, e)| format!("{}: {}", project.name, e))
+                .collect();
+            anyhow::bail!("Failed to clone:\n  {}", details.join("\n  "));
+        }
+        Ok(())
+    }
+
+    /// Clone remote projects that aren't checked out yet without opening
+    /// them, `clone_concurrency` at a time. Returns each project's outcome in
+    /// input order; a declined or refused confirmation counts as a failure.
+    pub fn clone_projects<'a>(
+        &self,
+        projects: &[&'a Project],
+        config: &Config,
+    ) -> Vec<(&'a Project, Result<()>)> {
+        let mut outcomes = Vec::new();
+        let mut to_clone = Vec::new();
+        for &project in projects {
+            match self.prepare_clone(project, config) {
+                Ok(true) => to_clone.push(project),
+                Ok(false) => outcomes.push((project, Ok(()))),
+                Err(e) => outcomes.push((project, Err(e))),
+            }
+        }
+
+        outcomes.extend(self.clone_limited(&to_clone, config));
+        outcomes.sort_by_key(|(project, _)| {
+            projects
+                .iter()
+                .position(|candidate| std::ptr::eq(*candidate, *project))
+        });
+        outcomes
+    }
+
+    /// Clone `projects` on up to `clone_concurrency` threads, printing
+    /// `[k/n] name` as each one finishes
+    fn clone_limited<'a>(
+        &self,
+        projects: &[&'a Project],
+        config: &Config,
+    ) -> Vec<(&'a Project, Result<()>)> {
+        let total = projects.len();
+        let finished = AtomicUsize::new(0);
+        run_limited(projects, config.clone_concurrency, |project| {
+            let result = self.clone_github_project(project);
+            let done = finished.fetch_add(1, Ordering::SeqCst) + 1;
+            say!("[{}/{}] {}", done, total, project.name);
+            result
+        })
+    }
+
+    /// Spawn the configured post-open command detached, if there is one
+    fn run_post_open_command(&self, path: &Path, config: &Config) -> Result<()> {
+        let Some(command) = post_open_command(path, config) else {
+            return Ok(());
+        };
+
+        build_post_open_command(&command, path)
+            .spawn()
+            .with_context(|| format!("Failed to run post-open command: {}", command))?;
+        Ok(())
+    }
+
+    fn confirm_clone(&self, project: &Project) -> Result<bool> {
+        let repo = project.github_url.as_deref().unwrap_or(&project.name);
+
+        dialoguer::Confirm::new()
+            .with_prompt(format!("Clone {} into {}?", repo, project.path.display()))
+            .default(false)
+            .interact()
+            .context("Failed to get clone confirmation")
     }
 
     fn clone_github_project(&self, project: &Project) -> Result<()> {
@@ -30,76 +329,345 @@ impl ProjectOpener {
                 .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
         }
 
-        println!("Cloning GitHub repository: {}", github_url);
+        say!("Cloning GitHub repository: {}", github_url);
+        trace!("$ git clone {} {}", github_url, project.path.display());
 
+        // git removes what it created when it fails, only a kill leaves it
+        let marker = clone_marker(&project.path);
+        std::fs::write(&marker, github_url)
+            .with_context(|| format!("Failed to create clone marker: {}", marker.display()))?;
         let output = Command::new("git")
             .args(["clone", github_url, &project.path.to_string_lossy()])
-            .output()
-            .context("Failed to execute git clone command")?;
+            .output();
+        let _ = std::fs::remove_file(&marker);
+        let output = output.context("Failed to execute git clone command")?;
 
         if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr);
             anyhow::bail!("Git clone failed: {}", stderr);
         }
 
-        println!(
+        say!(
             "Successfully cloned {} to {}",
             project.name,
             project.path.display()
         );
         Ok(())
     }
+}
 
-    pub fn open_project_path<P: AsRef<Path>>(&self, path: P, config: &Config) -> Result<()> {
-        let path = path.as_ref();
+impl Opener for ProjectOpener {
+    fn open_project(&self, project: &Project, config: &Config) -> Result<()> {
+        if self.prepare_clone(project, config)? {
+            self.clone_github_project(project)?;
+        }
 
-        if config.editor_command.trim().is_empty() {
-            anyhow::bail!("Editor command is empty");
+        match launch_plan(
+            config.on_already_open,
+            self.is_already_open(project, config),
+        ) {
+            LaunchPlan::Open => {
+                self.launch(project, config)?;
+                self.run_post_open_command(&project.path, config)
+            }
+            // Opening an open folder without window flags focuses its window
+            LaunchPlan::Focus => {
+                let focus = Config {
+                    reuse_window: false,
+                    open_in_current_window: false,
+                    ..config.clone()
+                };
+                self.launch(project, &focus)
+            }
+            LaunchPlan::Skip => {
+                say!("{} is already open, leaving it as is", project.name);
+                Ok(())
+            }
         }
+    }
 
-        if !path.exists() {
-            anyhow::bail!("Project path does not exist: {}", path.display());
+    fn open_project_path(&self, path: &Path, config: &Config) -> Result<()> {
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        self.launch(&Project::new_local(name, path), config)
+    }
+}
+
+/// Whether a remote project still has to be cloned before it can be opened
+pub fn needs_clone(project: &Project) -> bool {
+    project.source == ProjectSource::GitHub && clone_state(&project.path) != CloneState::Ready
+}
+
+impl Default for ProjectOpener {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The post-open command for `path`. A `.sw.toml` in the project overrides
+/// the global setting, but only for projects listed in `trusted_projects`:
+/// any cloned repository could ship one.
+fn post_open_command(path: &Path, config: &Config) -> Option<String> {
+    let dir = working_dir(path);
+    let settings = ProjectSettings::load(dir).unwrap_or_else(|e| {
+        eprintln!("Warning: {:#}", e);
+        ProjectSettings::default()
+    });
+
+    let project_command = match settings.post_open_command {
+        Some(_) if !config.trusts_project(dir) => {
+            eprintln!(
+                "Warning: Ignoring post_open_command in {}; add {} to trusted_projects to run it",
+                dir.join(PROJECT_SETTINGS_FILE).display(),
+                dir.display()
+            );
+            None
         }
+        command => command,
+    };
 
-        let parts: Vec<&str> = config.editor_command.split_whitespace().collect();
-        if parts.is_empty() {
-            anyhow::bail!("Editor command is empty");
+    project_command
+        .or_else(|| config.post_open_command.clone())
+        .filter(|command| !command.trim().is_empty())
+}
+
+/// Run `task` for each project on up to `limit` threads, returning the
+/// results in input order. A path listed more than once is only handled
+/// for its first project, so the same directory is never cloned twice.
+fn run_limited<'a, T: Send>(
+    projects: &[&'a Project],
+    limit: usize,
+    task: impl Fn(&Project) -> Result<T> + Sync,
+) -> Vec<(&'a Project, Result<T>)> {
+    let mut seen = HashSet::new();
+    let unique: Vec<&Project> = projects
+        .iter()
+        .copied()
+        .filter(|project| seen.insert(&project.path))
+        .collect();
+
+    let next = AtomicUsize::new(0);
+    let results: Mutex<Vec<Option<Result<T>>>> = Mutex::new(unique.iter().map(|_| None).collect());
+
+    std::thread::scope(|scope| {
+        for _ in 0..limit.max(1).min(unique.len()) {
+            scope.spawn(|| loop {
+                let index = next.fetch_add(1, Ordering::SeqCst);
+                let Some(project) = unique.get(index) else {
+                    break;
+                };
+                let result = task(project);
+                results.lock().unwrap()[index] = Some(result);
+            });
         }
+    });
 
-        let editor = parts[0];
-        let args = &parts[1..];
+    let results = results.into_inner().unwrap();
+    unique
+        .into_iter()
+        .zip(results)
+        .map(|(project, result)| (project, result.expect("every project is processed")))
+        .collect()
+}
 
-        let mut cmd = Command::new(editor);
-        cmd.args(args);
-        cmd.arg(path.as_os_str());
+/// The configured `open_command`, unless it is blank
+fn open_command(config: &Config) -> Option<&str> {
+    config
+        .open_command
+        .as_deref()
+        .filter(|command| !command.trim().is_empty())
+}
 
-        if is_background_editor(editor) {
-            cmd.spawn()
-                .with_context(|| format!("Failed to launch editor: {}", config.editor_command))?;
-        } else {
-            let output = cmd.output().with_context(|| {
-                format!(
-                    "Failed to execute editor command: {}",
-                    config.editor_command
-                )
-            })?;
+/// `open_command` for the project at `path`, with `{path}` and `{name}` (or
+/// `$1` and `$2`) bound to its path and name
+fn build_open_command(command: &str, path: &Path, name: &str) -> Command {
+    shell_command(
+        command,
+        working_dir(path),
+        &[("{path}", path.as_os_str()), ("{name}", OsStr::new(name))],
+    )
+}
 
-            if !output.status.success() {
-                let stderr = String::from_utf8_lossy(&output.stderr);
-                anyhow::bail!("Editor command failed: {}", stderr);
-            }
+/// `command` run through the platform shell in `dir`. Each placeholder in
+/// `args`, e.g. `{path}`, is bound to its value: with `sh` the values are
+/// passed as `$1`, `$2`, … and the placeholders become quoted references to
+/// them, so a value can never be run as shell code. Quotes a config already
+/// has around a placeholder (`"{path}"`, `'{path}'`) are dropped first.
+pub fn shell_command(command: &str, dir: &Path, args: &[(&str, &OsStr)]) -> Command {
+    let mut cmd = if cfg!(windows) {
+        // cmd has no positional parameters; quote the values in place
+        let script = args
+            .iter()
+            .fold(command.to_string(), |script, (name, value)| {
+                let value = value.to_string_lossy().replace('"', "");
+                replace_placeholder(&script, name, &format!("\"{}\"", value))
+            });
+        let mut cmd = Command::new("cmd");
+        cmd.arg("/C").arg(script);
+        cmd
+    } else {
+        let script = args
+            .iter()
+            .enumerate()
+            .fold(command.to_string(), |script, (i, (name, _))| {
+                replace_placeholder(&script, name, &format!("\"${}\"", i + 1))
+            });
+        let mut cmd = Command::new("sh");
+        cmd.arg("-c").arg(script).arg("sh");
+        cmd.args(args.iter().map(|(_, value)| value));
+        cmd
+    };
+    cmd.current_dir(dir);
+    cmd
+}
+
+/// `script` with every `placeholder` replaced by `value`, including any
+/// quotes around it, so an already quoted placeholder isn't quoted twice
+fn replace_placeholder(script: &str, placeholder: &str, value: &str) -> String {
+    script
+        .replace(&format!("\"{}\"", placeholder), value)
+        .replace(&format!("'{}'", placeholder), value)
+        .replace(placeholder, value)
+}
+
+/// The post-open command for the project at `path`, run detached in its
+/// directory with `{path}` (or `$1`) bound to the path
+fn build_post_open_command(command: &str, path: &Path) -> Command {
+    let mut cmd = shell_command(command, working_dir(path), &[("{path}", path.as_os_str())]);
+    cmd.stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null());
+    cmd
+}
+
+/// Directory a project lives in: the path itself, or the parent directory
+/// when the project is a single file
+fn working_dir(path: &Path) -> &Path {
+    if path.is_file() {
+        path.parent().unwrap_or(path)
+    } else {
+        path
+    }
+}
+
+/// The editor binary name and the command that opens `path` in it
+fn build_editor_command<'a>(
+    path: &Path,
+    editor_command: &'a str,
+    config: &Config,
+) -> Result<(&'a str, Command)> {
+    let parts: Vec<&str> = editor_command.split_whitespace().collect();
+    if parts.is_empty() {
+        anyhow::bail!("Editor command is empty");
+    }
+
+    let editor = parts[0];
+    let args = &parts[1..];
+
+    let mut cmd = Command::new(editor);
+    cmd.args(args);
+    if config.reuse_window {
+        if let Some(flag) = reuse_window_flag(editor) {
+            cmd.arg(flag);
         }
+    }
+    // Adding to the workspace only makes sense for folders
+    if config.open_in_current_window && !path.is_file() {
+        if let Some(flag) = current_window_flag(editor) {
+            cmd.arg(flag);
+        }
+    }
+    cmd.arg(path.as_os_str());
 
-        Ok(())
+    Ok((editor, cmd))
+}
+
+/// Flag that makes the editor reuse its last active window
+fn reuse_window_flag(editor: &str) -> Option<&'static str> {
+    match editor {
+        "cursor" | "code" => Some("--reuse-window"),
+        _ => None,
     }
 }
 
-impl Default for ProjectOpener {
-    fn default() -> Self {
-        Self::new()
+/// Flag that adds a folder to the focused window, for editors that have one
+fn current_window_flag(editor: &str) -> Option<&'static str> {
+    match editor {
+        "cursor" | "code" | "subl" | "atom" => Some("--add"),
+        _ => None,
     }
 }
 
+/// Best-effort check whether `editor` has `path` open. code and cursor record
+/// their windows' folders as `file://` URIs under `windowsState` in
+/// `<config dir>/<Code|Cursor>/User/globalStorage/storage.json`. The file is
+/// written lazily, so a window opened moments ago can be missed and one
+/// closed since can still be listed. Other editors are never reported open.
+pub fn is_open_in_editor(path: &Path, editor: &str) -> bool {
+    let app_dir = match editor {
+        "code" => "Code",
+        "cursor" => "Cursor",
+        _ => return false,
+    };
+    let Some(storage_path) =
+        dirs::config_dir().map(|dir| dir.join(app_dir).join("User/globalStorage/storage.json"))
+    else {
+        return false;
+    };
+    let Some(storage) = std::fs::read_to_string(&storage_path)
+        .ok()
+        .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+    else {
+        return false;
+    };
+
+    let folders = open_folders(&storage);
+    folders.iter().any(|folder| folder == path)
+        || std::fs::canonicalize(path).is_ok_and(|canonical| folders.contains(&canonical))
+}
+
+/// Folders of the windows listed in an editor's `storage.json`
+fn open_folders(storage: &serde_json::Value) -> Vec<PathBuf> {
+    let windows_state = &storage["windowsState"];
+    std::iter::once(&windows_state["lastActiveWindow"])
+        .chain(
+            windows_state["openedWindows"]
+                .as_array()
+                .into_iter()
+                .flatten(),
+        )
+        .filter_map(|window| window["folder"].as_str())
+        .filter_map(file_uri_to_path)
+        .collect()
+}
+
+/// Path of a `file://` URI, with percent-escapes decoded
+fn file_uri_to_path(uri: &str) -> Option<PathBuf> {
+    let encoded = uri.strip_prefix("file://")?;
+    let bytes = encoded.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = (bytes[i] == b'%')
+            .then(|| bytes.get(i + 1..i + 3))
+            .flatten()
+            .and_then(|hex| u8::from_str_radix(std::str::from_utf8(hex).ok()?, 16).ok());
+        match escaped {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    Some(PathBuf::from(String::from_utf8(decoded).ok()?))
+}
+
 fn is_background_editor(editor: &str) -> bool {
     matches!(editor, "cursor" | "code" | "subl" | "atom")
 }
@@ -198,6 +766,7 @@ mod tests {
         config.set_editor("echo".to_string());
 
         let temp_dir = TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join("README.md"), "# existing").unwrap();
         let project = create_github_project(
             "existing-repo",
             temp_dir.path(),
@@ -208,4 +777,468 @@ mod tests {
 
         let _ = result;
     }
+
+    #[test]
+    fn test_clone_decision() {
+        let mut config = Config::default();
+        assert_eq!(clone_decision(&config, true), CloneDecision::Clone);
+        assert_eq!(clone_decision(&config, false), CloneDecision::Clone);
+
+        config.confirm_clone = true;
+        assert_eq!(clone_decision(&config, true), CloneDecision::Prompt);
+        assert_eq!(clone_decision(&config, false), CloneDecision::Clone);
+
+        config.clone_without_tty = false;
+        assert_eq!(clone_decision(&config, true), CloneDecision::Prompt);
+        assert_eq!(clone_decision(&config, false), CloneDecision::Refuse);
+    }
+
+    #[test]
+    fn test_post_open_command_skipped_when_unset() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = Config::default();
+
+        assert_eq!(post_open_command(temp_dir.path(), &config), None);
+    }
+
+    #[test]
+    fn test_post_open_command_project_override_needs_trust() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(
+            temp_dir.path().join(PROJECT_SETTINGS_FILE),
+            "post_open_command = \"npm run dev\"\n",
+        )
+        .unwrap();
+        let mut config = Config {
+            post_open_command: Some("cargo watch".to_string()),
+            ..Config::default()
+        };
+
+        // A repository's own settings are ignored until the user trusts it
+        assert_eq!(
+            post_open_command(temp_dir.path(), &config),
+            Some("cargo watch".to_string())
+        );
+
+        config.trusted_projects.push(temp_dir.path().to_path_buf());
+        assert_eq!(
+            post_open_command(temp_dir.path(), &config),
+            Some("npm run dev".to_string())
+        );
+    }
+
+    #[test]
+    fn test_build_post_open_command() {
+        let temp_dir = TempDir::new().unwrap();
+        let cmd = build_post_open_command("cargo watch -C {path}", temp_dir.path());
+
+        assert_eq!(cmd.get_current_dir(), Some(temp_dir.path()));
+        if cfg!(unix) {
+            let args: Vec<_> = cmd.get_args().collect();
+            assert_eq!(
+                args,
+                vec![
+                    OsStr::new("-c"),
+                    OsStr::new("cargo watch -C \"$1\""),
+                    OsStr::new("sh"),
+                    temp_dir.path().as_os_str(),
+                ]
+            );
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_shell_command_never_runs_placeholder_values() {
+        let temp_dir = TempDir::new().unwrap();
+        let project = temp_dir.path().join("it's $(touch pwned); a dir");
+        std::fs::create_dir(&project).unwrap();
+
+        let output = shell_command(
+            "printf %s {path}",
+            temp_dir.path(),
+            &[("{path}", project.as_os_str())],
+        )
+        .output()
+        .unwrap();
+
+        assert_eq!(output.stdout, project.as_os_str().as_encoded_bytes());
+        assert!(!temp_dir.path().join("pwned").exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_shell_command_unquotes_quoted_placeholders() {
+        let temp_dir = TempDir::new().unwrap();
+        let project = temp_dir.path().join("my project");
+        std::fs::create_dir(&project).unwrap();
+
+        for command in [
+            "printf '<%s>' \"{path}\"",
+            "printf '<%s>' '{path}'",
+            "printf '<%s>' {path}",
+        ] {
+            let output =
+                shell_command(command, temp_dir.path(), &[("{path}", project.as_os_str())])
+                    .output()
+                    .unwrap();
+            assert_eq!(
+                String::from_utf8(output.stdout).unwrap(),
+                format!("<{}>", project.display()),
+                "{}",
+                command
+            );
+        }
+    }
+
+    #[test]
+    fn test_open_command_binds_placeholders() {
+        let config = Config {
+            open_command: Some("open -a Finder {path} # {name}".to_string()),
+            ..Config::default()
+        };
+        let command = open_command(&config).unwrap();
+
+        if cfg!(unix) {
+            let cmd = build_open_command(command, Path::new("/code/sw"), "sw");
+            let args: Vec<_> = cmd.get_args().collect();
+            assert_eq!(
+                args,
+                vec![
+                    "-c",
+                    "open -a Finder \"$1\" # \"$2\"",
+                    "sh",
+                    "/code/sw",
+                    "sw"
+                ]
+            );
+        }
+        assert_eq!(open_command(&Config::default()), None);
+    }
+
+    #[test]
+    fn test_run_limited_caps_concurrency_and_skips_duplicate_paths() {
+        let projects: Vec<Project> = (0..6)
+            .map(|i| {
+                Project::new_github(
+                    format!("repo-{}", i),
+                    format!("/clones/repo-{}", i),
+                    format!("https://github.com/user/repo-{}", i),
+                )
+            })
+            .chain(std::iter::once(Project::new_github(
+                "repo-0-again".to_string(),
+                "/clones/repo-0",
+                "https://github.com/user/repo-0".to_string(),
+            )))
+            .collect();
+        let refs: Vec<&Project> = projects.iter().collect();
+
+        let active = AtomicUsize::new(0);
+        let peak = AtomicUsize::new(0);
+        let runs = Mutex::new(Vec::new());
+        let results = run_limited(&refs, 2, |project| {
+            let now = active.fetch_add(1, Ordering::SeqCst) + 1;
+            peak.fetch_max(now, Ordering::SeqCst);
+            std::thread::sleep(std::time::Duration::from_millis(20));
+            runs.lock().unwrap().push(project.path.clone());
+            active.fetch_sub(1, Ordering::SeqCst);
+            Ok(project.name.clone())
+        });
+
+        assert!(peak.load(Ordering::SeqCst) <= 2);
+        assert_eq!(runs.lock().unwrap().len(), 6);
+        let names: Vec<String> = results
+            .into_iter()
+            .map(|(_, result)| result.unwrap())
+            .collect();
+        assert_eq!(
+            names,
+            (0..6).map(|i| format!("repo-{}", i)).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_run_limited_reports_failures_per_project() {
+        let projects = [
+            Project::new_local("ok".to_string(), "/code/ok"),
+            Project::new_local("broken".to_string(), "/code/broken"),
+        ];
+        let refs: Vec<&Project> = projects.iter().collect();
+
+        let results = run_limited(&refs, 0, |project| {
+            if project.name == "broken" {
+                anyhow::bail!("clone failed")
+            }
+            Ok(())
+        });
+
+        assert!(results[0].1.is_ok());
+        assert_eq!(results[1].0.name, "broken");
+        assert!(results[1].1.is_err());
+    }
+
+    #[test]
+    fn test_switch_command_expands_placeholders() {
+        let config = Config {
+            open_command: Some("open {path}".to_string()),
+            editor_switch_command: Some("code --reuse-window {path} # {name}".to_string()),
+            ..Config::default()
+        };
+
+        assert_eq!(
+            open_command(&config.for_switching()),
+            Some("code --reuse-window {path} # {name}")
+        );
+        assert_eq!(open_command(&config), Some("open {path}"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_open_command_takes_precedence_over_editor() {
+        let temp_dir = TempDir::new().unwrap();
+        let marker = temp_dir.path().join("opened");
+        let opener = ProjectOpener::new();
+
+        let config = Config {
+            editor_command: "false".to_string(),
+            open_command: Some(format!("echo {{name}} > {}", marker.display())),
+            ..Config::default()
+        };
+        opener.open_project_path(temp_dir.path(), &config).unwrap();
+
+        let name = temp_dir.path().file_name().unwrap().to_string_lossy();
+        assert_eq!(std::fs::read_to_string(&marker).unwrap().trim(), name);
+
+        let failing = Config {
+            editor_command: "true".to_string(),
+            open_command: Some("exit 3".to_string()),
+            ..Config::default()
+        };
+        assert!(opener.open_project_path(temp_dir.path(), &failing).is_err());
+    }
+
+    #[test]
+    fn test_launch_plan_for_each_action() {
+        use AlreadyOpenAction::*;
+
+        assert_eq!(launch_plan(Focus, true), LaunchPlan::Focus);
+        assert_eq!(launch_plan(Reopen, true), LaunchPlan::Open);
+        assert_eq!(launch_plan(Noop, true), LaunchPlan::Skip);
+
+        // Projects that aren't open are always opened normally
+        for action in [Focus, Reopen, Noop] {
+            assert_eq!(launch_plan(action, false), LaunchPlan::Open);
+        }
+    }
+
+    #[test]
+    fn test_open_folders_from_editor_storage() {
+        let storage = serde_json::json!({
+            "windowsState": {
+                "lastActiveWindow": {"folder": "file:///home/me/code/my%20app"},
+                "openedWindows": [
+                    {"folder": "file:///home/me/code/api"},
+                    {"workspace": {"configPath": "file:///home/me/ws.code-workspace"}},
+                    {"folder": "vscode-remote://ssh-remote%2Bbox/srv/app"}
+                ]
+            }
+        });
+
+        assert_eq!(
+            open_folders(&storage),
+            vec![
+                PathBuf::from("/home/me/code/my app"),
+                PathBuf::from("/home/me/code/api"),
+            ]
+        );
+        assert!(open_folders(&serde_json::json!({})).is_empty());
+    }
+
+    #[test]
+    fn test_unsupported_editor_is_never_open() {
+        assert!(!is_open_in_editor(Path::new("/"), "vim"));
+    }
+
+    #[test]
+    fn test_current_window_flag() {
+        assert_eq!(current_window_flag("code"), Some("--add"));
+        assert_eq!(current_window_flag("cursor"), Some("--add"));
+        assert_eq!(current_window_flag("vim"), None);
+        assert_eq!(current_window_flag("nano"), None);
+    }
+
+    fn editor_args(editor_command: &str, reuse_window: bool) -> Vec<String> {
+        let config = Config {
+            editor_command: editor_command.to_string(),
+            reuse_window,
+            ..Config::default()
+        };
+        let (_, cmd) =
+            build_editor_command(Path::new("/tmp/project"), &config.editor_command, &config)
+                .unwrap();
+        cmd.get_args()
+            .map(|arg| arg.to_string_lossy().into_owned())
+            .collect()
+    }
+
+    #[test]
+    fn test_reuse_window_flag() {
+        for editor in ["code", "cursor"] {
+            assert_eq!(
+                editor_args(editor, true),
+                vec!["--reuse-window", "/tmp/project"]
+            );
+            assert_eq!(editor_args(editor, false), vec!["/tmp/project"]);
+        }
+
+        assert_eq!(editor_args("subl", true), vec!["/tmp/project"]);
+        assert_eq!(editor_args("atom", true), vec!["/tmp/project"]);
+        assert_eq!(
+            editor_args("code --wait", true),
+            vec!["--wait", "--reuse-window", "/tmp/project"]
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_open_project_path_binds_placeholders() {
+        let temp_dir = TempDir::new().unwrap();
+        let project = temp_dir.path().join("my project");
+        std::fs::create_dir(&project).unwrap();
+        let marker = temp_dir.path().join("opened");
+
+        let config = Config {
+            open_command: Some(format!(
+                "printf '%s|%s' {{path}} {{name}} > {}",
+                marker.display()
+            )),
+            ..Config::default()
+        };
+        ProjectOpener::new()
+            .open_project_path(&project, &config)
+            .unwrap();
+
+        assert_eq!(
+            std::fs::read_to_string(&marker).unwrap(),
+            format!("{}|my project", project.display())
+        );
+    }
+
+    #[test]
+    fn test_open_file_project() {
+        let temp_dir = TempDir::new().unwrap();
+        let notes = temp_dir.path().join("notes.md");
+        std::fs::write(&notes, "# scratch").unwrap();
+
+        let opener = ProjectOpener::new();
+        let config = Config {
+            editor_command: "echo".to_string(),
+            open_in_current_window: true,
+            ..Config::default()
+        };
+
+        assert!(opener.open_project_path(&notes, &config).is_ok());
+        assert_eq!(working_dir(&notes), temp_dir.path());
+
+        let missing = temp_dir.path().join("missing.md");
+        let result = opener.open_project_path(&missing, &config);
+        assert!(result.is_err());
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("Project path does not exist"));
+    }
+
+    #[test]
+    fn test_add_flag_skipped_for_file_projects() {
+        let temp_dir = TempDir::new().unwrap();
+        let notes = temp_dir.path().join("notes.md");
+        std::fs::write(&notes, "# scratch").unwrap();
+
+        let config = Config {
+            editor_command: "code".to_string(),
+            open_in_current_window: true,
+            ..Config::default()
+        };
+
+        let (_, cmd) = build_editor_command(&notes, &config.editor_command, &config).unwrap();
+        assert_eq!(cmd.get_args().count(), 1);
+
+        let (_, cmd) =
+            build_editor_command(temp_dir.path(), &config.editor_command, &config).unwrap();
+        assert_eq!(cmd.get_args().next().unwrap(), "--add");
+    }
+
+    #[test]
+    fn test_open_target_is_passed_to_editor() {
+        let temp_dir = TempDir::new().unwrap();
+        let workspace = temp_dir.path().join("app.code-workspace");
+        std::fs::write(&workspace, "{\"folders\": []}").unwrap();
+
+        let config = Config {
+            editor_command: "code".to_string(),
+            prefer_workspace_file: true,
+            ..Config::default()
+        };
+        let target = create_test_project(temp_dir.path()).open_target(&config);
+        let (_, cmd) =
+            build_editor_command(Path::new(&target), &config.editor_command, &config).unwrap();
+        let args: Vec<_> = cmd.get_args().collect();
+        assert_eq!(args, vec![workspace.as_os_str()]);
+    }
+
+    #[test]
+    fn test_clone_state() {
+        let temp_dir = TempDir::new().unwrap();
+
+        assert_eq!(
+            clone_state(&temp_dir.path().join("missing")),
+            CloneState::Missing
+        );
+
+        let empty = temp_dir.path().join("empty");
+        std::fs::create_dir_all(&empty).unwrap();
+        assert_eq!(clone_state(&empty), CloneState::Empty);
+
+        let partial = temp_dir.path().join("partial");
+        git2::Repository::init(&partial).unwrap();
+        assert_eq!(clone_state(&partial), CloneState::Ready);
+        std::fs::write(clone_marker(&partial), "").unwrap();
+        assert_eq!(clone_state(&partial), CloneState::Incomplete);
+
+        let valid = temp_dir.path().join("valid");
+        let repo = git2::Repository::init(&valid).unwrap();
+        std::fs::write(valid.join("README.md"), "# valid").unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new("README.md")).unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let signature = git2::Signature::now("test", "test@example.com").unwrap();
+        repo.commit(Some("HEAD"), &signature, &signature, "init", &tree, &[])
+            .unwrap();
+        assert_eq!(clone_state(&valid), CloneState::Ready);
+
+        let unrelated = temp_dir.path().join("unrelated");
+        std::fs::create_dir_all(&unrelated).unwrap();
+        std::fs::write(unrelated.join("notes.txt"), "keep me").unwrap();
+        assert_eq!(clone_state(&unrelated), CloneState::Ready);
+    }
+
+    #[test]
+    fn test_empty_clone_dir_triggers_clone() {
+        let temp_dir = TempDir::new().unwrap();
+        let project = create_github_project("repo", temp_dir.path(), "");
+
+        let opener = ProjectOpener::new();
+        let config = Config {
+            editor_command: "echo".to_string(),
+            ..Config::default()
+        };
+
+        // An empty directory is not opened as-is: a clone is attempted and
+        // fails on the bogus URL instead
+        let result = opener.open_project(&project, &config);
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("Git clone failed"));
+    }
 }
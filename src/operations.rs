@@ -1,13 +1,18 @@
-use crate::config::Config;
-use crate::opener::ProjectOpener;
-use crate::project_manager;
+use crate::config::{Config, FzfFormat};
+use crate::models::{neighbor_index, Project, ProjectFilter, ProjectList, ProjectSource, Step};
+use crate::opener::{self, Opener, ProjectOpener};
+use crate::output::{say, trace};
+use crate::project_manager::{self, ScanOptions};
 use crate::scanner;
 use crate::tui::run_interactive_mode_with_receiver;
 use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
 use clap_complete::{generate, Shell};
 use dialoguer::{Confirm, Input};
+use std::fs::{self, File, TryLockError};
 use std::io;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
 
 /// Handle the setup wizard operation
 pub fn handle_setup_wizard(config: &Config, verbose: bool) -> Result<()> {
@@ -64,6 +69,12 @@ pub fn handle_setup_wizard(config: &Config, verbose: bool) -> Result<()> {
         println!("⚠️  GitHub CLI (gh) is not installed.");
         println!("To enable GitHub repository discovery, please install it with:");
         println!("  brew install gh");
+        if let Some(suggested) = scanner::github::suggest_username() {
+            println!(
+                "💡 Your git setup suggests the GitHub username '{}'; set github_username to use it",
+                suggested
+            );
+        }
 
         let skip_github = Confirm::new()
             .with_prompt("Continue without GitHub integration?")
@@ -81,7 +92,7 @@ pub fn handle_setup_wizard(config: &Config, verbose: bool) -> Result<()> {
             project_dirs,
             github_username: None,
             gitlab_username: None,
-            cache_ttl_seconds: config.cache_ttl_seconds,
+            ..config.clone()
         };
 
         new_config.save().context("Failed to save configuration")?;
@@ -94,8 +105,13 @@ pub fn handle_setup_wizard(config: &Config, verbose: bool) -> Result<()> {
     let new_config = if is_authenticated {
         println!("✅ GitHub CLI is authenticated");
 
-        let current_username = scanner::github::get_gh_username()
-            .unwrap_or_else(|_| config.github_username.as_deref().unwrap_or("").to_string());
+        let current_username = scanner::github::get_gh_username().unwrap_or_else(|_| {
+            config
+                .github_username
+                .clone()
+                .or_else(scanner::github::suggest_username)
+                .unwrap_or_default()
+        });
 
         let use_github = Confirm::new()
             .with_prompt(format!(
@@ -117,7 +133,7 @@ pub fn handle_setup_wizard(config: &Config, verbose: bool) -> Result<()> {
             project_dirs,
             github_username: github_username.clone(),
             gitlab_username: None,
-            cache_ttl_seconds: config.cache_ttl_seconds,
+            ..config.clone()
         };
 
         if use_github {
@@ -147,7 +163,12 @@ pub fn handle_setup_wizard(config: &Config, verbose: bool) -> Result<()> {
                     }
                     Err(e) => {
                         println!("⚠️  Could not determine GitHub username: {}", e);
-                        let manual_username: String = Input::new()
+                        let mut prompt = Input::new();
+                        // Offered as the default, never used without confirming
+                        if let Some(suggested) = scanner::github::suggest_username() {
+                            prompt = prompt.default(suggested);
+                        }
+                        let manual_username: String = prompt
                             .with_prompt("Please enter your GitHub username")
                             .allow_empty(true)
                             .interact()
@@ -173,7 +194,7 @@ pub fn handle_setup_wizard(config: &Config, verbose: bool) -> Result<()> {
             project_dirs,
             github_username: github_username.clone(),
             gitlab_username: None,
-            cache_ttl_seconds: config.cache_ttl_seconds,
+            ..config.clone()
         };
 
         if github_username.is_some() {
@@ -222,11 +243,8 @@ pub fn handle_setup_wizard(config: &Config, verbose: bool) -> Result<()> {
                 Some(gitlab_username_input.trim().to_string())
             };
 
-            if username.is_some() {
-                println!(
-                    "🦊 GitLab integration enabled for user '{}'",
-                    username.as_ref().unwrap()
-                );
+            if let Some(ref name) = username {
+                println!("🦊 GitLab integration enabled for user '{}'", name);
             }
 
             username
@@ -236,11 +254,8 @@ pub fn handle_setup_wizard(config: &Config, verbose: bool) -> Result<()> {
     };
 
     let final_config = Config {
-        editor_command: new_config.editor_command,
-        project_dirs: new_config.project_dirs,
-        github_username: new_config.github_username,
         gitlab_username,
-        cache_ttl_seconds: new_config.cache_ttl_seconds,
+        ..new_config
     };
 
     final_config
@@ -282,14 +297,58 @@ pub fn handle_setup_wizard(config: &Config, verbose: bool) -> Result<()> {
     Ok(())
 }
 
+/// Print the configuration file's settings as a portable bundle
+pub fn handle_export_config() -> Result<()> {
+    // Read the file again: `config` may carry --open-with and SW_EXTRA_DIRS
+    let config = Config::load_from_path(Config::config_file_path()?)?;
+    println!("{}", config.to_bundle()?);
+    Ok(())
+}
+
+/// Replace the configuration file with the bundle at `path`, keeping the
+/// previous file as `<name>.bak`
+pub fn handle_import_config(path: &Path) -> Result<()> {
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read config bundle: {}", path.display()))?;
+    let config = Config::from_bundle(&content)?;
+
+    let config_path = Config::config_file_path()?;
+    if config_path.exists() {
+        let mut backup = config_path.as_os_str().to_owned();
+        backup.push(".bak");
+        let backup = PathBuf::from(backup);
+        fs::copy(&config_path, &backup)
+            .with_context(|| format!("Failed to back up config file to {}", backup.display()))?;
+        say!("Saved the previous configuration to {}", backup.display());
+    }
+
+    config.save_to_path(&config_path)?;
+    say!("Imported configuration into {}", config_path.display());
+    Ok(())
+}
+
 /// Handle showing the current configuration
 pub fn handle_show_config(config: &Config, _verbose: bool) -> Result<()> {
     println!("Configuration:");
-    println!("  Editor: {}", config.editor_command);
+    if config.editor_command.trim() == crate::config::ENV_EDITOR_SENTINEL {
+        println!(
+            "  Editor: {} (currently {})",
+            config.editor_command,
+            config.resolved_editor_command()
+        );
+    } else {
+        println!("  Editor: {}", config.editor_command);
+    }
     println!("  Project directories:");
     for dir in &config.project_dirs {
         println!("    {}", dir.display());
     }
+    if !config.extra_scan_dirs.is_empty() {
+        println!("  Extra scan directories:");
+        for dir in &config.extra_scan_dirs {
+            println!("    {}", dir.display());
+        }
+    }
     println!("  Cache TTL: {} seconds", config.cache_ttl_seconds);
 
     if let Some(ref username) = config.github_username {
@@ -345,9 +404,68 @@ pub fn handle_show_config(config: &Config, _verbose: bool) -> Result<()> {
     Ok(())
 }
 
+/// Output format for `sw list`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ListFormat {
+    Human,
+    Json,
+    Porcelain,
+}
+
+/// Order for `sw list`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum ListSort {
+    /// Most recently modified first
+    #[default]
+    Recent,
+    Name,
+    /// Largest on disk first
+    Size,
+    /// Repos with uncommitted changes first (needs `show_git_status`),
+    /// then most recent
+    Activity,
+}
+
 /// Handle listing projects
-pub fn handle_list_projects(config: &Config, verbose: bool) -> Result<()> {
-    let project_list = project_manager::get_projects_with_cache(config, verbose)?;
+pub fn handle_list_projects(
+    config: &Config,
+    options: &ScanOptions,
+    verbose: bool,
+    format: ListFormat,
+    show_size: bool,
+    sort: ListSort,
+    modified_since: Option<DateTime<Utc>>,
+) -> Result<()> {
+    let mut project_list = project_manager::get_projects_with_cache(config, options, verbose)?;
+    if let Some(cutoff) = modified_since {
+        project_list.retain_modified_since(cutoff);
+    }
+
+    if show_size || sort == ListSort::Size {
+        crate::disk_usage::populate_sizes(&mut project_list, config)?;
+    }
+    match sort {
+        ListSort::Recent => {}
+        ListSort::Name => project_list.sort_by_name(),
+        ListSort::Size => project_list.sort_by_size(),
+        ListSort::Activity => project_list.sort_by_activity(),
+    }
+
+    match format {
+        ListFormat::Json => {
+            let json = serde_json::to_string_pretty(project_list.projects())
+                .context("Failed to serialize projects")?;
+            println!("{}", json);
+            return Ok(());
+        }
+        ListFormat::Porcelain => {
+            for project in project_list.projects() {
+                println!("{}", project.porcelain_line());
+            }
+            return Ok(());
+        }
+        ListFormat::Human => {}
+    }
 
     if project_list.is_empty() {
         println!("No projects found in configured directories:");
@@ -359,41 +477,490 @@ pub fn handle_list_projects(config: &Config, verbose: bool) -> Result<()> {
 
     println!("Found {} project(s):", project_list.len());
     for project in project_list.projects() {
-        println!("  {}", project.display_string());
+        let size = match project.size_bytes {
+            Some(bytes) if show_size => format!(" [{}]", crate::disk_usage::format_size(bytes)),
+            _ => String::new(),
+        };
+        println!(
+            "  {}{}",
+            project.display_string_with_label(
+                config.display_name_for(&project.path),
+                config.relative_times,
+                &config.dirty_marker,
+            ),
+            size
+        );
+    }
+
+    Ok(())
+}
+
+/// Report project names that resolve to more than one path
+pub fn handle_show_duplicates(config: &Config, options: &ScanOptions, verbose: bool) -> Result<()> {
+    let project_list = project_manager::get_projects_with_cache(config, options, verbose)?;
+    let duplicates = project_list.duplicate_names();
+
+    if duplicates.is_empty() {
+        println!("No duplicate project names found");
+    } else {
+        println!("Found {} ambiguous project name(s):", duplicates.len());
+        for (name, paths) in duplicates {
+            println!("  {}", name);
+            for path in paths {
+                println!("    {}", path.display());
+            }
+        }
+    }
+
+    let clones = project_list.multi_clones();
+    if !clones.is_empty() {
+        println!(
+            "Found {} repository(ies) cloned more than once:",
+            clones.len()
+        );
+        for (repo, paths) in clones {
+            println!("  {}", repo);
+            for path in paths {
+                println!("    {}", path.display());
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Projects that need the user's attention, by reason
+#[derive(Debug, Default)]
+struct AttentionReport<'a> {
+    /// Remote projects with a quick code that aren't checked out yet
+    uncloned: Vec<(char, &'a Project)>,
+    /// Local working trees with uncommitted changes
+    dirty: Vec<&'a Project>,
+    /// Local projects whose path no longer exists
+    missing: Vec<&'a Project>,
+}
+
+impl AttentionReport<'_> {
+    fn is_empty(&self) -> bool {
+        self.uncloned.is_empty() && self.dirty.is_empty() && self.missing.is_empty()
+    }
+}
+
+/// Sort `projects` into an [`AttentionReport`]. Quick codes stand in for
+/// favorites; `dirty` must already be filled in.
+fn attention_report<'a>(
+    projects: &'a ProjectList,
+    config: &Config,
+    exists: impl Fn(&std::path::Path) -> bool,
+) -> AttentionReport<'a> {
+    let mut report = AttentionReport::default();
+
+    let mut codes: Vec<(&char, &String)> = config.quick_codes.iter().collect();
+    codes.sort();
+    for (code, name) in codes {
+        if let Some(project) = crate::models::find_project_by_name(projects.projects(), name, true)
+        {
+            if crate::opener::needs_clone(project) {
+                report.uncloned.push((*code, project));
+            }
+        }
+    }
+
+    for project in projects.projects() {
+        if project.dirty == Some(true) {
+            report.dirty.push(project);
+        }
+        let is_local = matches!(project.source, ProjectSource::Local | ProjectSource::Cursor);
+        if is_local && !exists(&project.path) {
+            report.missing.push(project);
+        }
+    }
+
+    report
+}
+
+/// Summarize projects that need attention: uncloned quick-code projects,
+/// repos with uncommitted changes and cached paths that have vanished
+pub fn handle_status(config: &Config, options: &ScanOptions, verbose: bool) -> Result<()> {
+    let mut project_list = project_manager::get_projects_with_cache(config, options, verbose)?;
+    if !config.show_git_status {
+        scanner::local::populate_git_status(&mut project_list);
+    }
+    let report = attention_report(&project_list, config, |path| path.exists());
+
+    if report.is_empty() {
+        println!("Nothing needs attention");
+        return Ok(());
+    }
+
+    if !report.uncloned.is_empty() {
+        println!("Not cloned yet ({}):", report.uncloned.len());
+        for (code, project) in &report.uncloned {
+            println!("  @{} {}", code, project.name);
+        }
+    }
+    if !report.dirty.is_empty() {
+        println!("Uncommitted changes ({}):", report.dirty.len());
+        for project in &report.dirty {
+            println!("  {} - {}", project.shown_name(), project.path.display());
+        }
+    }
+    if !report.missing.is_empty() {
+        println!("Missing paths ({}):", report.missing.len());
+        for project in &report.missing {
+            println!("  {} - {}", project.shown_name(), project.path.display());
+        }
+        say!("💡 Run 'sw refresh' to drop them from the cache");
     }
 
     Ok(())
 }
 
 /// Handle refreshing the cache
-pub fn handle_refresh_cache(config: &Config, verbose: bool) -> Result<()> {
+/// With `profile_json`, timings are printed to stderr as a JSON object.
+pub fn handle_refresh_cache(
+    config: &Config,
+    options: &ScanOptions,
+    verbose: bool,
+    profile_json: bool,
+) -> Result<()> {
     if verbose {
         println!("Refreshing project cache...");
     }
 
-    let project_list = project_manager::get_projects_fresh(config, verbose)?;
+    // One line per source as it lands, so a slow scanner doesn't look hung
+    let (project_list, profile) =
+        project_manager::get_projects_fresh_profiled(config, options, verbose, |event| {
+            say!("  {}", event);
+            let _ = io::Write::flush(&mut io::stdout());
+        })?;
+
+    say!("Cache refreshed! Found {} projects.", project_list.len());
+    if profile_json {
+        let json = serde_json::to_string_pretty(&profile).context("Failed to serialize profile")?;
+        eprintln!("{}", json);
+    }
+    Ok(())
+}
+
+/// Start `sw prewarm --foreground` detached from the terminal and return
+/// without waiting, so it can run from a shell rc file
+pub fn handle_prewarm(config_path: Option<&Path>, verbose: bool) -> Result<()> {
+    let exe = std::env::current_exe().context("Failed to locate the sw executable")?;
+    let mut cmd = Command::new(exe);
+    cmd.args(["prewarm", "--foreground"]);
+    if let Some(path) = config_path {
+        cmd.arg("--config").arg(path);
+    }
+    cmd.stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null());
+
+    // Own process group, so the shell's job control and Ctrl+C leave it alone
+    #[cfg(unix)]
+    std::os::unix::process::CommandExt::process_group(&mut cmd, 0);
+
+    let child = cmd
+        .spawn()
+        .context("Failed to start background cache refresh")?;
+    if verbose {
+        println!("Prewarming cache in the background (pid {})", child.id());
+    }
+    Ok(())
+}
+
+/// Refresh the cache unless another prewarm is already doing so
+pub fn handle_prewarm_foreground(config: &Config, verbose: bool) -> Result<()> {
+    let lock_path = Config::cache_dir_path()?.join("prewarm.lock");
+    let ran = run_exclusive(&lock_path, || {
+        project_manager::get_projects_fresh(config, &ScanOptions::default(), verbose).map(|_| ())
+    })?;
 
-    println!("Cache refreshed! Found {} projects.", project_list.len());
+    if !ran && verbose {
+        println!("Another prewarm is already running");
+    }
     Ok(())
 }
 
+/// Run `task` while holding an exclusive lock on `lock_path`. Returns
+/// `Ok(false)` without running it if another process holds the lock.
+fn run_exclusive(lock_path: &Path, task: impl FnOnce() -> Result<()>) -> Result<bool> {
+    if let Some(parent) = lock_path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+    }
+
+    let lock_file = File::create(lock_path)
+        .with_context(|| format!("Failed to open lock file: {}", lock_path.display()))?;
+    match lock_file.try_lock() {
+        Ok(()) => {}
+        Err(TryLockError::WouldBlock) => return Ok(false),
+        Err(TryLockError::Error(e)) => {
+            return Err(e).with_context(|| format!("Failed to lock: {}", lock_path.display()));
+        }
+    }
+
+    task()?;
+    Ok(true)
+}
+
 /// Handle opening a project by name
 pub fn handle_open_project_by_name(
     project_name: &str,
+    exact: bool,
+    first: bool,
     config: &Config,
+    options: &ScanOptions,
     verbose: bool,
 ) -> Result<()> {
-    let opener = ProjectOpener::new();
+    let projects = project_manager::get_projects_with_cache(config, options, verbose)?;
+    let rescan = || project_manager::get_projects_fresh(config, options, verbose);
+    let project = if first {
+        open_first_match(
+            &ProjectOpener::new(),
+            project_name,
+            &projects,
+            config,
+            verbose,
+            rescan,
+        )?
+    } else {
+        open_project_by_name(
+            &ProjectOpener::new(),
+            project_name,
+            exact,
+            &projects,
+            config,
+            verbose,
+            rescan,
+        )?
+    };
+    record_usage(&project);
+
+    Ok(())
+}
 
-    let projects = project_manager::get_projects_with_cache(config, verbose)?;
+/// Resolve `project_name` and open it with `opener`, returning the project
+fn open_project_by_name(
+    opener: &dyn Opener,
+    project_name: &str,
+    exact: bool,
+    cached: &ProjectList,
+    config: &Config,
+    verbose: bool,
+    rescan: impl FnOnce() -> Result<ProjectList>,
+) -> Result<Project> {
+    let (project_name, exact) = expand_quick_code(project_name, exact, config)?;
+    let project = resolve_project_by_name(project_name, exact, cached, verbose, rescan)?;
+    open_resolved(opener, project, config)
+}
 
-    let matching_project = projects
-        .projects()
+/// Open the project a name resolved to and confirm it, handing it back
+fn open_resolved(opener: &dyn Opener, project: Project, config: &Config) -> Result<Project> {
+    opener.open_project(&project, config)?;
+    say!("Opened project: {}", project.name);
+
+    Ok(project)
+}
+
+/// Open every project whose name contains `query` (`sw <query> --all`),
+/// cloning missing remotes `clone_concurrency` at a time
+pub fn handle_open_all_matching(
+    query: &str,
+    config: &Config,
+    options: &ScanOptions,
+    verbose: bool,
+) -> Result<()> {
+    let mut projects = project_manager::get_projects_with_cache(config, options, verbose)?;
+    if crate::models::projects_matching_name(projects.projects(), query).is_empty() {
+        projects = project_manager::get_projects_fresh(config, options, verbose)?;
+    }
+    let matches: Vec<Project> = crate::models::projects_matching_name(projects.projects(), query)
+        .into_iter()
+        .cloned()
+        .collect();
+    if matches.is_empty() {
+        return Err(ProjectNotFound::NoMatch(query.to_string()).into());
+    }
+
+    let result = ProjectOpener::new().open_projects(&matches, config);
+    for project in &matches {
+        record_usage(project);
+    }
+    result?;
+    say!("Opened {} projects matching '{}'", matches.len(), query);
+    Ok(())
+}
+
+/// Open the project the fuzzy ranker scores highest for `query`, without
+/// asking between close matches; rescans once when nothing cached matches.
+/// A quick code still opens exactly the project it names.
+fn open_first_match(
+    opener: &dyn Opener,
+    query: &str,
+    cached: &ProjectList,
+    config: &Config,
+    verbose: bool,
+    rescan: impl FnOnce() -> Result<ProjectList>,
+) -> Result<Project> {
+    let (query, exact) = expand_quick_code(query, false, config)?;
+    if exact {
+        let project = resolve_project_by_name(query, true, cached, verbose, rescan)?;
+        return open_resolved(opener, project, config);
+    }
+
+    let project = match crate::tui::best_match(cached.projects(), query, config) {
+        Some(project) => project.clone(),
+        None => {
+            let fresh_projects = rescan()?;
+            crate::tui::best_match(fresh_projects.projects(), query, config)
+                .cloned()
+                .ok_or_else(|| ProjectNotFound::NoMatch(query.to_string()))?
+        }
+    };
+    trace!("Best match for '{}': {}", query, project.path.display());
+    open_resolved(opener, project, config)
+}
+
+/// No project matched a name given on the command line
+#[derive(Debug, thiserror::Error)]
+pub enum ProjectNotFound {
+    #[error("No exact match for project '{0}'")]
+    NoExactMatch(String),
+    #[error("No project found matching '{0}'")]
+    NoMatch(String),
+    #[error("No quick code '@{0}' (add one with: sw code set {0} <project>)")]
+    UnknownQuickCode(char),
+    #[error("'{query}' matches several projects: {}", candidates.join(", "))]
+    Ambiguous {
+        query: String,
+        candidates: Vec<String>,
+    },
+}
+
+/// Print the path of the project `project_name` resolves to, as direct open
+/// would pick it, but refuse to guess between several candidates
+pub fn handle_which(
+    project_name: &str,
+    exact: bool,
+    config: &Config,
+    options: &ScanOptions,
+    verbose: bool,
+) -> Result<()> {
+    let projects = project_manager::get_projects_with_cache(config, options, verbose)?;
+    let project = which_project(project_name, exact, &projects, config, || {
+        project_manager::get_projects_fresh(config, options, verbose)
+    })?;
+    println!("{}", project.path.display());
+    Ok(())
+}
+
+/// Resolve `project_name` like [`open_project_by_name`] without opening it,
+/// falling back to a fresh scan when the cache has no match
+fn which_project(
+    project_name: &str,
+    exact: bool,
+    cached: &ProjectList,
+    config: &Config,
+    rescan: impl FnOnce() -> Result<ProjectList>,
+) -> Result<Project> {
+    let (project_name, exact) = expand_quick_code(project_name, exact, config)?;
+    if let Some(project) = unique_project_by_name(cached.projects(), project_name, exact)? {
+        return Ok(project.clone());
+    }
+
+    let fresh_projects = rescan()?;
+    match unique_project_by_name(fresh_projects.projects(), project_name, exact)? {
+        Some(project) => Ok(project.clone()),
+        None if exact => Err(ProjectNotFound::NoExactMatch(project_name.to_string()).into()),
+        None => Err(ProjectNotFound::NoMatch(project_name.to_string()).into()),
+    }
+}
+
+/// [`crate::models::find_project_by_name`], except that the match must
+/// single out one project instead of taking the first: several projects
+/// with the exact name, or with no exact match several containing it, are
+/// ambiguous
+fn unique_project_by_name<'a>(
+    projects: &'a [Project],
+    query: &str,
+    exact: bool,
+) -> Result<Option<&'a Project>, ProjectNotFound> {
+    let lowered = query.to_lowercase();
+    let mut matches: Vec<&Project> = projects
         .iter()
-        .find(|p| p.name.to_lowercase().contains(&project_name.to_lowercase()))
-        .cloned();
+        .filter(|candidate| candidate.name.to_lowercase() == lowered)
+        .collect();
+    if matches.is_empty() && !exact {
+        matches = projects
+            .iter()
+            .filter(|candidate| candidate.name.to_lowercase().contains(&lowered))
+            .collect();
+    }
+
+    match matches.as_slice() {
+        [] => Ok(None),
+        [project] => Ok(Some(project)),
+        _ => Err(ProjectNotFound::Ambiguous {
+            query: query.to_string(),
+            candidates: matches
+                .iter()
+                .map(|candidate| candidate.shown_name().to_string())
+                .collect(),
+        }),
+    }
+}
+
+/// Resolve an `@<code>` argument through `quick_codes` to the exact project
+/// name it stands for. Anything else is passed through unchanged.
+fn expand_quick_code<'a>(
+    project_name: &'a str,
+    exact: bool,
+    config: &'a Config,
+) -> Result<(&'a str, bool), ProjectNotFound> {
+    let mut chars = project_name.chars();
+    let (Some('@'), Some(code), None) = (chars.next(), chars.next(), chars.next()) else {
+        return Ok((project_name, exact));
+    };
 
-    if let Some(project) = matching_project {
+    config
+        .quick_codes
+        .get(&code)
+        .map(|name| (name.as_str(), true))
+        .ok_or(ProjectNotFound::UnknownQuickCode(code))
+}
+
+/// Assign (or with `None`, remove) the quick code `code` in the config file
+pub fn handle_set_quick_code(code: char, project_name: Option<&str>) -> Result<()> {
+    let mut config = Config::load()?;
+    match project_name {
+        Some(name) => {
+            config.quick_codes.insert(code, name.to_string());
+            config.save()?;
+            say!("sw @{} now opens {}", code, name);
+        }
+        None => {
+            if config.quick_codes.remove(&code).is_none() {
+                anyhow::bail!(ProjectNotFound::UnknownQuickCode(code));
+            }
+            config.save()?;
+            say!("Removed quick code @{}", code);
+        }
+    }
+    Ok(())
+}
+
+/// Look `project_name` up in the cached projects, falling back to a fresh scan
+fn resolve_project_by_name(
+    project_name: &str,
+    exact: bool,
+    cached: &ProjectList,
+    verbose: bool,
+    rescan: impl FnOnce() -> Result<ProjectList>,
+) -> Result<Project> {
+    if let Some(project) =
+        crate::models::find_project_by_name(cached.projects(), project_name, exact)
+    {
         if verbose {
             println!(
                 "Found project: {} at {}",
@@ -401,23 +968,16 @@ pub fn handle_open_project_by_name(
                 project.path.display()
             );
         }
+        return Ok(project.clone());
+    }
 
-        opener.open_project(&project, config)?;
-        println!("Opened project: {}", project.name);
-    } else {
-        // Try fresh scan if not found in cache
-        if verbose {
-            println!("Project not found in cache, trying fresh scan...");
-        }
-        let fresh_projects = project_manager::get_projects_fresh(config, verbose)?;
-
-        let fresh_matching = fresh_projects
-            .projects()
-            .iter()
-            .find(|p| p.name.to_lowercase().contains(&project_name.to_lowercase()))
-            .cloned();
+    if verbose {
+        println!("Project not found in cache, trying fresh scan...");
+    }
+    let fresh_projects = rescan()?;
 
-        if let Some(project) = fresh_matching {
+    match crate::models::find_project_by_name(fresh_projects.projects(), project_name, exact) {
+        Some(project) => {
             if verbose {
                 println!(
                     "Found project in fresh scan: {} at {}",
@@ -425,26 +985,26 @@ pub fn handle_open_project_by_name(
                     project.path.display()
                 );
             }
-            opener.open_project(&project, config)?;
-            println!("Opened project: {}", project.name);
-        } else {
-            println!("No project found matching '{}'", project_name);
-            std::process::exit(1);
+            Ok(project.clone())
         }
+        None if exact => Err(ProjectNotFound::NoExactMatch(project_name.to_string()).into()),
+        None => Err(ProjectNotFound::NoMatch(project_name.to_string()).into()),
     }
-
-    Ok(())
 }
 
 /// Handle interactive mode
-pub fn handle_interactive_mode(config: &Config, verbose: bool) -> Result<()> {
+pub fn handle_interactive_mode(
+    config: &Config,
+    options: &ScanOptions,
+    verbose: bool,
+) -> Result<()> {
     let opener = ProjectOpener::new();
 
     let (projects, update_receiver) =
-        project_manager::get_projects_with_background_refresh(config, verbose)?;
+        project_manager::get_projects_with_background_refresh(config, options, verbose)?;
 
     if projects.is_empty() && update_receiver.is_none() {
-        println!(
+        say!(
             "No projects found. Try running with --refresh to rescan or check your configuration."
         );
         return Ok(());
@@ -454,9 +1014,10 @@ pub fn handle_interactive_mode(config: &Config, verbose: bool) -> Result<()> {
         println!("Starting interactive mode with {} projects", projects.len());
     }
 
-    if let Some(selected_project) =
-        run_interactive_mode_with_receiver(projects.projects().to_vec(), update_receiver)?
+    if let Some(selection) =
+        run_interactive_mode_with_receiver(projects.projects().to_vec(), update_receiver, config)?
     {
+        let selected_project = selection.project;
         if verbose {
             println!(
                 "Selected project: {} at {}",
@@ -465,8 +1026,13 @@ pub fn handle_interactive_mode(config: &Config, verbose: bool) -> Result<()> {
             );
         }
 
-        opener.open_project(&selected_project, config)?;
-        println!("Opened project: {}", selected_project.name);
+        let open_config = Config {
+            open_in_current_window: selection.open_in_current_window,
+            ..config.for_switching()
+        };
+        opener.open_project(&selected_project, &open_config)?;
+        say!("Opened project: {}", selected_project.name);
+        record_usage(&selected_project);
     } else if verbose {
         println!("No project selected");
     }
@@ -474,11 +1040,147 @@ pub fn handle_interactive_mode(config: &Config, verbose: bool) -> Result<()> {
     Ok(())
 }
 
+/// Open the project next to (or before) the last-opened one in the project
+/// list's order, skipping projects that are gone from disk
+pub fn handle_cycle(
+    config: &Config,
+    options: &ScanOptions,
+    verbose: bool,
+    step: Step,
+) -> Result<()> {
+    let projects = project_manager::get_projects_with_cache(config, options, verbose)?;
+    let usage = crate::usage::UsageLog::load_from_path(&Config::usage_log_path()?)?;
+
+    let current = usage.last_opened().and_then(|last| {
+        projects
+            .projects()
+            .iter()
+            .position(|project| project.path == last)
+    });
+    if verbose {
+        match current {
+            Some(index) => println!("Last opened: {}", projects.projects()[index].name),
+            None => println!("No last-opened project in the list, starting at the end"),
+        }
+    }
+
+    let index = neighbor_index(projects.projects(), current, step, Project::exists_locally)
+        .context("No projects found")?;
+    let project = &projects.projects()[index];
+
+    ProjectOpener::new().open_project(project, config)?;
+    say!("Opened project: {}", project.name);
+    record_usage(project);
+    Ok(())
+}
+
+/// How many projects the numbered prompt offers
+const SIMPLE_MODE_LIMIT: usize = 20;
+
+/// Handle the numbered-list prompt, for terminals where the TUI misbehaves
+pub fn handle_simple_mode(config: &Config, options: &ScanOptions, verbose: bool) -> Result<()> {
+    let projects = project_manager::get_projects_with_cache(config, options, verbose)?;
+
+    if projects.is_empty() {
+        println!("No projects found");
+        return Ok(());
+    }
+
+    let shown: Vec<_> = projects.projects().iter().take(SIMPLE_MODE_LIMIT).collect();
+    for (number, project) in shown.iter().enumerate() {
+        println!(
+            "{:>3}) {}",
+            number + 1,
+            project.display_string_with_label(
+                config.display_name_for(&project.path),
+                config.relative_times,
+                &config.dirty_marker,
+            )
+        );
+    }
+
+    print!("Select project [1-{}]: ", shown.len());
+    io::Write::flush(&mut io::stdout()).context("Failed to flush stdout")?;
+
+    let mut answer = String::new();
+    io::stdin()
+        .read_line(&mut answer)
+        .context("Failed to read selection")?;
+    let answer = answer.trim();
+
+    if answer.is_empty() {
+        if verbose {
+            println!("No project selected");
+        }
+        return Ok(());
+    }
+
+    let project = answer
+        .parse::<usize>()
+        .ok()
+        .and_then(|number| number.checked_sub(1))
+        .and_then(|index| shown.get(index))
+        .with_context(|| format!("Invalid selection: {}", answer))?;
+
+    ProjectOpener::new().open_project(project, &config.for_switching())?;
+    say!("Opened project: {}", project.name);
+    record_usage(project);
+    Ok(())
+}
+
+/// What fzf mode does with the selected project
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SelectionAction {
+    #[default]
+    Open,
+    /// Print the path to stdout, for scripts
+    Print,
+    /// Copy the path to the clipboard
+    Copy,
+}
+
+/// Print the project's path on its own line, so it can be captured with `$(...)`
+fn print_project_path(project: &Project, out: &mut dyn io::Write) -> Result<()> {
+    writeln!(out, "{}", project.path.display()).context("Failed to write project path")
+}
+
+/// Carry out `action` on the `project` picked interactively. Opening goes
+/// through `editor_switch_command` when one is set.
+fn apply_selection(
+    action: SelectionAction,
+    project: &Project,
+    config: &Config,
+    opener: &dyn Opener,
+    out: &mut dyn io::Write,
+    copy: &dyn Fn(&str) -> Result<()>,
+) -> Result<()> {
+    match action {
+        SelectionAction::Open => {
+            opener.open_project(project, &config.for_switching())?;
+            if !crate::output::is_quiet() {
+                writeln!(out, "Opened project: {}", project.name)?;
+            }
+        }
+        SelectionAction::Print => print_project_path(project, out)?,
+        SelectionAction::Copy => {
+            copy(&project.path.to_string_lossy())?;
+            if !crate::output::is_quiet() {
+                writeln!(out, "Copied path: {}", project.path.display())?;
+            }
+        }
+    }
+    Ok(())
+}
+
 /// Handle fzf mode
-pub fn handle_fzf_mode(config: &Config, verbose: bool) -> Result<()> {
-    use crate::models;
+pub fn handle_fzf_mode(
+    config: &Config,
+    options: &ScanOptions,
+    verbose: bool,
+    filter: &ProjectFilter,
+    action: SelectionAction,
+) -> Result<()> {
     use std::io::Write;
-    use std::process::{Command, Stdio};
 
     if which::which("fzf").is_err() {
         anyhow::bail!("fzf binary not found. Please install fzf to use this mode.");
@@ -487,10 +1189,10 @@ pub fn handle_fzf_mode(config: &Config, verbose: bool) -> Result<()> {
     let opener = ProjectOpener::new();
 
     // For fzf mode, we use the regular cache function since fzf doesn't support dynamic updates
-    let projects = project_manager::get_projects_with_cache(config, verbose)?;
+    let projects = project_manager::get_projects_with_cache(config, options, verbose)?;
 
     if projects.is_empty() {
-        println!(
+        say!(
             "No projects found. Try running with --refresh to rescan or check your configuration."
         );
         return Ok(());
@@ -500,32 +1202,17 @@ pub fn handle_fzf_mode(config: &Config, verbose: bool) -> Result<()> {
         println!("Piping {} projects to fzf", projects.len());
     }
 
-    let project_lines: Vec<String> = projects
-        .projects()
-        .iter()
-        .map(|project| {
-            let source_indicator = match project.source {
-                models::ProjectSource::Local => "📁",
-                models::ProjectSource::Cursor => "🎯",
-                models::ProjectSource::GitHub => "🐙",
-                models::ProjectSource::GitLab => "🦊",
-            };
-
-            let time_str = if let Some(timestamp) = project.last_modified {
-                format!(" ({})", timestamp.format("%Y-%m-%d %H:%M"))
-            } else {
-                String::new()
-            };
-
-            format!("{} {}{}", source_indicator, project.name, time_str)
-        })
-        .collect();
+    let project_lines = fzf_project_lines(&projects, filter, config);
 
     let mut fzf_process = Command::new("fzf")
         .arg("--prompt=Select project: ")
         .arg("--height=40%")
         .arg("--reverse")
         .arg("--border")
+        // Lines carry a hidden index field so the selection maps back
+        // to its project whatever the visible layout
+        .arg("--delimiter=\t")
+        .arg("--with-nth=2..")
         .stdin(Stdio::piped())
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
@@ -533,8 +1220,8 @@ pub fn handle_fzf_mode(config: &Config, verbose: bool) -> Result<()> {
         .context("Failed to spawn fzf process")?;
 
     if let Some(stdin) = fzf_process.stdin.as_mut() {
-        for line in &project_lines {
-            writeln!(stdin, "{}", line).context("Failed to write to fzf stdin")?;
+        for (index, (_, line)) in project_lines.iter().enumerate() {
+            writeln!(stdin, "{}\t{}", index, line).context("Failed to write to fzf stdin")?;
         }
     }
 
@@ -542,16 +1229,29 @@ pub fn handle_fzf_mode(config: &Config, verbose: bool) -> Result<()> {
         .wait_with_output()
         .context("Failed to wait for fzf process")?;
 
-    if !output.status.success() {
-        if verbose {
-            println!("fzf cancelled or failed");
+    match fzf_outcome(output.status.code()) {
+        FzfOutcome::Selected => {}
+        FzfOutcome::NoMatch => {
+            if verbose {
+                println!("No project matched in fzf");
+            }
+            return Ok(());
+        }
+        FzfOutcome::Cancelled => {
+            if verbose {
+                println!("fzf cancelled");
+            }
+            return Ok(());
+        }
+        FzfOutcome::Failed => {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!("fzf failed ({}): {}", output.status, stderr.trim());
         }
-        return Ok(());
     }
 
     let selected_line = String::from_utf8(output.stdout)
         .context("Failed to parse fzf output")?
-        .trim()
+        .trim_end_matches(['\r', '\n'])
         .to_string();
 
     if selected_line.is_empty() {
@@ -561,13 +1261,7 @@ pub fn handle_fzf_mode(config: &Config, verbose: bool) -> Result<()> {
         return Ok(());
     }
 
-    let selected_project = projects
-        .projects()
-        .iter()
-        .zip(project_lines.iter())
-        .find(|(_, line)| **line == selected_line)
-        .map(|(project, _)| project)
-        .cloned();
+    let selected_project = selected_fzf_project(&project_lines, &selected_line).cloned();
 
     if let Some(project) = selected_project {
         if verbose {
@@ -578,8 +1272,17 @@ pub fn handle_fzf_mode(config: &Config, verbose: bool) -> Result<()> {
             );
         }
 
-        opener.open_project(&project, config)?;
-        println!("Opened project: {}", project.name);
+        apply_selection(
+            action,
+            &project,
+            config,
+            &opener,
+            &mut io::stdout(),
+            &crate::clipboard::copy_to_clipboard,
+        )?;
+        if action == SelectionAction::Open {
+            record_usage(&project);
+        }
     } else {
         anyhow::bail!("Failed to find selected project");
     }
@@ -587,8 +1290,892 @@ pub fn handle_fzf_mode(config: &Config, verbose: bool) -> Result<()> {
     Ok(())
 }
 
+/// Clone every remote project from `filter` that isn't checked out yet and
+/// whose name contains `pattern`, reporting each repository's outcome
+pub fn handle_clone_all(
+    config: &Config,
+    options: &ScanOptions,
+    verbose: bool,
+    filter: &ProjectFilter,
+    pattern: Option<&str>,
+) -> Result<()> {
+    let projects = project_manager::get_projects_with_cache(config, options, verbose)?;
+    let selected = uncloned_matching(projects.projects(), filter, pattern, opener::needs_clone);
+
+    if selected.is_empty() {
+        say!("No uncloned remote projects to clone");
+        return Ok(());
+    }
+    say!("Cloning {} projects", selected.len());
+
+    let results = ProjectOpener::new().clone_projects(&selected, config);
+    for line in clone_report(&results) {
+        println!("{}", line);
+    }
+
+    let failed = results.iter().filter(|(_, result)| result.is_err()).count();
+    if failed > 0 {
+        anyhow::bail!("Failed to clone {} of {} projects", failed, results.len());
+    }
+    Ok(())
+}
+
+/// Projects that `needs_clone`, limited to `filter`'s source and count and
+/// to names containing `pattern` (ignoring case)
+fn uncloned_matching<'a>(
+    projects: &'a [Project],
+    filter: &ProjectFilter,
+    pattern: Option<&str>,
+    needs_clone: impl Fn(&Project) -> bool,
+) -> Vec<&'a Project> {
+    let pattern = pattern.map(str::to_lowercase);
+    projects
+        .iter()
+        .filter(|project| filter.source.as_ref().is_none_or(|s| project.source == *s))
+        .filter(|project| {
+            pattern
+                .as_deref()
+                .is_none_or(|pattern| project.name.to_lowercase().contains(pattern))
+        })
+        .filter(|project| needs_clone(project))
+        .take(filter.limit.unwrap_or(usize::MAX))
+        .collect()
+}
+
+/// One line per cloned project, then a summary
+fn clone_report(results: &[(&Project, Result<()>)]) -> Vec<String> {
+    let mut lines: Vec<String> = results
+        .iter()
+        .map(|(project, result)| match result {
+            Ok(()) => format!("✓ {} → {}", project.name, project.path.display()),
+            Err(e) => format!("✗ {}: {}", project.name, e),
+        })
+        .collect();
+
+    let cloned = results.iter().filter(|(_, result)| result.is_ok()).count();
+    lines.push(format!("Cloned {} of {} projects", cloned, results.len()));
+    lines
+}
+
+/// Count an open in the usage log; failures only warn
+fn record_usage(project: &Project) {
+    if let Err(e) = crate::usage::UsageLog::record_open(&project.path) {
+        eprintln!("Warning: Failed to update usage log: {}", e);
+    }
+}
+
+/// How an fzf run ended, from its exit code
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FzfOutcome {
+    Selected,
+    /// Nothing matched the query
+    NoMatch,
+    /// Esc or Ctrl-C
+    Cancelled,
+    /// fzf itself failed, e.g. on an unknown option
+    Failed,
+}
+
+/// fzf exits 0 on a selection, 1 with no match, 130 when interrupted and 2
+/// on errors. A missing code means fzf was killed by a signal.
+fn fzf_outcome(code: Option<i32>) -> FzfOutcome {
+    match code {
+        Some(0) => FzfOutcome::Selected,
+        Some(1) => FzfOutcome::NoMatch,
+        Some(130) | None => FzfOutcome::Cancelled,
+        Some(_) => FzfOutcome::Failed,
+    }
+}
+
+/// Lines fed to fzf, paired with the project each one selects
+fn fzf_project_lines<'a>(
+    projects: &'a ProjectList,
+    filter: &ProjectFilter,
+    config: &Config,
+) -> Vec<(&'a Project, String)> {
+    let projects = filter.apply(projects.projects());
+    match config.fzf_format {
+        FzfFormat::Compact => compact_fzf_lines(projects, config),
+        FzfFormat::Columns => {
+            let home = dirs::home_dir();
+            let now = chrono::Utc::now();
+            let rows = projects
+                .iter()
+                .map(|project| {
+                    vec![
+                        project.labeled_name(config.display_name_for(&project.path)),
+                        project.source.as_str().to_string(),
+                        project.relative_age(now).unwrap_or_else(|| "-".to_string()),
+                        crate::models::abbreviate_home(&project.path, home.as_deref()),
+                    ]
+                })
+                .collect();
+            projects.into_iter().zip(align_columns(rows)).collect()
+        }
+    }
+}
+
+/// Pad every column but the last to its widest cell, two spaces apart
+fn align_columns(rows: Vec<Vec<String>>) -> Vec<String> {
+    let mut widths: Vec<usize> = Vec::new();
+    for row in &rows {
+        for (column, cell) in row.iter().enumerate() {
+            let width = cell.chars().count();
+            match widths.get_mut(column) {
+                Some(max) => *max = (*max).max(width),
+                None => widths.push(width),
+            }
+        }
+    }
+
+    rows.into_iter()
+        .map(|row| {
+            let last = row.len().saturating_sub(1);
+            row.into_iter()
+                .enumerate()
+                .map(|(column, cell)| {
+                    if column == last {
+                        cell
+                    } else {
+                        let padding = widths[column] - cell.chars().count();
+                        format!("{}{}", cell, " ".repeat(padding + 2))
+                    }
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// The project behind a line fzf printed back: its leading index field
+fn selected_fzf_project<'a>(
+    project_lines: &[(&'a Project, String)],
+    selected_line: &str,
+) -> Option<&'a Project> {
+    let (index, _) = selected_line.split_once('\t')?;
+    let index: usize = index.trim().parse().ok()?;
+    project_lines.get(index).map(|(project, _)| *project)
+}
+
+/// `{icon} {name} ({date})` lines
+fn compact_fzf_lines<'a>(
+    projects: Vec<&'a Project>,
+    config: &Config,
+) -> Vec<(&'a Project, String)> {
+    projects
+        .into_iter()
+        .map(|project| {
+            let source_indicator = match project.source {
+                ProjectSource::Local => "📁",
+                ProjectSource::Cursor => "🎯",
+                ProjectSource::GitHub => "🐙",
+                ProjectSource::GitLab => "🦊",
+                ProjectSource::Unknown(_) => "❔",
+            };
+
+            let time_str = project
+                .time_label(config.relative_times, "%Y-%m-%d %H:%M")
+                .map(|time| format!(" ({})", time))
+                .unwrap_or_default();
+
+            let type_icon = config
+                .type_icon(project)
+                .map(|icon| format!("{} ", icon))
+                .unwrap_or_default();
+
+            let name = project.labeled_name(config.display_name_for(&project.path));
+            (
+                project,
+                format!("{} {}{}{}", source_indicator, type_icon, name, time_str),
+            )
+        })
+        .collect()
+}
+
 /// Handle generating shell completions
 pub fn handle_generate_completions(shell: Shell, cli_command: &mut clap::Command) -> Result<()> {
     generate(shell, cli_command, "sw", &mut io::stdout());
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cached_projects() -> ProjectList {
+        ProjectList::from_projects(vec![Project::new_local(
+            "zapplication".to_string(),
+            "/path/to/zapplication",
+        )])
+    }
+
+    #[test]
+    fn test_resolve_project_returns_not_found_error() {
+        let err = resolve_project_by_name("missing", false, &cached_projects(), false, || {
+            Ok(ProjectList::new())
+        })
+        .unwrap_err();
+
+        assert!(matches!(
+            err.downcast_ref::<ProjectNotFound>(),
+            Some(ProjectNotFound::NoMatch(name)) if name == "missing"
+        ));
+        assert_eq!(err.to_string(), "No project found matching 'missing'");
+    }
+
+    #[test]
+    fn test_resolve_project_exact_reports_no_exact_match() {
+        let err = resolve_project_by_name("app", true, &cached_projects(), false, || {
+            Ok(cached_projects())
+        })
+        .unwrap_err();
+
+        assert_eq!(err.to_string(), "No exact match for project 'app'");
+    }
+
+    #[test]
+    fn test_resolve_project_falls_back_to_rescan() {
+        let fresh = ProjectList::from_projects(vec![Project::new_local(
+            "new-project".to_string(),
+            "/path/to/new-project",
+        )]);
+
+        let project =
+            resolve_project_by_name("new-project", false, &ProjectList::new(), false, || {
+                Ok(fresh)
+            })
+            .unwrap();
+
+        assert_eq!(project.name, "new-project");
+    }
+
+    fn which_projects() -> ProjectList {
+        ProjectList::from_projects(vec![
+            Project::new_local("api".to_string(), "/path/to/api"),
+            Project::new_local("api-gateway".to_string(), "/path/to/api-gateway"),
+            Project::new_local("api-docs".to_string(), "/path/to/api-docs"),
+            Project::new_local("frontend".to_string(), "/path/to/frontend"),
+        ])
+    }
+
+    #[test]
+    fn test_which_resolves_exact_and_unique_names() {
+        let config = Config::default();
+        let no_rescan = || -> Result<ProjectList> { panic!("cache hit must not rescan") };
+
+        // An exact name wins even though other names contain it
+        let project = which_project("API", false, &which_projects(), &config, no_rescan).unwrap();
+        assert_eq!(project.path, PathBuf::from("/path/to/api"));
+
+        let project = which_project("front", false, &which_projects(), &config, no_rescan).unwrap();
+        assert_eq!(project.path, PathBuf::from("/path/to/frontend"));
+    }
+
+    #[test]
+    fn test_which_rejects_ambiguous_names() {
+        let err = which_project("api-", false, &which_projects(), &Config::default(), || {
+            Ok(ProjectList::new())
+        })
+        .unwrap_err();
+
+        assert!(matches!(
+            err.downcast_ref::<ProjectNotFound>(),
+            Some(ProjectNotFound::Ambiguous { candidates, .. }) if candidates.len() == 2
+        ));
+        assert_eq!(
+            err.to_string(),
+            "'api-' matches several projects: api-gateway, api-docs"
+        );
+    }
+
+    #[test]
+    fn test_which_rejects_duplicate_exact_names() {
+        let mut projects = ProjectList::from_projects(vec![
+            Project::new_local("api".to_string(), "/work/api"),
+            Project::new_local("api".to_string(), "/personal/api"),
+            Project::new_local("api-docs".to_string(), "/work/api-docs"),
+        ]);
+        projects.qualify_colliding_names();
+
+        for exact in [false, true] {
+            let err = which_project("api", exact, &projects, &Config::default(), || {
+                Ok(ProjectList::new())
+            })
+            .unwrap_err();
+            match err.downcast_ref::<ProjectNotFound>() {
+                Some(ProjectNotFound::Ambiguous { candidates, .. }) => {
+                    assert_eq!(candidates.len(), 2);
+                    assert_ne!(candidates[0], candidates[1]);
+                }
+                other => panic!("expected an ambiguous match, got {:?}", other),
+            }
+        }
+    }
+
+    #[test]
+    fn test_which_reports_no_match_after_rescan() {
+        let err = which_project(
+            "missing",
+            false,
+            &which_projects(),
+            &Config::default(),
+            || Ok(which_projects()),
+        )
+        .unwrap_err();
+        assert_eq!(err.to_string(), "No project found matching 'missing'");
+
+        let err = which_project("front", true, &which_projects(), &Config::default(), || {
+            Ok(which_projects())
+        })
+        .unwrap_err();
+        assert_eq!(err.to_string(), "No exact match for project 'front'");
+    }
+
+    #[test]
+    fn test_attention_report_categories() {
+        let mut dirty = Project::new_local("dirty".to_string(), "/path/dirty");
+        dirty.dirty = Some(true);
+        let mut clean = Project::new_local("clean".to_string(), "/path/clean");
+        clean.dirty = Some(false);
+        let projects = ProjectList::from_projects(vec![
+            dirty,
+            clean,
+            Project::new_local("gone".to_string(), "/path/gone"),
+            Project::new_github(
+                "pinned".to_string(),
+                "/nonexistent/sw-test/pinned",
+                "https://github.com/user/pinned".to_string(),
+            ),
+            Project::new_github(
+                "unpinned".to_string(),
+                "/nonexistent/sw-test/unpinned",
+                "https://github.com/user/unpinned".to_string(),
+            ),
+        ]);
+        let config = Config {
+            quick_codes: std::collections::HashMap::from([
+                ('p', "pinned".to_string()),
+                ('d', "dirty".to_string()),
+            ]),
+            ..Config::default()
+        };
+
+        let report = attention_report(&projects, &config, |path| {
+            path != std::path::Path::new("/path/gone")
+        });
+        let names = |projects: &[&Project]| -> Vec<String> {
+            projects
+                .iter()
+                .map(|project| project.name.clone())
+                .collect()
+        };
+
+        assert_eq!(report.uncloned.len(), 1);
+        assert_eq!(report.uncloned[0].0, 'p');
+        assert_eq!(report.uncloned[0].1.name, "pinned");
+        assert_eq!(names(&report.dirty), vec!["dirty"]);
+        // Remote projects aren't expected to exist until cloned
+        assert_eq!(names(&report.missing), vec!["gone"]);
+        assert!(!report.is_empty());
+
+        let quiet = attention_report(&projects, &Config::default(), |_| true);
+        assert!(quiet.uncloned.is_empty());
+        assert!(quiet.missing.is_empty());
+    }
+
+    #[test]
+    fn test_fzf_lines_respect_source_filter_and_limit() {
+        let projects = ProjectList::from_projects(vec![
+            Project::new_local("one".to_string(), "/path/one"),
+            Project::new_github(
+                "remote".to_string(),
+                "/path/remote",
+                "https://github.com/user/remote".to_string(),
+            ),
+            Project::new_local("two".to_string(), "/path/two"),
+            Project::new_local("three".to_string(), "/path/three"),
+        ]);
+        let config = Config::default();
+
+        let all = fzf_project_lines(&projects, &ProjectFilter::default(), &config);
+        assert_eq!(all.len(), 4);
+        assert_eq!(all[1].1, "🐙 remote");
+
+        let filter = ProjectFilter {
+            source: Some(ProjectSource::Local),
+            limit: Some(2),
+        };
+        let lines: Vec<String> = fzf_project_lines(&projects, &filter, &config)
+            .into_iter()
+            .map(|(_, line)| line)
+            .collect();
+        assert_eq!(lines, vec!["📁 one", "📁 two"]);
+
+        let rust_project = ProjectList::from_projects(vec![Project::new_local(
+            "crate".to_string(),
+            "/path/crate",
+        )
+        .with_project_type(Some("rust".to_string()))]);
+        let lines = fzf_project_lines(&rust_project, &ProjectFilter::default(), &config);
+        assert_eq!(lines[0].1, "📁 🦀 crate");
+    }
+
+    #[test]
+    fn test_fzf_exit_codes() {
+        assert_eq!(fzf_outcome(Some(0)), FzfOutcome::Selected);
+        assert_eq!(fzf_outcome(Some(1)), FzfOutcome::NoMatch);
+        assert_eq!(fzf_outcome(Some(130)), FzfOutcome::Cancelled);
+        assert_eq!(fzf_outcome(None), FzfOutcome::Cancelled);
+        assert_eq!(fzf_outcome(Some(2)), FzfOutcome::Failed);
+        assert_eq!(fzf_outcome(Some(127)), FzfOutcome::Failed);
+    }
+
+    #[test]
+    fn test_fzf_columns_are_aligned() {
+        let projects = ProjectList::from_projects(vec![
+            Project::new_local("api".to_string(), "/path/api"),
+            Project::new_github(
+                "web-frontend".to_string(),
+                "/path/web-frontend",
+                "https://github.com/user/web-frontend".to_string(),
+            )
+            .with_last_modified(chrono::Utc::now() - chrono::Duration::days(3)),
+        ]);
+        let config = Config {
+            fzf_format: FzfFormat::Columns,
+            ..Config::default()
+        };
+
+        let lines: Vec<String> = fzf_project_lines(&projects, &ProjectFilter::default(), &config)
+            .into_iter()
+            .map(|(_, line)| line)
+            .collect();
+        assert_eq!(
+            lines,
+            vec![
+                "api           local   -       /path/api",
+                "web-frontend  github  3d ago  /path/web-frontend",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_selected_fzf_line_maps_back_to_project() {
+        let projects = ProjectList::from_projects(vec![
+            Project::new_local("same".to_string(), "/path/a/same"),
+            Project::new_local("same".to_string(), "/path/b/same"),
+        ]);
+        let config = Config::default();
+        let lines = fzf_project_lines(&projects, &ProjectFilter::default(), &config);
+        // Both lines read the same; only the index field tells them apart
+        assert_eq!(lines[0].1, lines[1].1);
+
+        let selected = selected_fzf_project(&lines, &format!("1\t{}", lines[1].1)).unwrap();
+        assert_eq!(selected.path, PathBuf::from("/path/b/same"));
+        assert!(selected_fzf_project(&lines, "7\tmissing").is_none());
+        assert!(selected_fzf_project(&lines, &lines[0].1).is_none());
+    }
+
+    /// Records opened paths, and the `open_command` each was opened with,
+    /// instead of launching an editor
+    #[derive(Default)]
+    struct RecordingOpener {
+        opened: std::sync::Mutex<Vec<PathBuf>>,
+        open_commands: std::sync::Mutex<Vec<Option<String>>>,
+    }
+
+    impl Opener for RecordingOpener {
+        fn open_project(&self, project: &Project, config: &Config) -> Result<()> {
+            self.open_project_path(&project.path, config)
+        }
+
+        fn open_project_path(&self, path: &std::path::Path, config: &Config) -> Result<()> {
+            self.opened.lock().unwrap().push(path.to_path_buf());
+            self.open_commands
+                .lock()
+                .unwrap()
+                .push(config.open_command.clone());
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_open_by_name_opens_resolved_project() {
+        let opener = RecordingOpener::default();
+        let projects = ProjectList::from_projects(vec![
+            Project::new_local("app-server".to_string(), "/path/to/app-server"),
+            Project::new_local("app".to_string(), "/path/to/app"),
+        ]);
+
+        let project = open_project_by_name(
+            &opener,
+            "app",
+            false,
+            &projects,
+            &Config::default(),
+            false,
+            || panic!("cached match should not rescan"),
+        )
+        .unwrap();
+
+        assert_eq!(project.name, "app");
+        assert_eq!(
+            *opener.opened.lock().unwrap(),
+            vec![PathBuf::from("/path/to/app")]
+        );
+    }
+
+    #[test]
+    fn test_quick_code_opens_mapped_project() {
+        let opener = RecordingOpener::default();
+        let projects = ProjectList::from_projects(vec![
+            Project::new_local("app-server".to_string(), "/path/to/app-server"),
+            Project::new_local("app".to_string(), "/path/to/app"),
+        ]);
+        let mut config = Config::default();
+        config.quick_codes.insert('a', "app".to_string());
+
+        let project = open_project_by_name(&opener, "@a", false, &projects, &config, false, || {
+            panic!("cached match should not rescan")
+        })
+        .unwrap();
+
+        assert_eq!(project.name, "app");
+        assert_eq!(
+            *opener.opened.lock().unwrap(),
+            vec![PathBuf::from("/path/to/app")]
+        );
+    }
+
+    #[test]
+    fn test_unmapped_quick_code_errors() {
+        let opener = RecordingOpener::default();
+
+        let err = open_project_by_name(
+            &opener,
+            "@z",
+            false,
+            &cached_projects(),
+            &Config::default(),
+            false,
+            || panic!("unmapped code should not rescan"),
+        )
+        .unwrap_err();
+
+        assert!(matches!(
+            err.downcast_ref::<ProjectNotFound>(),
+            Some(ProjectNotFound::UnknownQuickCode('z'))
+        ));
+        assert_eq!(
+            err.to_string(),
+            "No quick code '@z' (add one with: sw code set z <project>)"
+        );
+        assert!(opener.opened.lock().unwrap().is_empty());
+
+        // Longer names starting with @ are ordinary names
+        assert_eq!(
+            expand_quick_code("@team", false, &Config::default()).unwrap(),
+            ("@team", false)
+        );
+    }
+
+    #[test]
+    fn test_first_opens_top_ranked_match() {
+        let opener = RecordingOpener::default();
+        let projects = ProjectList::from_projects(vec![
+            Project::new_local("legacy-rapid-importer".to_string(), "/path/to/importer"),
+            Project::new_local("api-gateway".to_string(), "/path/to/api-gateway"),
+            Project::new_local("my-api".to_string(), "/path/to/my-api"),
+        ]);
+        let config = Config {
+            recency_weight: 0,
+            ..Config::default()
+        };
+
+        let project = open_first_match(&opener, "api", &projects, &config, false, || {
+            panic!("cached match should not rescan")
+        })
+        .unwrap();
+
+        assert_eq!(project.name, "api-gateway");
+        assert_eq!(
+            *opener.opened.lock().unwrap(),
+            vec![PathBuf::from("/path/to/api-gateway")]
+        );
+    }
+
+    #[test]
+    fn test_first_expands_quick_codes() {
+        let opener = RecordingOpener::default();
+        let projects = ProjectList::from_projects(vec![
+            Project::new_local("api-gateway".to_string(), "/path/to/api-gateway"),
+            Project::new_local("api".to_string(), "/path/to/api"),
+        ]);
+        let mut config = Config::default();
+        config.quick_codes.insert('a', "api".to_string());
+
+        let project = open_first_match(&opener, "@a", &projects, &config, false, || {
+            panic!("cached match should not rescan")
+        })
+        .unwrap();
+        assert_eq!(project.path, PathBuf::from("/path/to/api"));
+
+        let err = open_first_match(&opener, "@z", &projects, &config, false, || {
+            panic!("unknown code should not rescan")
+        })
+        .unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<ProjectNotFound>(),
+            Some(ProjectNotFound::UnknownQuickCode('z'))
+        ));
+    }
+
+    #[test]
+    fn test_first_errors_without_matches() {
+        let opener = RecordingOpener::default();
+        let rescanned = std::cell::Cell::new(false);
+
+        let err = open_first_match(
+            &opener,
+            "zzz",
+            &cached_projects(),
+            &Config::default(),
+            false,
+            || {
+                rescanned.set(true);
+                Ok(ProjectList::new())
+            },
+        )
+        .unwrap_err();
+
+        assert!(rescanned.get());
+        assert!(matches!(
+            err.downcast_ref::<ProjectNotFound>(),
+            Some(ProjectNotFound::NoMatch(query)) if query == "zzz"
+        ));
+        assert!(opener.opened.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_open_by_name_does_not_open_on_miss() {
+        let opener = RecordingOpener::default();
+
+        let err = open_project_by_name(
+            &opener,
+            "missing",
+            false,
+            &cached_projects(),
+            &Config::default(),
+            false,
+            || Ok(ProjectList::new()),
+        )
+        .unwrap_err();
+
+        assert!(err.downcast_ref::<ProjectNotFound>().is_some());
+        assert!(opener.opened.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_prewarm_skips_while_another_holds_the_lock() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let lock_path = temp_dir.path().join("cache").join("prewarm.lock");
+
+        let ran = run_exclusive(&lock_path, || Ok(())).unwrap();
+        assert!(ran);
+
+        let holder = File::create(&lock_path).unwrap();
+        holder.lock().unwrap();
+
+        let start = std::time::Instant::now();
+        let ran = run_exclusive(&lock_path, || panic!("rescanned while locked")).unwrap();
+        assert!(!ran);
+        assert!(start.elapsed() < std::time::Duration::from_secs(1));
+
+        holder.unlock().unwrap();
+        assert!(run_exclusive(&lock_path, || Ok(())).unwrap());
+    }
+
+    #[test]
+    fn test_apply_selection_dispatches_on_action() {
+        let project = Project::new_local("app".to_string(), "/path/to/app");
+        let config = Config::default();
+        let copied = std::sync::Mutex::new(Vec::new());
+        let copy = |text: &str| {
+            copied.lock().unwrap().push(text.to_string());
+            Ok(())
+        };
+
+        let opener = RecordingOpener::default();
+        let mut out = Vec::new();
+        apply_selection(
+            SelectionAction::Print,
+            &project,
+            &config,
+            &opener,
+            &mut out,
+            &copy,
+        )
+        .unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "/path/to/app\n");
+        assert!(opener.opened.lock().unwrap().is_empty());
+        assert!(copied.lock().unwrap().is_empty());
+
+        let mut out = Vec::new();
+        apply_selection(
+            SelectionAction::Copy,
+            &project,
+            &config,
+            &opener,
+            &mut out,
+            &copy,
+        )
+        .unwrap();
+        assert_eq!(*copied.lock().unwrap(), vec!["/path/to/app".to_string()]);
+        assert!(opener.opened.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_uncloned_matching_selects_filtered_subset() {
+        let projects = vec![
+            Project::new_github(
+                "payments-api".to_string(),
+                "/clones/payments-api",
+                "https://github.com/org/payments-api".to_string(),
+            ),
+            Project::new_github(
+                "Payments-Web".to_string(),
+                "/clones/payments-web",
+                "https://github.com/org/payments-web".to_string(),
+            ),
+            Project::new_github(
+                "payments-cli".to_string(),
+                "/clones/payments-cli",
+                "https://github.com/org/payments-cli".to_string(),
+            ),
+            Project::new_github(
+                "search".to_string(),
+                "/clones/search",
+                "https://github.com/org/search".to_string(),
+            ),
+            Project::new_local("payments-local".to_string(), "/code/payments-local"),
+        ];
+        // payments-cli is already checked out
+        let needs_clone = |project: &Project| {
+            project.source == ProjectSource::GitHub && project.name != "payments-cli"
+        };
+        let names = |selected: Vec<&Project>| -> Vec<String> {
+            selected
+                .iter()
+                .map(|project| project.name.clone())
+                .collect()
+        };
+
+        let github = ProjectFilter {
+            source: Some(ProjectSource::GitHub),
+            limit: None,
+        };
+        assert_eq!(
+            names(uncloned_matching(
+                &projects,
+                &github,
+                Some("PAYMENTS"),
+                needs_clone
+            )),
+            vec!["payments-api", "Payments-Web"]
+        );
+        assert_eq!(
+            names(uncloned_matching(&projects, &github, None, needs_clone)),
+            vec!["payments-api", "Payments-Web", "search"]
+        );
+
+        let limited = ProjectFilter {
+            source: None,
+            limit: Some(1),
+        };
+        assert_eq!(
+            names(uncloned_matching(
+                &projects,
+                &limited,
+                Some("web"),
+                needs_clone
+            )),
+            vec!["Payments-Web"]
+        );
+        assert!(uncloned_matching(&projects, &github, Some("nothing"), needs_clone).is_empty());
+    }
+
+    #[test]
+    fn test_clone_report_lists_each_repository() {
+        let ok = Project::new_github(
+            "api".to_string(),
+            "/clones/api",
+            "https://github.com/org/api".to_string(),
+        );
+        let broken = Project::new_github(
+            "web".to_string(),
+            "/clones/web",
+            "https://github.com/org/web".to_string(),
+        );
+        let results = vec![
+            (&ok, Ok(())),
+            (&broken, Err(anyhow::anyhow!("Git clone failed: denied"))),
+        ];
+
+        assert_eq!(
+            clone_report(&results),
+            vec![
+                "✓ api → /clones/api".to_string(),
+                "✗ web: Git clone failed: denied".to_string(),
+                "Cloned 1 of 2 projects".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_apply_selection_open_uses_opener() {
+        let project = Project::new_local("app".to_string(), "/path/to/app");
+        let opener = RecordingOpener::default();
+        let mut out = Vec::new();
+        let copy = |_: &str| -> Result<()> { panic!("copied on open") };
+
+        apply_selection(
+            SelectionAction::Open,
+            &project,
+            &Config::default(),
+            &opener,
+            &mut out,
+            &copy,
+        )
+        .unwrap();
+        assert_eq!(
+            *opener.opened.lock().unwrap(),
+            vec![PathBuf::from("/path/to/app")]
+        );
+        assert_eq!(String::from_utf8(out).unwrap(), "Opened project: app\n");
+    }
+
+    #[test]
+    fn test_apply_selection_open_prefers_switch_command() {
+        let project = Project::new_local("app".to_string(), "/path/to/app");
+        let opener = RecordingOpener::default();
+        let copy = |_: &str| -> Result<()> { panic!("copied on open") };
+        let config = Config {
+            open_command: Some("open {path}".to_string()),
+            editor_switch_command: Some("code --reuse-window {path}".to_string()),
+            ..Config::default()
+        };
+
+        apply_selection(
+            SelectionAction::Open,
+            &project,
+            &config,
+            &opener,
+            &mut Vec::new(),
+            &copy,
+        )
+        .unwrap();
+        assert_eq!(
+            *opener.open_commands.lock().unwrap(),
+            vec![Some("code --reuse-window {path}".to_string())]
+        );
+    }
+}
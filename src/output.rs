@@ -0,0 +1,46 @@
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+
+static QUIET: AtomicBool = AtomicBool::new(false);
+static VERBOSITY: AtomicU8 = AtomicU8::new(0);
+
+/// Silence confirmations and tips for the rest of the process (`--quiet`)
+pub fn set_quiet(quiet: bool) {
+    QUIET.store(quiet, Ordering::Relaxed);
+}
+
+pub fn is_quiet() -> bool {
+    QUIET.load(Ordering::Relaxed)
+}
+
+/// How many times `-v` was given: 1 for summaries, 2 for command lines and
+/// cache decisions
+pub fn set_verbosity(level: u8) {
+    VERBOSITY.store(level, Ordering::Relaxed);
+}
+
+pub fn verbosity() -> u8 {
+    VERBOSITY.load(Ordering::Relaxed)
+}
+
+/// `println!` for confirmations, progress and tips that scripts don't need;
+/// prints nothing under `--quiet`. Requested output such as listings and
+/// errors should keep using `println!`/`eprintln!`.
+macro_rules! say {
+    ($($arg:tt)*) => {
+        if !$crate::output::is_quiet() {
+            println!($($arg)*);
+        }
+    };
+}
+pub(crate) use say;
+
+/// `eprintln!` for `-vv` diagnostics: external command lines, cache
+/// decisions and other per-repository detail
+macro_rules! trace {
+    ($($arg:tt)*) => {
+        if $crate::output::verbosity() >= 2 {
+            eprintln!($($arg)*);
+        }
+    };
+}
+pub(crate) use trace;
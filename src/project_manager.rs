@@ -1,17 +1,50 @@
 use crate::cache::Cache;
 use crate::config::Config;
-use crate::models::ProjectList;
-use crate::scanner::ScanManager;
+use crate::models::{ProjectList, ProjectSource, Refresh};
+use crate::opener::shell_command;
+use crate::output::trace;
+use crate::scanner::{millis, ScanManager, ScanProfile, ScanProgress};
 use anyhow::Result;
+use chrono::Utc;
+use std::path::Path;
+use std::process::{Child, Stdio};
 use std::sync::mpsc::{channel, Receiver};
 use std::thread;
 
+/// Per-run command-line choices about which projects a scan yields; unlike
+/// the config they are never saved
+#[derive(Debug, Clone, Default)]
+pub struct ScanOptions {
+    /// Only show projects on this remote host (`--host`)
+    pub host_filter: Option<String>,
+    /// Keep every scanner's entry for a path instead of merging them
+    /// (`--no-dedup`); such scans bypass the cache
+    pub skip_dedup: bool,
+}
+
 /// Get projects using cache if valid, otherwise scan fresh
-pub fn get_projects_with_cache(config: &Config, verbose: bool) -> Result<ProjectList> {
+pub fn get_projects_with_cache(
+    config: &Config,
+    options: &ScanOptions,
+    verbose: bool,
+) -> Result<ProjectList> {
+    if let Some(reason) = cache_bypass_reason(config, options) {
+        if verbose {
+            println!("{}, scanning without the cache...", reason);
+        }
+        return get_projects_fresh(config, options, verbose);
+    }
+
     let cache = Cache::new(config)?;
     let _scan_manager = ScanManager::new();
 
     let cached_projects = cache.load_projects()?;
+    let cache_path = cache.projects_cache_path();
+    trace!(
+        "Projects cache {}: {}",
+        cache_path.display(),
+        cache.freshness(&cache_path)
+    );
     let should_scan =
         cached_projects.is_none() || !cache.is_cache_valid(cache.projects_cache_path());
 
@@ -20,7 +53,7 @@ pub fn get_projects_with_cache(config: &Config, verbose: bool) -> Result<Project
             if verbose {
                 println!("Using cached projects");
             }
-            return Ok(cached);
+            return Ok(present(cached, config, options));
         } else if verbose {
             println!("Cache is stale, refreshing...");
         }
@@ -28,19 +61,68 @@ pub fn get_projects_with_cache(config: &Config, verbose: bool) -> Result<Project
         println!("Cache miss, scanning for projects...");
     }
 
-    get_projects_fresh(config, verbose)
+    get_projects_fresh(config, options, verbose)
 }
 
 /// Get projects by scanning fresh (ignoring cache)
-pub fn get_projects_fresh(config: &Config, verbose: bool) -> Result<ProjectList> {
+pub fn get_projects_fresh(
+    config: &Config,
+    options: &ScanOptions,
+    verbose: bool,
+) -> Result<ProjectList> {
+    get_projects_fresh_with_progress(config, options, verbose, |_| {})
+}
+
+/// Scan fresh, reporting each scanner's outcome as it completes
+pub fn get_projects_fresh_with_progress(
+    config: &Config,
+    options: &ScanOptions,
+    verbose: bool,
+    on_progress: impl FnMut(ScanProgress),
+) -> Result<ProjectList> {
+    get_projects_fresh_profiled(config, options, verbose, on_progress).map(|(projects, _)| projects)
+}
+
+/// Like `get_projects_fresh_with_progress`, also timing each scanner and
+/// the cache reads and writes around them
+pub fn get_projects_fresh_profiled(
+    config: &Config,
+    options: &ScanOptions,
+    verbose: bool,
+    mut on_progress: impl FnMut(ScanProgress),
+) -> Result<(ProjectList, ScanProfile)> {
+    let total_start = std::time::Instant::now();
     let cache = Cache::new(config)?;
-    let scan_manager = ScanManager::new();
+    let scan_manager = if options.skip_dedup {
+        ScanManager::new().keep_duplicates()
+    } else {
+        ScanManager::new()
+    };
+
+    // Sources that failed or timed out keep their previous entry
+    let read_start = std::time::Instant::now();
+    let mut metadata = cache.scan_metadata().unwrap_or_default();
+    let cache_read = read_start.elapsed();
 
     let scan_start = std::time::Instant::now();
-    let project_list = scan_manager.scan_all_verbose(config, verbose)?;
+    let started_at = Utc::now();
+    let (project_list, scanners) = scan_manager.scan_all_profiled(config, verbose, |event| {
+        if let ScanProgress::Finished { scanner, count } = event {
+            metadata.record(scanner, count, started_at);
+        }
+        on_progress(event);
+    })?;
     let scan_duration = scan_start.elapsed();
 
-    cache.save_projects(&project_list)?;
+    // Undeduplicated lists and one-off roots must not be served to later runs
+    let write_start = std::time::Instant::now();
+    if cache_bypass_reason(config, options).is_none() {
+        cache.save_projects(&project_list)?;
+        if let Err(e) = cache.save_scan_metadata(&metadata) {
+            eprintln!("Warning: Failed to save scan metadata: {}", e);
+        }
+    }
+    let cache_write = write_start.elapsed();
 
     if verbose {
         println!(
@@ -50,14 +132,79 @@ pub fn get_projects_fresh(config: &Config, verbose: bool) -> Result<ProjectList>
         );
     }
 
-    Ok(project_list)
+    run_post_scan_command(config, project_list.len());
+
+    let profile = ScanProfile {
+        scanners,
+        projects: project_list.len(),
+        cache_read_ms: millis(cache_read),
+        cache_write_ms: millis(cache_write),
+        total_ms: millis(total_start.elapsed()),
+    };
+    Ok((present(project_list, config, options), profile))
+}
+
+/// Why this run's scan must neither read nor write the shared projects
+/// cache, if it must
+fn cache_bypass_reason(config: &Config, options: &ScanOptions) -> Option<&'static str> {
+    if options.skip_dedup {
+        Some("Deduplication disabled")
+    } else if config.has_extra_dirs() {
+        Some("SW_EXTRA_DIRS set")
+    } else {
+        None
+    }
+}
+
+/// Spawn `post_scan_command` detached, if set, with `{count}` and
+/// `SW_PROJECT_COUNT` set to `count`. A hook that can't be started only
+/// warns; its exit status is never checked.
+fn run_post_scan_command(config: &Config, count: usize) -> Option<Child> {
+    let command = config.post_scan_command.as_deref()?.trim();
+    if command.is_empty() {
+        return None;
+    }
+
+    let command = command.replace("{count}", &count.to_string());
+    trace!("Running post-scan command: {}", command);
+    let mut cmd = shell_command(&command, Path::new("."), &[]);
+    cmd.env("SW_PROJECT_COUNT", count.to_string())
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null());
+    match cmd.spawn() {
+        Ok(child) => Some(child),
+        Err(e) => {
+            eprintln!("Warning: Failed to run post-scan command: {}", e);
+            None
+        }
+    }
+}
+
+/// Narrow a full project list to `options.host_filter`, if set, then
+/// qualify the names that still collide and, with `show_git_status`, check
+/// working trees for changes. The cache always holds every project.
+fn present(mut project_list: ProjectList, config: &Config, options: &ScanOptions) -> ProjectList {
+    if let Some(ref host) = options.host_filter {
+        project_list.retain_host(host);
+    }
+    project_list.qualify_colliding_names();
+    if config.show_git_status {
+        crate::scanner::local::populate_git_status(&mut project_list);
+    }
+    project_list
 }
 
 /// Get projects immediately from cache (even if stale) and optionally refresh in background
 pub fn get_projects_with_background_refresh(
     config: &Config,
+    options: &ScanOptions,
     verbose: bool,
-) -> Result<(ProjectList, Option<Receiver<ProjectList>>)> {
+) -> Result<(ProjectList, Option<Receiver<Refresh>>)> {
+    if cache_bypass_reason(config, options).is_some() {
+        return Ok((get_projects_with_cache(config, options, verbose)?, None));
+    }
+
     let cache = Cache::new(config)?;
 
     // Always load cached data first, even if stale
@@ -66,6 +213,7 @@ pub fn get_projects_with_background_refresh(
     // Check if we need to refresh
     let needs_refresh =
         cached_projects.is_empty() || !cache.is_cache_valid(cache.projects_cache_path());
+    let cached_projects = present(cached_projects, config, options);
 
     if needs_refresh {
         if verbose {
@@ -74,12 +222,23 @@ pub fn get_projects_with_background_refresh(
 
         let (tx, rx) = channel();
         let config_clone = config.clone();
+        let options_clone = options.clone();
 
         // Spawn background thread to refresh
         thread::spawn(move || {
-            if let Ok(fresh_projects) = get_projects_fresh(&config_clone, false) {
+            let mut scanned_sources = Vec::new();
+            let fresh =
+                get_projects_fresh_with_progress(&config_clone, &options_clone, false, |event| {
+                    if let ScanProgress::Finished { scanner, .. } = event {
+                        scanned_sources.push(ProjectSource::from(scanner.to_string()));
+                    }
+                });
+            if let Ok(projects) = fresh {
                 // Ignore send errors (receiver might have been dropped)
-                let _ = tx.send(fresh_projects);
+                let _ = tx.send(Refresh {
+                    projects,
+                    scanned_sources,
+                });
             }
         });
 
@@ -91,3 +250,40 @@ pub fn get_projects_with_background_refresh(
         Ok((cached_projects, None))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn hook_config(command: &str) -> Config {
+        Config {
+            post_scan_command: Some(command.to_string()),
+            ..Config::default()
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_post_scan_command_gets_project_count() {
+        let temp_dir = TempDir::new().unwrap();
+        let out = temp_dir.path().join("count.txt");
+        let config = hook_config(&format!(
+            "echo {{count}} $SW_PROJECT_COUNT > '{}'",
+            out.display()
+        ));
+
+        let status = run_post_scan_command(&config, 42).unwrap().wait().unwrap();
+        assert!(status.success());
+        assert_eq!(std::fs::read_to_string(&out).unwrap().trim(), "42 42");
+    }
+
+    #[test]
+    fn test_failing_post_scan_command_is_not_an_error() {
+        let mut child = run_post_scan_command(&hook_config("exit 3"), 1).unwrap();
+        assert_eq!(child.wait().unwrap().code(), Some(3));
+
+        assert!(run_post_scan_command(&hook_config("  "), 1).is_none());
+        assert!(run_post_scan_command(&Config::default(), 1).is_none());
+    }
+}
@@ -0,0 +1,62 @@
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::fs;
+use std::path::Path;
+
+/// Per-project overrides read from a file in the project root
+pub const PROJECT_SETTINGS_FILE: &str = ".sw.toml";
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct ProjectSettings {
+    /// Overrides the global `post_open_command` for this project, if the
+    /// project is listed in `trusted_projects`
+    pub post_open_command: Option<String>,
+}
+
+impl ProjectSettings {
+    /// Load settings from `project_dir`, or defaults if the file is missing
+    pub fn load(project_dir: &Path) -> Result<Self> {
+        let path = project_dir.join(PROJECT_SETTINGS_FILE);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        toml::from_str(&content).with_context(|| format!("Failed to parse {}", path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_missing_file_uses_defaults() {
+        let temp_dir = TempDir::new().unwrap();
+        let settings = ProjectSettings::load(temp_dir.path()).unwrap();
+        assert_eq!(settings, ProjectSettings::default());
+    }
+
+    #[test]
+    fn test_load_post_open_command() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join(PROJECT_SETTINGS_FILE),
+            "post_open_command = \"npm run dev\"\n",
+        )
+        .unwrap();
+
+        let settings = ProjectSettings::load(temp_dir.path()).unwrap();
+        assert_eq!(settings.post_open_command.as_deref(), Some("npm run dev"));
+    }
+
+    #[test]
+    fn test_invalid_file_is_an_error() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join(PROJECT_SETTINGS_FILE), "not = [valid").unwrap();
+
+        assert!(ProjectSettings::load(temp_dir.path()).is_err());
+    }
+}
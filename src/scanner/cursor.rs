@@ -23,29 +23,40 @@ struct WorkspaceIdentifier {
 }
 
 impl ProjectScanner for CursorScanner {
-    fn scan(&self, _config: &Config) -> Result<ProjectList> {
-        let mut project_list = ProjectList::new();
-
+    fn scan(&self, config: &Config) -> Result<ProjectList> {
         let cursor_storage_path = get_cursor_storage_path()?;
-        if !cursor_storage_path.exists() {
-            return Ok(project_list);
-        }
+        scan_storage(&cursor_storage_path, config)
+    }
 
-        let workspaces = scan_cursor_workspaces(&cursor_storage_path)?;
+    fn scanner_name(&self) -> &'static str {
+        "cursor"
+    }
+}
 
-        for workspace in workspaces {
-            if let Some(project) = workspace_to_project(workspace)? {
-                project_list.add_project(project);
-            }
-        }
+fn scan_storage(storage_path: &Path, config: &Config) -> Result<ProjectList> {
+    let mut project_list = ProjectList::new();
 
-        project_list.sort_by_last_modified();
-        Ok(project_list)
+    if !storage_path.exists() {
+        return Ok(project_list);
     }
 
-    fn scanner_name(&self) -> &'static str {
-        "cursor"
+    let roots = config.scan_roots();
+    let workspaces = scan_cursor_workspaces(storage_path)?;
+
+    for workspace in workspaces {
+        if config.cursor_restrict_to_project_dirs
+            && !roots.iter().any(|root| workspace.path.starts_with(root))
+        {
+            continue;
+        }
+
+        if let Some(project) = workspace_to_project(workspace)? {
+            project_list.add_project(project);
+        }
     }
+
+    project_list.sort_by_last_modified();
+    Ok(project_list)
 }
 
 fn get_cursor_storage_path() -> Result<PathBuf> {
@@ -158,7 +169,9 @@ fn workspace_to_project(workspace: WorkspaceInfo) -> Result<Option<Project>> {
         return Ok(None);
     }
 
-    let mut project = Project::new_cursor(workspace.name, workspace.path);
+    let project_type = super::detect_project_type(&workspace.path);
+    let mut project =
+        Project::new_cursor(workspace.name, workspace.path).with_project_type(project_type);
 
     if let Some(timestamp) = workspace.last_modified {
         project = project.with_last_modified(timestamp);
@@ -379,6 +392,41 @@ mod tests {
         assert!(projects.iter().all(|p| p.source == ProjectSource::Cursor));
     }
 
+    #[test]
+    fn test_restrict_to_project_dirs() {
+        let storage = TempDir::new().unwrap();
+        let projects_root = create_test_project_dir("/code/inside");
+        let elsewhere = create_test_project_dir("/tmp/scratch");
+
+        create_test_workspace_storage(
+            storage.path(),
+            "inside",
+            projects_root.path().join("code/inside").to_str().unwrap(),
+        );
+        create_test_workspace_storage(
+            storage.path(),
+            "outside",
+            elsewhere.path().join("tmp/scratch").to_str().unwrap(),
+        );
+
+        let mut config = Config {
+            project_dirs: vec![projects_root.path().join("code")],
+            ..Config::default()
+        };
+
+        let all = scan_storage(storage.path(), &config).unwrap();
+        assert_eq!(all.len(), 2);
+
+        config.cursor_restrict_to_project_dirs = true;
+        let restricted = scan_storage(storage.path(), &config).unwrap();
+        let names: Vec<&str> = restricted
+            .projects()
+            .iter()
+            .map(|p| p.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["inside"]);
+    }
+
     #[test]
     fn test_get_cursor_storage_path() {
         let path = get_cursor_storage_path().unwrap();
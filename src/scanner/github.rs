@@ -5,11 +5,25 @@ use std::path::PathBuf;
 use std::process::Command;
 
 use super::ProjectScanner;
-use crate::config::Config;
+use crate::config::{Config, GitHubTimestampField};
 use crate::models::{Project, ProjectList};
+use crate::output::trace;
 
 pub struct GitHubScanner;
 
+const GITHUB_API_URL: &str = "https://api.github.com";
+const GITHUB_API_PAGE_SIZE: usize = 100;
+/// Longest line of `gh` output accepted as one repository; a single entry is
+/// a few hundred bytes, so anything this long is a broken stream
+const MAX_REPOSITORY_LINE_BYTES: u64 = 64 * 1024;
+
+/// A GitHub REST API request used when `gh` is unavailable but a token is set
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct GitHubApiRequest {
+    url: String,
+    headers: Vec<(&'static str, String)>,
+}
+
 #[derive(Debug, Deserialize)]
 struct GitHubRepository {
     name: String,
@@ -30,20 +44,40 @@ impl ProjectScanner for GitHubScanner {
             }
         };
 
-        if !is_gh_installed() {
-            return Ok(project_list);
-        }
-
-        if !is_gh_authenticated()? {
+        let fetch_result = if is_gh_installed() {
+            if !is_gh_authenticated()? {
+                return Ok(project_list);
+            }
+            fetch_user_repositories_with_timeout(github_username, 10, config.github_repo_limit)
+        } else if let Some(token) = github_token_from_env() {
+            fetch_user_repositories_with_token(
+                github_username,
+                &token,
+                10,
+                config.github_repo_limit,
+            )
+        } else {
             return Ok(project_list);
-        }
+        };
 
-        let repositories = match fetch_user_repositories_with_timeout(github_username, 10) {
+        let repositories = match fetch_result {
             Ok(repos) => repos,
-            Err(e) => {
-                eprintln!("Warning: GitHub API request timed out or failed: {}", e);
-                return Ok(project_list);
-            }
+            Err(e) => match classify_github_error(&e.to_string()) {
+                GitHubFailure::RateLimited => {
+                    eprintln!(
+                        "Warning: GitHub rate limit reached, keeping previously cached GitHub projects"
+                    );
+                    return Ok(cached_github_projects(config));
+                }
+                GitHubFailure::Auth => {
+                    eprintln!("Warning: GitHub authentication failed: {}", e);
+                    return Ok(project_list);
+                }
+                GitHubFailure::Other => {
+                    eprintln!("Warning: GitHub API request timed out or failed: {}", e);
+                    return Ok(project_list);
+                }
+            },
         };
 
         for repo in repositories {
@@ -61,11 +95,69 @@ impl ProjectScanner for GitHubScanner {
     }
 }
 
+/// Why a GitHub fetch failed, as far as the error text tells
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum GitHubFailure {
+    RateLimited,
+    Auth,
+    Other,
+}
+
+fn classify_github_error(message: &str) -> GitHubFailure {
+    let message = message.to_lowercase();
+
+    if message.contains("rate limit")
+        || message.contains("abuse detection")
+        || message.contains("http 429")
+    {
+        GitHubFailure::RateLimited
+    } else if message.contains("bad credentials")
+        || message.contains("requires authentication")
+        || message.contains("http 401")
+        || message.contains("gh auth login")
+    {
+        GitHubFailure::Auth
+    } else {
+        GitHubFailure::Other
+    }
+}
+
+/// A 403/429 is a rate limit when GitHub says no requests remain or asks us
+/// to retry later; otherwise a 403 is a permissions problem
+fn is_rate_limit_status(status: u16, remaining: Option<&str>, retry_after: Option<&str>) -> bool {
+    match status {
+        429 => true,
+        403 => remaining.map(str::trim) == Some("0") || retry_after.is_some(),
+        _ => false,
+    }
+}
+
+/// GitHub projects from the last cached scan, regardless of age
+fn cached_github_projects(config: &Config) -> ProjectList {
+    let cached = crate::cache::Cache::new(config)
+        .and_then(|cache| cache.load_stale_projects())
+        .ok()
+        .flatten()
+        .unwrap_or_default();
+
+    ProjectList::from_projects(
+        cached
+            .filter_by_source(crate::models::ProjectSource::GitHub)
+            .into_iter()
+            .cloned()
+            .collect(),
+    )
+}
+
 pub fn is_gh_installed() -> bool {
     which::which("gh").is_ok()
 }
 
 pub fn is_gh_authenticated() -> Result<bool> {
+    if !is_gh_installed() {
+        return Ok(false);
+    }
+
     let output = Command::new("gh")
         .args(["api", "user", "--jq", ".login"])
         .output()
@@ -94,6 +186,7 @@ pub fn run_gh_auth_login() -> Result<bool> {
 }
 
 /// Prompt user to set up GitHub integration interactively
+#[allow(dead_code)]
 pub fn prompt_github_setup() -> Result<Option<String>> {
     use dialoguer::Confirm;
 
@@ -223,6 +316,59 @@ pub fn prompt_github_setup() -> Result<Option<String>> {
     }
 }
 
+/// A likely GitHub username from local git setup, for `sw setup` to offer:
+/// the owner of the current repo's GitHub `origin`, else a
+/// `users.noreply.github.com` email or a single-word `user.name`
+pub fn suggest_username() -> Option<String> {
+    let from_origin = std::env::current_dir()
+        .ok()
+        .and_then(|cwd| git2::Repository::discover(cwd).ok())
+        .and_then(|repo| {
+            let remote = repo.find_remote("origin").ok()?;
+            remote.url().and_then(github_owner_from_remote)
+        });
+    if from_origin.is_some() {
+        return from_origin;
+    }
+
+    let git_config = git2::Config::open_default().ok()?;
+    let setting = |key: &str| git_config.get_string(key).ok();
+    setting("user.email")
+        .as_deref()
+        .and_then(username_from_noreply_email)
+        .or_else(|| setting("user.name").filter(|name| is_plausible_username(name)))
+}
+
+/// Owner part of a GitHub remote, e.g. `octocat` for
+/// `git@github.com:octocat/hello.git`
+pub fn github_owner_from_remote(url: &str) -> Option<String> {
+    let host = crate::models::remote_host(url)?;
+    if !host.contains("github") {
+        return None;
+    }
+    let web_url = crate::models::remote_web_url(url)?;
+    let path = web_url.strip_prefix(&format!("https://{}/", host))?;
+    let (owner, _repo) = path.split_once('/')?;
+    is_plausible_username(owner).then(|| owner.to_string())
+}
+
+/// `octocat` from `octocat@users.noreply.github.com` or
+/// `12345+octocat@users.noreply.github.com`
+fn username_from_noreply_email(email: &str) -> Option<String> {
+    let local = email.trim().strip_suffix("@users.noreply.github.com")?;
+    let login = local.split_once('+').map_or(local, |(_, login)| login);
+    is_plausible_username(login).then(|| login.to_string())
+}
+
+/// Letters, digits and inner hyphens, as GitHub allows in usernames
+fn is_plausible_username(name: &str) -> bool {
+    !name.is_empty()
+        && name.len() <= 39
+        && !name.starts_with('-')
+        && !name.ends_with('-')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+}
+
 /// Get the authenticated GitHub username
 pub fn get_gh_username() -> Result<String> {
     let output = Command::new("gh")
@@ -247,69 +393,283 @@ pub fn get_gh_username() -> Result<String> {
 fn fetch_user_repositories_with_timeout(
     username: &str,
     timeout_seconds: u64,
+    limit: usize,
 ) -> Result<Vec<GitHubRepository>> {
+    use std::io::Read;
     use std::process::{Command, Stdio};
-    use std::time::{Duration, Instant};
-
-    let start_time = Instant::now();
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    let endpoint = format!("/users/{}/repos", username);
+    let args = [
+        "api",
+        &endpoint,
+        "--paginate",
+        "--jq",
+        ".[] | {name, html_url, archived, pushed_at, updated_at}",
+    ];
+    trace!("$ gh {}", args.join(" "));
 
     let mut child = Command::new("gh")
-        .args([
-            "api",
-            &format!("/users/{}/repos", username),
-            "--paginate",
-            "--jq",
-            ".[] | {name, html_url, archived, pushed_at, updated_at}",
-        ])
+        .args(args)
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
         .spawn()
         .context("Failed to spawn GitHub API command")?;
 
-    loop {
-        match child.try_wait() {
-            Ok(Some(status)) => {
-                let output = child
-                    .wait_with_output()
-                    .context("Failed to get output from GitHub API command")?;
-
-                if !status.success() {
-                    let stderr = String::from_utf8_lossy(&output.stderr);
-                    anyhow::bail!("GitHub API call failed: {}", stderr);
-                }
+    // Parse on a separate thread so the timeout still applies while the
+    // read blocks
+    let stdout = child
+        .stdout
+        .take()
+        .context("Failed to read output from GitHub API command")?;
+    let (sender, receiver) = mpsc::channel();
+    std::thread::spawn(move || {
+        let _ = sender.send(read_repositories(std::io::BufReader::new(stdout), limit));
+    });
+
+    let parsed = match receiver.recv_timeout(Duration::from_secs(timeout_seconds)) {
+        Ok(parsed) => parsed,
+        Err(_) => {
+            let _ = child.kill();
+            let _ = child.wait();
+            anyhow::bail!(
+                "GitHub API request timed out after {} seconds",
+                timeout_seconds
+            );
+        }
+    };
+
+    // The output is drained either way, so gh finishes on its own and its
+    // exit status says whether the listing is complete
+    let status = child
+        .wait()
+        .context("Error waiting for GitHub API command")?;
+    if !status.success() {
+        let mut stderr = String::new();
+        if let Some(mut pipe) = child.stderr.take() {
+            let _ = pipe.read_to_string(&mut stderr);
+        }
+        anyhow::bail!("GitHub API call failed: {}", stderr);
+    }
 
-                let stdout = String::from_utf8_lossy(&output.stdout);
-                let mut repositories = Vec::new();
+    let (repositories, truncated) = parsed?;
+    if truncated {
+        eprintln!(
+            "Warning: Keeping only the first {} GitHub repositories (github_repo_limit)",
+            limit
+        );
+    }
+    Ok(repositories)
+}
 
-                for line in stdout.lines() {
-                    if line.trim().is_empty() {
-                        continue;
-                    }
+/// Parse `gh`'s one-repository-per-line output as it arrives, up to
+/// `limit + 1` repositories, then drain the rest unparsed so `gh` can run to
+/// completion. Returns at most `limit` repositories and whether there were
+/// more.
+fn read_repositories(
+    mut reader: impl std::io::BufRead,
+    limit: usize,
+) -> Result<(Vec<GitHubRepository>, bool)> {
+    let parsed = parse_repositories(&mut reader, limit.saturating_add(1));
+    let _ = std::io::copy(&mut reader, &mut std::io::sink());
+
+    let mut repositories = parsed?;
+    let truncated = repositories.len() > limit;
+    repositories.truncate(limit);
+    Ok((repositories, truncated))
+}
 
-                    let repo: GitHubRepository = serde_json::from_str(line)
-                        .with_context(|| format!("Failed to parse repository JSON: {}", line))?;
-                    repositories.push(repo);
-                }
+/// The first `max` repositories in `reader`, or all of them if it holds fewer
+fn parse_repositories(
+    mut reader: impl std::io::BufRead,
+    max: usize,
+) -> Result<Vec<GitHubRepository>> {
+    use std::io::{BufRead, Read};
+
+    let mut repositories = Vec::new();
+    let mut line = Vec::new();
+
+    while repositories.len() < max {
+        line.clear();
+        let read = (&mut reader)
+            .take(MAX_REPOSITORY_LINE_BYTES)
+            .read_until(b'\n', &mut line)
+            .context("Failed to read output from GitHub API command")?;
+        if read == 0 {
+            break;
+        }
+        if line.last() != Some(&b'\n') && read as u64 == MAX_REPOSITORY_LINE_BYTES {
+            anyhow::bail!(
+                "GitHub API output line exceeds {} bytes",
+                MAX_REPOSITORY_LINE_BYTES
+            );
+        }
 
-                return Ok(repositories);
-            }
-            Ok(None) => {
-                if start_time.elapsed() > Duration::from_secs(timeout_seconds) {
-                    let _ = child.kill();
-                    let _ = child.wait();
-                    anyhow::bail!(
-                        "GitHub API request timed out after {} seconds",
-                        timeout_seconds
-                    );
-                }
+        let text = String::from_utf8_lossy(&line);
+        let text = text.trim();
+        if text.is_empty() {
+            continue;
+        }
+
+        let repo: GitHubRepository = serde_json::from_str(text)
+            .with_context(|| format!("Failed to parse repository JSON: {}", text))?;
+        repositories.push(repo);
+    }
+
+    Ok(repositories)
+}
+
+/// Read a GitHub token from `GITHUB_TOKEN` or `GH_TOKEN`
+fn github_token_from_env() -> Option<String> {
+    select_github_token(
+        std::env::var("GITHUB_TOKEN").ok(),
+        std::env::var("GH_TOKEN").ok(),
+    )
+}
+
+fn select_github_token(github_token: Option<String>, gh_token: Option<String>) -> Option<String> {
+    [github_token, gh_token]
+        .into_iter()
+        .flatten()
+        .map(|token| token.trim().to_string())
+        .find(|token| !token.is_empty())
+}
+
+/// Headers for an authenticated GitHub REST API request
+fn api_headers(token: &str) -> Vec<(&'static str, String)> {
+    vec![
+        ("Accept", "application/vnd.github+json".to_string()),
+        ("Authorization", format!("Bearer {}", token)),
+        ("User-Agent", format!("sw/{}", env!("CARGO_PKG_VERSION"))),
+        ("X-GitHub-Api-Version", "2022-11-28".to_string()),
+    ]
+}
+
+/// Request for the account the token belongs to
+fn build_token_user_request(token: &str) -> GitHubApiRequest {
+    GitHubApiRequest {
+        url: format!("{}/user", GITHUB_API_URL),
+        headers: api_headers(token),
+    }
+}
+
+/// Request for a page of `username`'s repositories. `/users/{username}/repos`
+/// only lists public repositories, so when the token is `username`'s own,
+/// `/user/repos` is asked for the repositories it owns instead.
+fn build_repos_request(
+    username: &str,
+    token: &str,
+    page: usize,
+    own_token: bool,
+) -> GitHubApiRequest {
+    let url = if own_token {
+        format!(
+            "{}/user/repos?affiliation=owner&per_page={}&page={}",
+            GITHUB_API_URL, GITHUB_API_PAGE_SIZE, page
+        )
+    } else {
+        format!(
+            "{}/users/{}/repos?per_page={}&page={}",
+            GITHUB_API_URL, username, GITHUB_API_PAGE_SIZE, page
+        )
+    };
+    GitHubApiRequest {
+        url,
+        headers: api_headers(token),
+    }
+}
 
-                std::thread::sleep(Duration::from_millis(100));
+#[derive(Debug, Deserialize)]
+struct GitHubUser {
+    login: String,
+}
+
+/// Login of the account `token` belongs to
+fn token_login(agent: &ureq::Agent, token: &str) -> Result<String> {
+    let body = send_api_request(agent, &build_token_user_request(token))?;
+    let user: GitHubUser =
+        serde_json::from_str(&body).context("Failed to parse GitHub API response")?;
+    Ok(user.login)
+}
+
+/// Send `request` and return the response body
+fn send_api_request(agent: &ureq::Agent, request: &GitHubApiRequest) -> Result<String> {
+    trace!("GET {}", request.url);
+    let mut call = agent.get(&request.url);
+    for (name, value) in &request.headers {
+        call = call.set(name, value);
+    }
+
+    call.call()
+        .map_err(|e| match e {
+            ureq::Error::Status(status, ref response)
+                if is_rate_limit_status(
+                    status,
+                    response.header("x-ratelimit-remaining"),
+                    response.header("retry-after"),
+                ) =>
+            {
+                anyhow::anyhow!("GitHub API rate limit exceeded (HTTP {})", status)
             }
-            Err(e) => {
-                let _ = child.kill();
-                return Err(e).context("Error waiting for GitHub API command");
+            e => anyhow::anyhow!("GitHub API call failed: {}", e),
+        })?
+        .into_string()
+        .context("Failed to read GitHub API response")
+}
+
+fn fetch_user_repositories_with_token(
+    username: &str,
+    token: &str,
+    timeout_seconds: u64,
+    limit: usize,
+) -> Result<Vec<GitHubRepository>> {
+    use std::time::{Duration, Instant};
+
+    let timeout = Duration::from_secs(timeout_seconds);
+    let start_time = Instant::now();
+    let agent = ureq::AgentBuilder::new().timeout(timeout).build();
+
+    // Tokens that can't read their own user, e.g. Actions' GITHUB_TOKEN,
+    // fall back to the public listing
+    let own_token =
+        token_login(&agent, token).is_ok_and(|login| login.eq_ignore_ascii_case(username));
+
+    let mut repositories = Vec::new();
+    let mut page = 1;
+
+    loop {
+        if start_time.elapsed() > timeout {
+            anyhow::bail!(
+                "GitHub API request timed out after {} seconds",
+                timeout_seconds
+            );
+        }
+
+        let request = build_repos_request(username, token, page, own_token);
+        let body = send_api_request(&agent, &request)?;
+
+        let page_repositories: Vec<GitHubRepository> =
+            serde_json::from_str(&body).context("Failed to parse GitHub API response")?;
+
+        let page_len = page_repositories.len();
+        repositories.extend(page_repositories);
+
+        if repositories.len() >= limit {
+            if page_len == GITHUB_API_PAGE_SIZE || repositories.len() > limit {
+                eprintln!(
+                    "Warning: Keeping only the first {} GitHub repositories (github_repo_limit)",
+                    limit
+                );
             }
+            repositories.truncate(limit);
+            return Ok(repositories);
         }
+        if page_len < GITHUB_API_PAGE_SIZE {
+            return Ok(repositories);
+        }
+
+        page += 1;
     }
 }
 
@@ -318,9 +678,17 @@ fn repository_to_project(repo: GitHubRepository, config: &Config) -> Result<Opti
         return Ok(None);
     }
 
-    let clone_path = get_clone_path(&repo.name, config)?;
+    let clone_path = get_clone_path(&repo.name, &repo.html_url, config)?;
 
-    let last_modified = parse_github_timestamp(&repo.pushed_at.or(repo.updated_at))?;
+    let timestamp = match config.github_timestamp_field {
+        GitHubTimestampField::Pushed => repo.pushed_at,
+        GitHubTimestampField::Updated => repo.updated_at,
+        GitHubTimestampField::PushedThenUpdated => repo.pushed_at.or(repo.updated_at),
+    };
+    let last_modified = parse_github_timestamp(&timestamp)?;
+    if !super::is_recently_active(last_modified, config, Utc::now()) {
+        return Ok(None);
+    }
 
     let mut project = Project::new_github(repo.name, clone_path, repo.html_url);
 
@@ -331,10 +699,17 @@ fn repository_to_project(repo: GitHubRepository, config: &Config) -> Result<Opti
     Ok(Some(project))
 }
 
-fn get_clone_path(repo_name: &str, _config: &Config) -> Result<PathBuf> {
-    let home = dirs::home_dir().context("Failed to get home directory")?;
-
-    Ok(home.join("Documents/git").join(repo_name))
+fn get_clone_path(repo_name: &str, html_url: &str, config: &Config) -> Result<PathBuf> {
+    let (host, owner) = super::repo_url_parts(html_url).unwrap_or((
+        "github.com",
+        config.github_username.as_deref().unwrap_or_default(),
+    ));
+    let template = config
+        .clone_path_template
+        .as_deref()
+        .unwrap_or(super::GITHUB_CLONE_TEMPLATE);
+
+    super::render_clone_path(template, host, owner, repo_name)
 }
 
 fn parse_github_timestamp(timestamp_str: &Option<String>) -> Result<Option<DateTime<Utc>>> {
@@ -416,6 +791,67 @@ mod tests {
         assert!(result.is_none());
     }
 
+    #[test]
+    fn test_repository_to_project_outside_active_window() {
+        let pushed = |days_ago: i64| (Utc::now() - chrono::Duration::days(days_ago)).to_rfc3339();
+        let config = Config {
+            github_active_within_days: Some(30),
+            ..Config::default()
+        };
+
+        let kept: Vec<_> = [("recent", Some(pushed(3))), ("stale", Some(pushed(90)))]
+            .into_iter()
+            .filter_map(|(name, pushed_at)| {
+                let repo = create_test_repo(name, false, pushed_at.as_deref());
+                repository_to_project(repo, &config).unwrap()
+            })
+            .map(|project| project.name)
+            .collect();
+        assert_eq!(kept, vec!["recent".to_string()]);
+
+        let mut undated = create_test_repo("undated", false, None);
+        undated.updated_at = None;
+        assert!(repository_to_project(undated, &config).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_github_timestamp_field() {
+        let modified = |field: GitHubTimestampField, pushed_at: Option<&str>| {
+            let repo = create_test_repo("both", false, pushed_at);
+            let config = Config {
+                github_timestamp_field: field,
+                ..Config::default()
+            };
+            repository_to_project(repo, &config)
+                .unwrap()
+                .unwrap()
+                .last_modified
+                .map(|timestamp| timestamp.to_rfc3339())
+        };
+        let pushed = Some("2024-03-01T12:00:00+00:00".to_string());
+        let updated = Some("2024-01-01T00:00:00+00:00".to_string());
+
+        assert_eq!(
+            modified(GitHubTimestampField::Pushed, pushed.as_deref()),
+            pushed
+        );
+        assert_eq!(
+            modified(GitHubTimestampField::Updated, pushed.as_deref()),
+            updated
+        );
+        assert_eq!(
+            modified(GitHubTimestampField::PushedThenUpdated, pushed.as_deref()),
+            pushed
+        );
+
+        // Without a push only the fallback mode picks up the update time
+        assert_eq!(modified(GitHubTimestampField::Pushed, None), None);
+        assert_eq!(
+            modified(GitHubTimestampField::PushedThenUpdated, None),
+            updated
+        );
+    }
+
     #[test]
     fn test_repository_to_project_no_timestamp() {
         let mut repo = create_test_repo("no-timestamp", false, None);
@@ -453,18 +889,131 @@ mod tests {
     #[test]
     fn test_get_clone_path() {
         let config = Config::default();
-        let path = get_clone_path("test-repo", &config).unwrap();
+        let path = get_clone_path(
+            "test-repo",
+            "https://github.com/testuser/test-repo",
+            &config,
+        )
+        .unwrap();
 
         let expected = dirs::home_dir().unwrap().join("Documents/git/test-repo");
         assert_eq!(path, expected);
     }
 
+    #[test]
+    fn test_get_clone_path_with_template() {
+        let config = Config {
+            clone_path_template: Some("~/src/{host}/{owner}/{repo}".to_string()),
+            ..Config::default()
+        };
+        let path = get_clone_path("cli", "https://github.com/octo-org/cli", &config).unwrap();
+
+        let expected = dirs::home_dir()
+            .unwrap()
+            .join("src/github.com/octo-org/cli");
+        assert_eq!(path, expected);
+    }
+
     #[test]
     fn test_github_scanner_name() {
         let scanner = GitHubScanner;
         assert_eq!(scanner.scanner_name(), "github");
     }
 
+    /// Hands out one chunk per `read` call, like a pipe, and fails once the
+    /// chunks run out unless `then_eof` is set
+    struct ChunkedReader {
+        chunks: std::collections::VecDeque<Vec<u8>>,
+        then_eof: bool,
+    }
+
+    impl ChunkedReader {
+        fn new(chunks: &[&str], then_eof: bool) -> Self {
+            Self {
+                chunks: chunks
+                    .iter()
+                    .map(|chunk| chunk.as_bytes().to_vec())
+                    .collect(),
+                then_eof,
+            }
+        }
+    }
+
+    impl std::io::Read for ChunkedReader {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            let Some(mut chunk) = self.chunks.pop_front() else {
+                return if self.then_eof {
+                    Ok(0)
+                } else {
+                    Err(std::io::Error::other("read past the end"))
+                };
+            };
+            let len = chunk.len().min(buf.len());
+            buf[..len].copy_from_slice(&chunk[..len]);
+            if len < chunk.len() {
+                self.chunks.push_front(chunk.split_off(len));
+            }
+            Ok(len)
+        }
+    }
+
+    fn repo_line(name: &str) -> String {
+        format!(
+            "{{\"name\":\"{}\",\"html_url\":\"https://github.com/u/{}\",\"archived\":false,\"pushed_at\":null,\"updated_at\":null}}\n",
+            name, name
+        )
+    }
+
+    #[test]
+    fn test_read_repositories_parses_lines_split_across_reads() {
+        let first = repo_line("alpha");
+        let second = repo_line("beta");
+        let (head, tail) = second.split_at(10);
+        let reader = std::io::BufReader::new(ChunkedReader::new(&[&first, "\n", head, tail], true));
+
+        let (repositories, truncated) = read_repositories(reader, 100).unwrap();
+
+        let names: Vec<&str> = repositories.iter().map(|repo| repo.name.as_str()).collect();
+        assert_eq!(names, vec!["alpha", "beta"]);
+        assert!(!truncated);
+    }
+
+    #[test]
+    fn test_read_repositories_stops_at_limit() {
+        let lines: Vec<String> = ["a", "b", "c"].iter().map(|name| repo_line(name)).collect();
+        // Lines past the first `limit + 1` are drained without being parsed
+        let mut reader =
+            std::io::Cursor::new(format!("{}{}{}not json\n", lines[0], lines[1], lines[2]));
+
+        let (repositories, truncated) = read_repositories(&mut reader, 2).unwrap();
+
+        assert_eq!(repositories.len(), 2);
+        assert!(truncated);
+        assert_eq!(reader.position() as usize, reader.get_ref().len());
+    }
+
+    #[test]
+    fn test_read_repositories_at_exactly_the_limit_is_complete() {
+        let reader = std::io::BufReader::new(ChunkedReader::new(
+            &[&repo_line("a"), &repo_line("b")],
+            true,
+        ));
+
+        let (repositories, truncated) = read_repositories(reader, 2).unwrap();
+
+        assert_eq!(repositories.len(), 2);
+        assert!(!truncated);
+    }
+
+    #[test]
+    fn test_read_repositories_rejects_oversized_lines() {
+        let huge = "x".repeat(MAX_REPOSITORY_LINE_BYTES as usize + 10);
+        let reader = std::io::BufReader::new(ChunkedReader::new(&[&huge], true));
+
+        let err = read_repositories(reader, 100).unwrap_err();
+        assert!(err.to_string().contains("exceeds"), "{}", err);
+    }
+
     #[test]
     fn test_is_gh_installed() {
         let _installed = is_gh_installed();
@@ -479,7 +1028,7 @@ mod tests {
 
     #[test]
     fn test_timeout_mechanism() {
-        let result = fetch_user_repositories_with_timeout("testuser", 1);
+        let result = fetch_user_repositories_with_timeout("testuser", 1, 100);
         let _ = result;
     }
 
@@ -500,4 +1049,159 @@ mod tests {
         let result = is_gh_authenticated();
         assert!(result.is_ok() || result.is_err());
     }
+
+    #[test]
+    fn test_select_github_token_prefers_github_token() {
+        let token = select_github_token(Some("ghp_one".to_string()), Some("ghp_two".to_string()));
+        assert_eq!(token, Some("ghp_one".to_string()));
+
+        let token = select_github_token(None, Some("ghp_two".to_string()));
+        assert_eq!(token, Some("ghp_two".to_string()));
+
+        let token = select_github_token(Some("  ".to_string()), Some("ghp_two".to_string()));
+        assert_eq!(token, Some("ghp_two".to_string()));
+
+        assert!(select_github_token(None, None).is_none());
+    }
+
+    #[test]
+    fn test_build_repos_request() {
+        let request = build_repos_request("testuser", "ghp_secret", 2, false);
+
+        assert_eq!(
+            request.url,
+            "https://api.github.com/users/testuser/repos?per_page=100&page=2"
+        );
+        assert!(request
+            .headers
+            .contains(&("Authorization", "Bearer ghp_secret".to_string())));
+        assert!(request
+            .headers
+            .contains(&("Accept", "application/vnd.github+json".to_string())));
+        assert!(request
+            .headers
+            .iter()
+            .any(|(name, value)| *name == "User-Agent" && value.starts_with("sw/")));
+    }
+
+    #[test]
+    fn test_build_repos_request_with_own_token_includes_private_repos() {
+        let request = build_repos_request("testuser", "ghp_secret", 1, true);
+        assert_eq!(
+            request.url,
+            "https://api.github.com/user/repos?affiliation=owner&per_page=100&page=1"
+        );
+
+        let user = build_token_user_request("ghp_secret");
+        assert_eq!(user.url, "https://api.github.com/user");
+        assert!(user
+            .headers
+            .contains(&("Authorization", "Bearer ghp_secret".to_string())));
+    }
+
+    #[test]
+    fn test_rest_response_uses_repository_conversion() {
+        let body = r#"[{
+            "name": "api-repo",
+            "full_name": "testuser/api-repo",
+            "html_url": "https://github.com/testuser/api-repo",
+            "archived": false,
+            "pushed_at": "2024-01-15T10:30:00Z",
+            "updated_at": "2024-01-01T00:00:00Z"
+        }]"#;
+
+        let repos: Vec<GitHubRepository> = serde_json::from_str(body).unwrap();
+        let project = repository_to_project(repos.into_iter().next().unwrap(), &Config::default())
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(project.name, "api-repo");
+        assert_eq!(
+            project.github_url,
+            Some("https://github.com/testuser/api-repo".to_string())
+        );
+    }
+
+    #[test]
+    fn test_classify_rate_limit_errors() {
+        assert_eq!(
+            classify_github_error(
+                "GitHub API call failed: gh: You have exceeded a secondary rate limit. Please wait a few minutes before you try again. (HTTP 403)"
+            ),
+            GitHubFailure::RateLimited
+        );
+        assert_eq!(
+            classify_github_error("gh: API rate limit exceeded for user ID 1234. (HTTP 403)"),
+            GitHubFailure::RateLimited
+        );
+        assert_eq!(
+            classify_github_error("GitHub API rate limit exceeded (HTTP 429)"),
+            GitHubFailure::RateLimited
+        );
+    }
+
+    #[test]
+    fn test_classify_auth_errors() {
+        assert_eq!(
+            classify_github_error("GitHub API call failed: gh: Bad credentials (HTTP 401)"),
+            GitHubFailure::Auth
+        );
+        assert_eq!(
+            classify_github_error("To get started with GitHub CLI, please run:  gh auth login"),
+            GitHubFailure::Auth
+        );
+        assert_eq!(
+            classify_github_error("GitHub API request timed out after 10 seconds"),
+            GitHubFailure::Other
+        );
+    }
+
+    #[test]
+    fn test_is_rate_limit_status() {
+        assert!(is_rate_limit_status(429, None, None));
+        assert!(is_rate_limit_status(403, Some("0"), None));
+        assert!(is_rate_limit_status(403, Some("12"), Some("60")));
+        assert!(!is_rate_limit_status(403, Some("4999"), None));
+        assert!(!is_rate_limit_status(401, Some("0"), None));
+    }
+
+    #[test]
+    fn test_github_owner_from_remote() {
+        assert_eq!(
+            github_owner_from_remote("git@github.com:octocat/hello-world.git").as_deref(),
+            Some("octocat")
+        );
+        assert_eq!(
+            github_owner_from_remote("https://github.com/rust-lang/rust").as_deref(),
+            Some("rust-lang")
+        );
+        assert_eq!(
+            github_owner_from_remote("ssh://git@github.com/octo-org/app.git").as_deref(),
+            Some("octo-org")
+        );
+        assert_eq!(
+            github_owner_from_remote("git@gitlab.com:octocat/hello.git"),
+            None
+        );
+        assert_eq!(github_owner_from_remote("https://github.com/octocat"), None);
+        assert_eq!(github_owner_from_remote("/srv/git/hello.git"), None);
+    }
+
+    #[test]
+    fn test_username_candidates_from_git_config() {
+        assert_eq!(
+            username_from_noreply_email("12345+octocat@users.noreply.github.com").as_deref(),
+            Some("octocat")
+        );
+        assert_eq!(
+            username_from_noreply_email("octocat@users.noreply.github.com").as_deref(),
+            Some("octocat")
+        );
+        assert_eq!(username_from_noreply_email("octocat@example.com"), None);
+
+        assert!(is_plausible_username("octo-cat42"));
+        assert!(!is_plausible_username("Mona Lisa"));
+        assert!(!is_plausible_username("-octocat"));
+        assert!(!is_plausible_username(""));
+    }
 }
@@ -1,5 +1,6 @@
 use crate::config::Config;
 use crate::models::{Project, ProjectList};
+use crate::output::trace;
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
 use serde_json::Value;
@@ -21,36 +22,50 @@ impl GitLabScanner {
     }
 
     /// Get the clone path for a GitLab repository
-    fn get_clone_path(username: &str, repo_name: &str) -> PathBuf {
-        // Use a similar pattern to GitHub scanner
-        dirs::home_dir()
-            .unwrap_or_else(|| PathBuf::from("/tmp"))
-            .join("gitlab")
-            .join(username)
-            .join(repo_name)
+    fn get_clone_path(
+        config: &Config,
+        host: &str,
+        username: &str,
+        repo_name: &str,
+    ) -> Result<PathBuf> {
+        let template = config
+            .clone_path_template
+            .as_deref()
+            .unwrap_or(crate::scanner::GITLAB_CLONE_TEMPLATE);
+
+        crate::scanner::render_clone_path(template, host, username, repo_name)
     }
 
-    /// Parse GitLab repository JSON into a Project
-    fn repository_to_project(repo_json: &Value, username: &str) -> Result<Project> {
+    /// Parse GitLab repository JSON into a Project. Older and newer glab
+    /// versions disagree on fields, so `name` falls back to the last segment of
+    /// `path_with_namespace` and `web_url` can be rebuilt from it.
+    fn repository_to_project(
+        repo_json: &Value,
+        username: &str,
+        config: &Config,
+    ) -> Result<Project> {
+        let path_with_namespace = repo_json["path_with_namespace"].as_str();
+
         let name = repo_json["name"]
             .as_str()
+            .or_else(|| path_with_namespace.and_then(|path| path.rsplit('/').next()))
+            .filter(|name| !name.is_empty())
             .context("Repository name not found")?
             .to_string();
 
-        let web_url = repo_json["web_url"]
-            .as_str()
-            .context("Repository web_url not found")?
-            .to_string();
-
-        let clone_path = Self::get_clone_path(username, &name);
+        let web_url = match repo_json["web_url"].as_str() {
+            Some(url) => url.to_string(),
+            None => {
+                let path = path_with_namespace.context("Repository web_url not found")?;
+                format!("https://{}/{}", default_gitlab_host(), path)
+            }
+        };
 
-        // Try to parse the last activity timestamp
-        let last_modified = repo_json["last_activity_at"]
-            .as_str()
-            .and_then(parse_gitlab_timestamp);
+        let host = crate::scanner::repo_url_parts(&web_url).map_or("gitlab.com", |(host, _)| host);
+        let clone_path = Self::get_clone_path(config, host, username, &name)?;
 
         Ok(Project::new_gitlab(name, clone_path, web_url)
-            .with_last_modified(last_modified.unwrap_or_else(Utc::now)))
+            .with_last_modified(last_activity(repo_json).unwrap_or_else(Utc::now)))
     }
 }
 
@@ -72,6 +87,7 @@ impl crate::scanner::ProjectScanner for GitLabScanner {
         }
 
         // Use glab to list repositories (uses default configured host)
+        trace!("$ glab repo list --mine -F json");
         let output = Command::new("glab")
             .args(["repo", "list", "--mine", "-F", "json"])
             .stdout(Stdio::piped())
@@ -91,17 +107,22 @@ impl crate::scanner::ProjectScanner for GitLabScanner {
             return Ok(ProjectList::new());
         }
 
-        let repos: Vec<Value> =
+        let json: Value =
             serde_json::from_str(&stdout).context("Failed to parse glab JSON output")?;
+        let repos = repository_entries(json)?;
 
+        let now = Utc::now();
         let mut projects = Vec::new();
         for repo in repos {
             // Skip archived repositories
             if repo["archived"].as_bool().unwrap_or(false) {
                 continue;
             }
+            if !crate::scanner::is_recently_active(last_activity(&repo), config, now) {
+                continue;
+            }
 
-            match Self::repository_to_project(&repo, username) {
+            match Self::repository_to_project(&repo, username, config) {
                 Ok(project) => projects.push(project),
                 Err(e) => {
                     eprintln!("Warning: Failed to parse GitLab repository: {}", e);
@@ -145,6 +166,40 @@ pub fn is_glab_accessible() -> bool {
     }
 }
 
+/// Repository objects from `glab repo list` output, which is a bare array in
+/// some glab versions and `{ "repositories": [...] }` in others
+fn repository_entries(json: Value) -> Result<Vec<Value>> {
+    match json {
+        Value::Array(repos) => Ok(repos),
+        Value::Object(mut object) => match object.remove("repositories") {
+            Some(Value::Array(repos)) => Ok(repos),
+            _ => anyhow::bail!("Unexpected glab JSON output: no repositories array"),
+        },
+        _ => anyhow::bail!("Unexpected glab JSON output: expected an array or object"),
+    }
+}
+
+/// When the repository last saw activity, from `last_activity_at`
+fn last_activity(repo_json: &Value) -> Option<DateTime<Utc>> {
+    repo_json["last_activity_at"]
+        .as_str()
+        .and_then(parse_gitlab_timestamp)
+}
+
+/// Host glab talks to when the output carries no URL
+fn default_gitlab_host() -> String {
+    std::env::var("GITLAB_HOST")
+        .ok()
+        .map(|host| {
+            host.trim_start_matches("https://")
+                .trim_start_matches("http://")
+                .trim_end_matches('/')
+                .to_string()
+        })
+        .filter(|host| !host.is_empty())
+        .unwrap_or_else(|| "gitlab.com".to_string())
+}
+
 /// Parse GitLab timestamp format
 fn parse_gitlab_timestamp(timestamp_str: &str) -> Option<DateTime<Utc>> {
     // GitLab uses ISO 8601 format: "2024-01-15T10:30:00.000Z"
@@ -168,7 +223,13 @@ mod tests {
 
     #[test]
     fn test_get_clone_path() {
-        let path = GitLabScanner::get_clone_path("testuser", "my-project");
+        let path = GitLabScanner::get_clone_path(
+            &Config::default(),
+            "gitlab.com",
+            "testuser",
+            "my-project",
+        )
+        .unwrap();
         let path_str = path.to_string_lossy();
 
         assert!(path_str.contains("gitlab"));
@@ -212,7 +273,9 @@ mod tests {
             "archived": false
         });
 
-        let project = GitLabScanner::repository_to_project(&repo_json, "testuser").unwrap();
+        let project =
+            GitLabScanner::repository_to_project(&repo_json, "testuser", &Config::default())
+                .unwrap();
 
         assert_eq!(project.name, "test-project");
         assert_eq!(project.source, crate::models::ProjectSource::GitLab);
@@ -232,12 +295,95 @@ mod tests {
             "archived": false
         });
 
-        let project = GitLabScanner::repository_to_project(&repo_json, "testuser").unwrap();
+        let project =
+            GitLabScanner::repository_to_project(&repo_json, "testuser", &Config::default())
+                .unwrap();
 
         assert_eq!(project.name, "test-project");
         assert!(project.last_modified.is_some()); // Should use current time
     }
 
+    #[test]
+    fn test_active_window_uses_last_activity() {
+        let now = Utc::now();
+        let repo = |days_ago: Option<i64>| match days_ago {
+            Some(days) => serde_json::json!({
+                "last_activity_at": (now - chrono::Duration::days(days)).to_rfc3339()
+            }),
+            None => serde_json::json!({}),
+        };
+        let config = Config {
+            github_active_within_days: Some(14),
+            ..Config::default()
+        };
+
+        let active: Vec<_> = [Some(1), Some(13), Some(15), Some(400), None]
+            .into_iter()
+            .map(|days_ago| {
+                crate::scanner::is_recently_active(last_activity(&repo(days_ago)), &config, now)
+            })
+            .collect();
+        assert_eq!(active, vec![true, true, false, false, false]);
+
+        assert!(crate::scanner::is_recently_active(
+            last_activity(&repo(None)),
+            &Config::default(),
+            now
+        ));
+    }
+
+    #[test]
+    fn test_repository_entries_bare_array() {
+        let json = serde_json::json!([
+            {
+                "name": "api",
+                "web_url": "https://gitlab.example.com/testuser/api",
+                "last_activity_at": "2024-01-15T10:30:00.000Z",
+                "archived": false
+            }
+        ]);
+
+        let repos = repository_entries(json).unwrap();
+        assert_eq!(repos.len(), 1);
+
+        let project =
+            GitLabScanner::repository_to_project(&repos[0], "testuser", &Config::default())
+                .unwrap();
+        assert_eq!(project.name, "api");
+        assert_eq!(
+            project.gitlab_url.as_deref(),
+            Some("https://gitlab.example.com/testuser/api")
+        );
+    }
+
+    #[test]
+    fn test_repository_entries_wrapped_with_path_only() {
+        let json = serde_json::json!({
+            "repositories": [
+                { "path_with_namespace": "group/sub/service", "archived": false }
+            ]
+        });
+
+        let repos = repository_entries(json).unwrap();
+        assert_eq!(repos.len(), 1);
+
+        let project =
+            GitLabScanner::repository_to_project(&repos[0], "testuser", &Config::default())
+                .unwrap();
+        assert_eq!(project.name, "service");
+        assert!(project
+            .gitlab_url
+            .as_deref()
+            .unwrap()
+            .ends_with("/group/sub/service"));
+    }
+
+    #[test]
+    fn test_repository_entries_rejects_other_shapes() {
+        assert!(repository_entries(serde_json::json!({ "message": "401" })).is_err());
+        assert!(repository_entries(serde_json::json!("text")).is_err());
+    }
+
     #[test]
     fn test_is_glab_installed_function() {
         // This test will pass if glab is installed, fail otherwise
@@ -253,6 +399,7 @@ mod tests {
             github_username: None,
             gitlab_username: None,
             cache_ttl_seconds: 1800,
+            ..Config::default()
         };
 
         let scanner = GitLabScanner;
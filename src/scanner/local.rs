@@ -1,35 +1,58 @@
-use anyhow::{Context, Result};
+use anyhow::Result;
 use chrono::{DateTime, Utc};
 use git2::Repository;
-use ignore::WalkBuilder;
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+use ignore::{DirEntry, WalkBuilder};
 use rayon::prelude::*;
+use std::collections::HashMap;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::time::Instant;
 
-use crate::config::Config;
-use crate::models::{Project, ProjectList};
+use crate::config::{Config, DedupPolicy};
+use crate::models::{Project, ProjectList, ProjectSource};
 use crate::scanner::ProjectScanner;
 
+/// Scans the configured roots, reading every repo's commit history
 pub struct LocalScanner;
 
 impl ProjectScanner for LocalScanner {
     fn scan(&self, config: &Config) -> Result<ProjectList> {
-        let all_projects: Result<Vec<_>> = config
-            .project_dirs
-            .par_iter()
-            .map(|dir| scan_directory(dir))
-            .collect();
+        self.scan_verbose(config, false)
+    }
 
-        let mut project_list = ProjectList::new();
-        for projects in all_projects? {
-            for project in projects {
-                project_list.add_project(project);
-            }
-        }
+    fn scan_verbose(&self, config: &Config, verbose: bool) -> Result<ProjectList> {
+        let options = LocalScanOptions {
+            verbose,
+            ..LocalScanOptions::from_config(config)
+        };
+        scan_local(config, &options)
+    }
+
+    fn scanner_name(&self) -> &'static str {
+        "local"
+    }
+}
+
+/// Like `LocalScanner`, but with `reuse_git_timestamps` it keeps the cached
+/// timestamp of repos unchanged since the previous scan in the user's cache
+pub struct IncrementalLocalScanner;
+
+impl ProjectScanner for IncrementalLocalScanner {
+    fn scan(&self, config: &Config) -> Result<ProjectList> {
+        self.scan_verbose(config, false)
+    }
 
-        project_list.sort_by_last_modified();
-        Ok(project_list)
+    fn scan_verbose(&self, config: &Config, verbose: bool) -> Result<ProjectList> {
+        if !reuses_timestamps(config) {
+            return LocalScanner.scan_verbose(config, verbose);
+        }
+        let options = LocalScanOptions {
+            previous: PreviousScan::load(config),
+            verbose,
+            ..LocalScanOptions::from_config(config)
+        };
+        scan_local(config, &options)
     }
 
     fn scanner_name(&self) -> &'static str {
@@ -37,22 +60,272 @@ impl ProjectScanner for LocalScanner {
     }
 }
 
-fn scan_directory(base_dir: &Path) -> Result<Vec<Project>> {
+/// Every project under the config's scan roots, most recent first
+fn scan_local(config: &Config, options: &LocalScanOptions) -> Result<ProjectList> {
+    let per_root = scan_each_root(config, options)?;
+
+    if options.verbose {
+        println!(
+            "Projects per root: {}",
+            root_counts_summary(&per_root, dirs::home_dir().as_deref())
+        );
+    }
+
+    let mut project_list = ProjectList::new();
+    for (_, projects) in per_root {
+        for project in projects {
+            project_list.add_project(project);
+        }
+    }
+
+    project_list.sort_by_last_modified();
+    Ok(project_list)
+}
+
+/// Whether to reuse cached timestamps. With remote metadata preferred, the
+/// cached timestamp of a local project may be the remote's rather than its
+/// own last commit.
+fn reuses_timestamps(config: &Config) -> bool {
+    config.reuse_git_timestamps && config.dedup_policy != DedupPolicy::PreferRemoteMetadata
+}
+
+/// How `scan_directory` walks a root and builds its projects. The default
+/// uses the default config's depth and markers, archives nothing by pattern
+/// and has no previous scan to reuse.
+struct LocalScanOptions {
+    /// Levels below the root to descend: the root itself is depth 0, so 1
+    /// means the root and its immediate children only
+    max_depth: usize,
+    /// A directory holding one of these is a project
+    markers: Vec<String>,
+    /// Projects whose path below the root matches are marked archived
+    archive_patterns: GlobSet,
+    /// Remote to use for repos that have several
+    preferred_remote: Option<String>,
+    /// Timestamps of repos that haven't changed since the last scan
+    previous: PreviousScan,
+    verbose: bool,
+}
+
+impl LocalScanOptions {
+    fn from_config(config: &Config) -> Self {
+        Self {
+            max_depth: config.scan_depth,
+            markers: config.project_markers.clone(),
+            archive_patterns: archive_matcher(&config.archive_patterns),
+            preferred_remote: config.preferred_remote.clone(),
+            previous: PreviousScan::default(),
+            verbose: false,
+        }
+    }
+}
+
+impl Default for LocalScanOptions {
+    fn default() -> Self {
+        let config = Config::default();
+        Self {
+            max_depth: config.scan_depth,
+            markers: config.project_markers,
+            archive_patterns: GlobSet::empty(),
+            preferred_remote: None,
+            previous: PreviousScan::default(),
+            verbose: false,
+        }
+    }
+}
+
+/// Timestamps from the previous local scan, reused for repos that haven't
+/// changed since it ran
+#[derive(Debug, Default)]
+struct PreviousScan {
+    scanned_at: Option<DateTime<Utc>>,
+    timestamps: HashMap<PathBuf, DateTime<Utc>>,
+}
+
+impl PreviousScan {
+    /// When the last local scan started, per the scan metadata, and the
+    /// timestamps of the local projects it found
+    fn load(config: &Config) -> Self {
+        let Ok(cache) = crate::cache::Cache::new(config) else {
+            return Self::default();
+        };
+        let scanned_at = cache
+            .scan_metadata()
+            .ok()
+            .and_then(|metadata| metadata.sources.get("local").map(|scan| scan.scanned_at));
+        let timestamps = cache
+            .load_stale_projects()
+            .ok()
+            .flatten()
+            .map(|cached| {
+                cached
+                    .projects()
+                    .iter()
+                    .filter(|project| project.source == ProjectSource::Local)
+                    .filter_map(|project| Some((project.path.clone(), project.last_modified?)))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Self {
+            scanned_at,
+            timestamps,
+        }
+    }
+
+    /// The cached timestamp for `path` when neither it nor its `.git` was
+    /// modified after the previous scan. Commits rewrite files directly in
+    /// `.git`, so they show up there even when `path` itself is untouched.
+    fn unchanged_timestamp(&self, path: &Path) -> Option<DateTime<Utc>> {
+        let scanned_at = self.scanned_at?;
+        let cached = *self.timestamps.get(path)?;
+        let changed_at = [path.to_path_buf(), path.join(".git")]
+            .iter()
+            .filter_map(|dir| fs::metadata(dir).ok()?.modified().ok())
+            .map(DateTime::<Utc>::from)
+            .max()?;
+        (changed_at < scanned_at).then_some(cached)
+    }
+}
+
+/// Projects found under each of the config's scan roots, in root order
+fn scan_each_root(
+    config: &Config,
+    options: &LocalScanOptions,
+) -> Result<Vec<(PathBuf, Vec<Project>)>> {
+    config
+        .scan_roots()
+        .into_par_iter()
+        .map(|root| {
+            let projects = scan_directory(&root, options)?;
+            Ok((root, projects))
+        })
+        .collect()
+}
+
+/// One `root: count` entry per scan root, e.g. `~/code: 12, ~/work: 0`
+fn root_counts_summary(per_root: &[(PathBuf, Vec<Project>)], home: Option<&Path>) -> String {
+    per_root
+        .iter()
+        .map(|(root, projects)| {
+            format!(
+                "{}: {}",
+                crate::models::abbreviate_home(root, home),
+                projects.len()
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// A directory holding this file is skipped during scans, along with
+/// everything below it
+const IGNORE_FILE: &str = ".sw-ignore";
+
+/// A project holding this file is marked archived
+const ARCHIVE_MARKER: &str = "ARCHIVED";
+
+/// Matcher for `archive_patterns`; invalid globs are skipped with a warning
+fn archive_matcher(patterns: &[String]) -> GlobSet {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        match GlobBuilder::new(pattern).literal_separator(true).build() {
+            Ok(glob) => {
+                builder.add(glob);
+            }
+            Err(e) => eprintln!(
+                "Warning: Invalid glob in archive_patterns '{}': {}",
+                pattern, e
+            ),
+        }
+    }
+    builder.build().unwrap_or_else(|_| GlobSet::empty())
+}
+
+/// Whether the project at `path` holds an [`ARCHIVE_MARKER`] or its path
+/// below the scan root `base_dir` matches `patterns`. Where the root itself
+/// lives doesn't count, so a root under some `archive` directory isn't
+/// archived wholesale.
+fn is_archived(path: &Path, base_dir: &Path, patterns: &GlobSet) -> bool {
+    path.join(ARCHIVE_MARKER).exists()
+        || path
+            .strip_prefix(base_dir)
+            .is_ok_and(|relative| patterns.is_match(relative))
+}
+
+/// Collect projects under `base_dir`, down to `options.max_depth`. A
+/// directory is a project when it holds one of the markers, unless it or a
+/// directory above it holds an [`IGNORE_FILE`]. Projects matching the
+/// archive patterns or holding an [`ARCHIVE_MARKER`] are marked archived.
+fn scan_directory(base_dir: &Path, options: &LocalScanOptions) -> Result<Vec<Project>> {
     if !base_dir.exists() {
         return Ok(vec![]);
     }
 
-    let mut potential_projects = Vec::new();
-
     let walker = WalkBuilder::new(base_dir)
-        .max_depth(Some(3))
+        .max_depth(Some(options.max_depth))
         .hidden(false)
         .ignore(false)
         .git_ignore(false)
+        .filter_entry(|entry| {
+            !(entry.file_type().is_some_and(|ft| ft.is_dir())
+                && entry.path().join(IGNORE_FILE).exists())
+        })
         .build();
 
-    for entry in walker {
-        let entry = entry.context("Failed to read directory entry")?;
+    let potential_projects =
+        collect_project_candidates(walker, base_dir, &options.markers, options.verbose);
+
+    let projects: Vec<Project> = potential_projects
+        .into_par_iter()
+        .map(|(name, path)| {
+            let mut project = Project::new_local(name, path.clone());
+
+            if let Some(timestamp) = options
+                .previous
+                .unchanged_timestamp(&path)
+                .or_else(|| get_project_timestamp_fast(&path))
+            {
+                project = project.with_last_modified(timestamp);
+            }
+
+            project
+                .with_remote_url(remote_url(&path, options.preferred_remote.as_deref()).as_deref())
+                .with_project_type(super::detect_project_type(&path))
+                .with_archived(is_archived(&path, base_dir, &options.archive_patterns))
+        })
+        .collect();
+
+    Ok(projects)
+}
+
+/// Walk entries and collect project directories. Unreadable entries (e.g.
+/// permission denied) are skipped so they don't abort the whole root.
+fn collect_project_candidates<I>(
+    entries: I,
+    base_dir: &Path,
+    markers: &[String],
+    verbose: bool,
+) -> Vec<(String, PathBuf)>
+where
+    I: IntoIterator<Item = std::result::Result<DirEntry, ignore::Error>>,
+{
+    let mut potential_projects = Vec::new();
+
+    for entry in entries {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(e) => {
+                if verbose {
+                    eprintln!(
+                        "Warning: Skipping unreadable entry under {}: {}",
+                        base_dir.display(),
+                        e
+                    );
+                }
+                continue;
+            }
+        };
         let path = entry.path();
 
         if !entry.file_type().is_some_and(|ft| ft.is_dir()) {
@@ -63,7 +336,7 @@ fn scan_directory(base_dir: &Path) -> Result<Vec<Project>> {
             continue;
         }
 
-        if is_project_directory(path) {
+        if is_project_directory(path, markers) {
             let project_name = path
                 .file_name()
                 .and_then(|n| n.to_str())
@@ -74,20 +347,7 @@ fn scan_directory(base_dir: &Path) -> Result<Vec<Project>> {
         }
     }
 
-    let projects: Vec<Project> = potential_projects
-        .into_par_iter()
-        .map(|(name, path)| {
-            let mut project = Project::new_local(name, path.clone());
-
-            if let Some(timestamp) = get_project_timestamp_fast(&path) {
-                project = project.with_last_modified(timestamp);
-            }
-
-            project
-        })
-        .collect();
-
-    Ok(projects)
+    potential_projects
 }
 
 fn is_hidden_directory(path: &Path) -> bool {
@@ -96,12 +356,8 @@ fn is_hidden_directory(path: &Path) -> bool {
         .is_some_and(|name| name.starts_with('.'))
 }
 
-fn is_project_directory(path: &Path) -> bool {
-    has_git_directory(path)
-}
-
-fn has_git_directory(path: &Path) -> bool {
-    path.join(".git").exists()
+fn is_project_directory(path: &Path, markers: &[String]) -> bool {
+    markers.iter().any(|marker| path.join(marker).exists())
 }
 
 fn get_project_timestamp_fast(path: &Path) -> Option<DateTime<Utc>> {
@@ -133,6 +389,13 @@ fn get_git_last_commit_time_fast(path: &Path, timeout_ms: u64) -> Option<DateTim
         return None;
     }
 
+    // A freshly initialised repository has an unborn HEAD with no commit
+    // to date it by; the caller falls back to the directory mtime
+    if repo.is_empty().unwrap_or(true) {
+        return None;
+    }
+
+    // Detached HEADs resolve straight to a commit, branches peel to theirs
     let head = repo.head().ok()?;
     let commit = head.peel_to_commit().ok()?;
     let timestamp = commit.time();
@@ -140,6 +403,59 @@ fn get_git_last_commit_time_fast(path: &Path, timeout_ms: u64) -> Option<DateTim
     DateTime::from_timestamp(timestamp.seconds(), 0)
 }
 
+/// Whether the repository at `path` has uncommitted changes, including
+/// untracked files; `None` when it isn't a readable git repository
+pub fn working_tree_dirty(path: &Path) -> Option<bool> {
+    let repo = Repository::open(path).ok()?;
+    let mut options = git2::StatusOptions::new();
+    options
+        .include_untracked(true)
+        .recurse_untracked_dirs(false)
+        .exclude_submodules(true);
+
+    let statuses = repo.statuses(Some(&mut options)).ok()?;
+    Some(!statuses.is_empty())
+}
+
+/// Fill in `dirty` for every project, checking repositories in parallel
+pub fn populate_git_status(project_list: &mut ProjectList) {
+    project_list
+        .projects_mut()
+        .par_iter_mut()
+        .for_each(|project| project.dirty = working_tree_dirty(&project.path));
+}
+
+/// URL of the remote a repo is known by, see [`pick_remote`]
+fn remote_url(path: &Path, preferred: Option<&str>) -> Option<String> {
+    let repo = Repository::open(path).ok()?;
+    let names = repo.remotes().ok()?;
+    let remotes: Vec<(String, String)> = names
+        .iter()
+        .flatten()
+        .filter_map(|name| {
+            let remote = repo.find_remote(name).ok()?;
+            Some((name.to_string(), remote.url()?.to_string()))
+        })
+        .collect();
+    pick_remote(&remotes, preferred).map(str::to_string)
+}
+
+/// Choose among `(name, url)` remotes: `preferred` if the repo has it, then
+/// `origin`, then the first GitHub or GitLab remote, then the first at all
+fn pick_remote<'a>(remotes: &'a [(String, String)], preferred: Option<&str>) -> Option<&'a str> {
+    let named = |name: &str| remotes.iter().find(|(remote, _)| remote == name);
+    let on_forge = |url: &str| {
+        crate::models::remote_host(url)
+            .is_some_and(|host| host.contains("github") || host.contains("gitlab"))
+    };
+    preferred
+        .and_then(named)
+        .or_else(|| named("origin"))
+        .or_else(|| remotes.iter().find(|(_, url)| on_forge(url)))
+        .or_else(|| remotes.first())
+        .map(|(_, url)| url.as_str())
+}
+
 fn get_directory_modified_time(path: &Path) -> Option<DateTime<Utc>> {
     let metadata = fs::metadata(path).ok()?;
     let modified = metadata.modified().ok()?;
@@ -156,11 +472,14 @@ fn get_directory_modified_time(path: &Path) -> Option<DateTime<Utc>> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::models::ProjectSource;
+    use chrono::TimeZone;
     use std::fs;
-    use std::path::PathBuf;
     use tempfile::TempDir;
 
+    fn markers() -> Vec<String> {
+        Config::default().project_markers
+    }
+
     fn create_test_project(base_dir: &Path, name: &str, project_file: &str) -> PathBuf {
         let project_dir = base_dir.join(name);
         fs::create_dir_all(&project_dir).unwrap();
@@ -201,17 +520,242 @@ mod tests {
         let temp_dir = TempDir::new().unwrap();
 
         let rust_project = create_test_project(temp_dir.path(), "rust-project", "Cargo.toml");
-        assert!(is_project_directory(&rust_project));
+        assert!(is_project_directory(&rust_project, &markers()));
 
         let node_project = create_test_project(temp_dir.path(), "node-project", "package.json");
-        assert!(is_project_directory(&node_project));
+        assert!(is_project_directory(&node_project, &markers()));
 
         let git_project = create_git_project(temp_dir.path(), "git-project");
-        assert!(is_project_directory(&git_project));
+        assert!(is_project_directory(&git_project, &markers()));
 
         let empty_dir = temp_dir.path().join("empty");
         fs::create_dir_all(&empty_dir).unwrap();
-        assert!(!is_project_directory(&empty_dir));
+        assert!(!is_project_directory(&empty_dir, &markers()));
+    }
+
+    #[test]
+    fn test_nix_only_directory_is_a_project() {
+        let temp_dir = TempDir::new().unwrap();
+        let flake_dir = temp_dir.path().join("dev-shell");
+        fs::create_dir_all(&flake_dir).unwrap();
+        fs::write(flake_dir.join("flake.nix"), "{ }").unwrap();
+        let direnv_dir = temp_dir.path().join("env-only");
+        fs::create_dir_all(&direnv_dir).unwrap();
+        fs::write(direnv_dir.join(".envrc"), "use flake").unwrap();
+
+        assert!(is_project_directory(&flake_dir, &markers()));
+        assert!(!is_project_directory(&flake_dir, &[".git".to_string()]));
+
+        let mut projects = scan_directory(
+            temp_dir.path(),
+            &LocalScanOptions {
+                max_depth: 1,
+                ..LocalScanOptions::default()
+            },
+        )
+        .unwrap();
+        projects.sort_by(|a, b| a.name.cmp(&b.name));
+        let types: Vec<_> = projects
+            .iter()
+            .map(|project| (project.name.as_str(), project.project_type.as_deref()))
+            .collect();
+        assert_eq!(
+            types,
+            vec![("dev-shell", Some("nix")), ("env-only", Some("direnv"))]
+        );
+    }
+
+    #[test]
+    fn test_origin_host_from_ssh_remote() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_dir = create_git_project(temp_dir.path(), "remote-project");
+        assert_eq!(remote_url(&project_dir, None), None);
+
+        Repository::open(&project_dir)
+            .unwrap()
+            .remote("origin", "git@gitlab.corp.example:team/remote-project.git")
+            .unwrap();
+
+        let projects = scan_directory(
+            temp_dir.path(),
+            &LocalScanOptions {
+                max_depth: 1,
+                ..LocalScanOptions::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(projects[0].host.as_deref(), Some("gitlab.corp.example"));
+        assert_eq!(
+            projects[0].gitlab_url.as_deref(),
+            Some("https://gitlab.corp.example/team/remote-project")
+        );
+    }
+
+    #[test]
+    fn test_scan_picks_preferred_remote() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_dir = create_git_project(temp_dir.path(), "mirrored");
+        let repo = Repository::open(&project_dir).unwrap();
+        repo.remote("origin", "git@gitlab.com:team/mirrored.git")
+            .unwrap();
+        repo.remote("upstream", "https://github.com/team/mirrored.git")
+            .unwrap();
+
+        let projects = scan_directory(
+            temp_dir.path(),
+            &LocalScanOptions {
+                max_depth: 1,
+                ..LocalScanOptions::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(projects[0].host.as_deref(), Some("gitlab.com"));
+        assert_eq!(
+            projects[0].gitlab_url.as_deref(),
+            Some("https://gitlab.com/team/mirrored")
+        );
+        assert_eq!(projects[0].github_url, None);
+
+        let projects = scan_directory(
+            temp_dir.path(),
+            &LocalScanOptions {
+                max_depth: 1,
+                preferred_remote: Some("upstream".to_string()),
+                ..LocalScanOptions::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(projects[0].host.as_deref(), Some("github.com"));
+        assert_eq!(
+            projects[0].github_url.as_deref(),
+            Some("https://github.com/team/mirrored")
+        );
+        assert_eq!(projects[0].gitlab_url, None);
+    }
+
+    #[test]
+    fn test_pick_remote_precedence() {
+        let remotes = |pairs: &[(&str, &str)]| -> Vec<(String, String)> {
+            pairs
+                .iter()
+                .map(|(name, url)| (name.to_string(), url.to_string()))
+                .collect()
+        };
+        let without_origin = remotes(&[
+            ("backup", "git@bitbucket.org:team/repo.git"),
+            ("hub", "git@github.com:team/repo.git"),
+        ]);
+        assert_eq!(
+            pick_remote(&without_origin, None),
+            Some("git@github.com:team/repo.git")
+        );
+        // A preferred name the repo lacks falls back to the defaults
+        assert_eq!(
+            pick_remote(&without_origin, Some("origin")),
+            Some("git@github.com:team/repo.git")
+        );
+        let plain = remotes(&[("backup", "/srv/git/repo.git")]);
+        assert_eq!(pick_remote(&plain, None), Some("/srv/git/repo.git"));
+        assert_eq!(pick_remote(&[], None), None);
+    }
+
+    #[test]
+    fn test_scan_detects_project_type() {
+        let temp_dir = TempDir::new().unwrap();
+        create_test_project(temp_dir.path(), "rust-project", "Cargo.toml");
+        create_test_project(temp_dir.path(), "node-project", "package.json");
+        create_git_project(temp_dir.path(), "plain-project");
+
+        let projects = scan_directory(
+            temp_dir.path(),
+            &LocalScanOptions {
+                max_depth: 1,
+                ..LocalScanOptions::default()
+            },
+        )
+        .unwrap();
+        let type_of = |name: &str| {
+            projects
+                .iter()
+                .find(|p| p.name == name)
+                .unwrap()
+                .project_type
+                .clone()
+        };
+
+        assert_eq!(type_of("rust-project").as_deref(), Some("rust"));
+        assert_eq!(type_of("node-project").as_deref(), Some("node"));
+        assert_eq!(type_of("plain-project"), None);
+    }
+
+    fn commit_readme(repo: &Repository, seconds: i64) -> git2::Oid {
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new("README.md")).unwrap();
+        index.write().unwrap();
+        let tree_id = index.write_tree().unwrap();
+        let tree = repo.find_tree(tree_id).unwrap();
+        let signature =
+            git2::Signature::new("sw", "sw@example.com", &git2::Time::new(seconds, 0)).unwrap();
+
+        repo.commit(Some("HEAD"), &signature, &signature, "init", &tree, &[])
+            .unwrap()
+    }
+
+    #[test]
+    fn test_working_tree_dirty() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_dir = create_git_project(temp_dir.path(), "status");
+        let repo = Repository::open(&project_dir).unwrap();
+        commit_readme(&repo, 1_700_000_000);
+
+        assert_eq!(working_tree_dirty(&project_dir), Some(false));
+
+        fs::write(project_dir.join("notes.txt"), "todo").unwrap();
+        assert_eq!(working_tree_dirty(&project_dir), Some(true));
+
+        let plain_dir = temp_dir.path().join("plain");
+        fs::create_dir_all(&plain_dir).unwrap();
+        assert_eq!(working_tree_dirty(&plain_dir), None);
+    }
+
+    #[test]
+    fn test_repo_without_commits_uses_directory_mtime() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_dir = create_git_project(temp_dir.path(), "never-committed");
+
+        assert_eq!(get_git_last_commit_time_fast(&project_dir, 10_000), None);
+
+        let projects = scan_directory(
+            temp_dir.path(),
+            &LocalScanOptions {
+                max_depth: 1,
+                ..LocalScanOptions::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(projects.len(), 1);
+        assert_eq!(projects[0].name, "never-committed");
+        assert_eq!(
+            projects[0].last_modified,
+            get_directory_modified_time(&project_dir)
+        );
+    }
+
+    #[test]
+    fn test_detached_head_uses_commit_time() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_dir = create_git_project(temp_dir.path(), "detached");
+        let repo = Repository::open(&project_dir).unwrap();
+
+        let commit_time = 1_700_000_000;
+        let oid = commit_readme(&repo, commit_time);
+        repo.set_head_detached(oid).unwrap();
+        assert!(repo.head_detached().unwrap());
+
+        assert_eq!(
+            get_git_last_commit_time_fast(&project_dir, 10_000),
+            DateTime::from_timestamp(commit_time, 0)
+        );
     }
 
     #[test]
@@ -242,7 +786,7 @@ mod tests {
         let empty_dir = temp_dir.path().join("empty");
         fs::create_dir_all(&empty_dir).unwrap();
 
-        let projects = scan_directory(temp_dir.path()).unwrap();
+        let projects = scan_directory(temp_dir.path(), &LocalScanOptions::default()).unwrap();
 
         assert_eq!(projects.len(), 3);
 
@@ -256,6 +800,140 @@ mod tests {
         assert!(projects.iter().all(|p| p.source == ProjectSource::Local));
     }
 
+    #[test]
+    fn test_scan_marks_archived_projects() {
+        let temp_dir = TempDir::new().unwrap();
+        create_git_project(temp_dir.path(), "active");
+        create_git_project(temp_dir.path(), "finished");
+        fs::write(temp_dir.path().join("finished").join(ARCHIVE_MARKER), "").unwrap();
+        create_git_project(&temp_dir.path().join("archive"), "moved");
+
+        let archived_under = |root: &Path, patterns: &[String]| {
+            let mut archived: Vec<String> = scan_directory(
+                root,
+                &LocalScanOptions {
+                    max_depth: 2,
+                    archive_patterns: archive_matcher(patterns),
+                    ..LocalScanOptions::default()
+                },
+            )
+            .unwrap()
+            .into_iter()
+            .filter(|project| project.archived)
+            .map(|project| project.name)
+            .collect();
+            archived.sort();
+            archived
+        };
+        let archived = |patterns: &[String]| archived_under(temp_dir.path(), patterns);
+
+        assert_eq!(
+            archived(&Config::default().archive_patterns),
+            vec!["finished", "moved"]
+        );
+        assert_eq!(archived(&[]), vec!["finished"]);
+        // Only the part below the scan root is matched
+        assert!(archived_under(
+            &temp_dir.path().join("archive"),
+            &Config::default().archive_patterns
+        )
+        .is_empty());
+        assert_eq!(archived(&["*".to_string()]), vec!["active", "finished"]);
+    }
+
+    #[test]
+    fn test_scan_skips_subtrees_with_sw_ignore() {
+        let temp_dir = TempDir::new().unwrap();
+
+        let archive = temp_dir.path().join("archive");
+        create_git_project(&archive, "old-app");
+        create_test_project(&archive.join("nested"), "old-lib", "Cargo.toml");
+        fs::write(archive.join(IGNORE_FILE), "").unwrap();
+
+        // A marked project directory is skipped itself
+        let vendored = create_git_project(temp_dir.path(), "vendored");
+        fs::write(vendored.join(IGNORE_FILE), "").unwrap();
+
+        create_git_project(&temp_dir.path().join("work"), "api");
+        create_git_project(temp_dir.path(), "site");
+
+        let mut names: Vec<String> = scan_directory(
+            temp_dir.path(),
+            &LocalScanOptions {
+                max_depth: 4,
+                ..LocalScanOptions::default()
+            },
+        )
+        .unwrap()
+        .into_iter()
+        .map(|project| project.name)
+        .collect();
+        names.sort();
+        assert_eq!(names, vec!["api", "site"]);
+    }
+
+    #[test]
+    fn test_scan_reuses_timestamps_of_unchanged_repos() {
+        let temp_dir = TempDir::new().unwrap();
+        let unchanged = create_git_project(temp_dir.path(), "unchanged");
+        let touched = create_git_project(temp_dir.path(), "touched");
+
+        let cached_at = Utc.with_ymd_and_hms(2001, 2, 3, 4, 5, 6).unwrap();
+        let previous = PreviousScan {
+            scanned_at: Some(Utc::now()),
+            timestamps: HashMap::from([(unchanged, cached_at), (touched.clone(), cached_at)]),
+        };
+
+        // Past any coarse mtime granularity, then change one repo's `.git`
+        std::thread::sleep(std::time::Duration::from_millis(1100));
+        fs::write(touched.join(".git").join("COMMIT_EDITMSG"), "wip").unwrap();
+
+        let projects = scan_directory(
+            temp_dir.path(),
+            &LocalScanOptions {
+                max_depth: 1,
+                previous,
+                ..LocalScanOptions::default()
+            },
+        )
+        .unwrap();
+        let timestamp = |name: &str| {
+            projects
+                .iter()
+                .find(|project| project.name == name)
+                .and_then(|project| project.last_modified)
+        };
+        assert_eq!(timestamp("unchanged"), Some(cached_at));
+        assert_ne!(timestamp("touched"), Some(cached_at));
+        assert!(timestamp("touched").is_some());
+
+        // Without a previous scan everything is read afresh
+        let projects = scan_directory(
+            temp_dir.path(),
+            &LocalScanOptions {
+                max_depth: 1,
+                ..LocalScanOptions::default()
+            },
+        )
+        .unwrap();
+        assert!(projects
+            .iter()
+            .all(|project| project.last_modified != Some(cached_at)));
+    }
+
+    #[test]
+    fn test_remote_metadata_disables_timestamp_reuse() {
+        assert!(reuses_timestamps(&Config::default()));
+        assert!(!reuses_timestamps(&Config {
+            dedup_policy: DedupPolicy::PreferRemoteMetadata,
+            ..Config::default()
+        }));
+        assert!(!reuses_timestamps(&Config {
+            reuse_git_timestamps: false,
+            ..Config::default()
+        }));
+    }
+
     #[test]
     fn test_local_scanner() {
         let temp_dir = TempDir::new().unwrap();
@@ -275,12 +953,63 @@ mod tests {
         assert_eq!(scanner.scanner_name(), "local");
     }
 
+    #[test]
+    fn test_local_scanner_covers_extra_scan_dirs() {
+        let temp_dir = TempDir::new().unwrap();
+        let code = temp_dir.path().join("code");
+        let scratch = temp_dir.path().join("scratch");
+        create_git_project(&code, "app");
+        create_git_project(&scratch, "experiment");
+
+        let config = Config {
+            project_dirs: vec![code],
+            extra_scan_dirs: vec![scratch],
+            ..Config::default()
+        };
+
+        let mut names: Vec<String> = LocalScanner
+            .scan(&config)
+            .unwrap()
+            .projects()
+            .iter()
+            .map(|project| project.name.clone())
+            .collect();
+        names.sort();
+        assert_eq!(names, vec!["app", "experiment"]);
+    }
+
+    #[test]
+    fn test_per_root_counts_include_empty_roots() {
+        let temp_dir = TempDir::new().unwrap();
+        let code = temp_dir.path().join("code");
+        let work = temp_dir.path().join("work");
+        fs::create_dir_all(&work).unwrap();
+        create_git_project(&code, "alpha");
+        create_git_project(&code, "beta");
+
+        let config = Config {
+            project_dirs: vec![code.clone(), work.clone()],
+            ..Config::default()
+        };
+        let per_root = scan_each_root(&config, &LocalScanOptions::from_config(&config)).unwrap();
+
+        let counts: Vec<_> = per_root
+            .iter()
+            .map(|(root, projects)| (root.clone(), projects.len()))
+            .collect();
+        assert_eq!(counts, vec![(code, 2), (work, 0)]);
+        assert_eq!(
+            root_counts_summary(&per_root, Some(temp_dir.path())),
+            "~/code: 2, ~/work: 0"
+        );
+    }
+
     #[test]
     fn test_scan_nonexistent_directory() {
         let temp_dir = TempDir::new().unwrap();
         let nonexistent = temp_dir.path().join("does-not-exist");
 
-        let projects = scan_directory(&nonexistent).unwrap();
+        let projects = scan_directory(&nonexistent, &LocalScanOptions::default()).unwrap();
         assert!(projects.is_empty());
     }
 
@@ -316,7 +1045,7 @@ mod tests {
         for file in &project_files {
             let project_dir = create_test_project(temp_dir.path(), &format!("test-{}", file), file);
             assert!(
-                is_project_directory(&project_dir),
+                is_project_directory(&project_dir, &markers()),
                 "Failed to detect Git repository with file: {}",
                 file
             );
@@ -325,6 +1054,60 @@ mod tests {
         let non_git_dir = temp_dir.path().join("not-a-git-repo");
         fs::create_dir_all(&non_git_dir).unwrap();
         fs::write(non_git_dir.join("Cargo.toml"), "").unwrap();
-        assert!(!is_project_directory(&non_git_dir));
+        assert!(!is_project_directory(&non_git_dir, &markers()));
+    }
+
+    #[test]
+    fn test_unreadable_entry_does_not_abort_scan() {
+        let temp_dir = TempDir::new().unwrap();
+
+        create_git_project(temp_dir.path(), "before");
+        create_git_project(temp_dir.path(), "after");
+
+        let walker = WalkBuilder::new(temp_dir.path()).max_depth(Some(3)).build();
+        let denied = ignore::Error::Io(std::io::Error::new(
+            std::io::ErrorKind::PermissionDenied,
+            "permission denied",
+        ));
+
+        let mut entries: Vec<_> = walker.collect();
+        entries.insert(1, Err(denied));
+
+        let candidates = collect_project_candidates(entries, temp_dir.path(), &markers(), false);
+        let names: Vec<&str> = candidates.iter().map(|(name, _)| name.as_str()).collect();
+
+        assert_eq!(candidates.len(), 2);
+        assert!(names.contains(&"before"));
+        assert!(names.contains(&"after"));
+    }
+
+    #[test]
+    fn test_scan_depth_one_skips_nested_repos() {
+        let temp_dir = TempDir::new().unwrap();
+
+        let top_level = create_git_project(temp_dir.path(), "top-level");
+        create_git_project(&top_level, "vendored");
+        let group = temp_dir.path().join("group");
+        fs::create_dir_all(&group).unwrap();
+        create_git_project(&group, "grouped");
+
+        let names = |depth| {
+            let mut names: Vec<String> = scan_directory(
+                temp_dir.path(),
+                &LocalScanOptions {
+                    max_depth: depth,
+                    ..LocalScanOptions::default()
+                },
+            )
+            .unwrap()
+            .into_iter()
+            .map(|project| project.name)
+            .collect();
+            names.sort();
+            names
+        };
+
+        assert_eq!(names(1), vec!["top-level"]);
+        assert_eq!(names(2), vec!["grouped", "top-level", "vendored"]);
     }
 }
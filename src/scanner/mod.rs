@@ -1,8 +1,13 @@
-use crate::config::Config;
+use crate::config::{Config, MultiClonePolicy};
 use crate::models::ProjectList;
-use anyhow::Result;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, RecvTimeoutError};
 use std::sync::Arc;
 use std::thread;
+use std::time::{Duration, Instant};
 
 pub mod cursor;
 pub mod github;
@@ -12,158 +17,364 @@ pub mod local;
 pub trait ProjectScanner: Send + Sync {
     fn scan(&self, config: &Config) -> Result<ProjectList>;
 
+    /// Scan with optional diagnostics on stderr. Scanners without extra
+    /// diagnostics fall back to `scan`.
+    fn scan_verbose(&self, config: &Config, verbose: bool) -> Result<ProjectList> {
+        let _ = verbose;
+        self.scan(config)
+    }
+
     fn scanner_name(&self) -> &'static str;
 }
 
+/// Marker files that identify a project's language, checked in order
+const PROJECT_TYPE_MARKERS: &[(&str, &str)] = &[
+    ("Cargo.toml", "rust"),
+    ("package.json", "node"),
+    ("pyproject.toml", "python"),
+    ("setup.py", "python"),
+    ("requirements.txt", "python"),
+    ("go.mod", "go"),
+    // Environment-only markers, used when no language marker is present
+    ("flake.nix", "nix"),
+    ("shell.nix", "nix"),
+    (".envrc", "direnv"),
+];
+
+/// Project type of a checked-out directory, from the marker files it holds
+pub fn detect_project_type(path: &Path) -> Option<String> {
+    PROJECT_TYPE_MARKERS
+        .iter()
+        .find(|(marker, _)| path.join(marker).is_file())
+        .map(|(_, project_type)| project_type.to_string())
+}
+
+/// Whether a remote repository last active at `last_activity` falls inside
+/// `github_active_within_days`. Without a window every repository passes;
+/// with one, repositories with no known activity are left out.
+pub fn is_recently_active(
+    last_activity: Option<DateTime<Utc>>,
+    config: &Config,
+    now: DateTime<Utc>,
+) -> bool {
+    let Some(days) = config.github_active_within_days else {
+        return true;
+    };
+    // A window reaching past the representable range excludes nothing
+    let Some(cutoff) = i64::try_from(days)
+        .ok()
+        .and_then(chrono::Duration::try_days)
+        .and_then(|window| now.checked_sub_signed(window))
+    else {
+        return true;
+    };
+    last_activity.is_some_and(|timestamp| timestamp >= cutoff)
+}
+
+/// Clone layout for GitHub repositories when `clone_path_template` is unset
+pub const GITHUB_CLONE_TEMPLATE: &str = "~/Documents/git/{repo}";
+/// Clone layout for GitLab repositories when `clone_path_template` is unset
+pub const GITLAB_CLONE_TEMPLATE: &str = "~/gitlab/{owner}/{repo}";
+
+/// Expand a clone path template. `{host}`, `{owner}` and `{repo}` are
+/// substituted; relative templates (and a leading `~`) resolve against the
+/// home directory, and the repo name is appended if `{repo}` is missing so
+/// repositories never share a directory.
+pub fn render_clone_path(template: &str, host: &str, owner: &str, repo: &str) -> Result<PathBuf> {
+    let rendered = template
+        .replace("{host}", host)
+        .replace("{owner}", owner)
+        .replace("{repo}", repo);
+
+    let relative = rendered
+        .strip_prefix("~/")
+        .or_else(|| rendered.strip_prefix('~'))
+        .unwrap_or(&rendered);
+    let mut path = PathBuf::from(relative);
+    if path.is_relative() {
+        let home = dirs::home_dir().context("Failed to get home directory")?;
+        path = home.join(path);
+    }
+
+    if !template.contains("{repo}") {
+        path.push(repo);
+    }
+
+    Ok(path)
+}
+
+/// Host and owner from a repository web URL such as
+/// `https://github.com/owner/repo`
+pub fn repo_url_parts(url: &str) -> Option<(&str, &str)> {
+    let without_scheme = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let segments: Vec<&str> = without_scheme
+        .trim_end_matches('/')
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .collect();
+
+    match segments.as_slice() {
+        [host, .., owner, _repo] => Some((host, owner)),
+        _ => None,
+    }
+}
+
+/// Outcome of a single scanner, reported as soon as it is known
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScanProgress {
+    Finished {
+        scanner: &'static str,
+        count: usize,
+    },
+    Failed {
+        scanner: &'static str,
+        error: String,
+    },
+    TimedOut {
+        scanner: &'static str,
+    },
+}
+
+impl std::fmt::Display for ScanProgress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ScanProgress::Finished { scanner, count } => write!(f, "✓ {} ({})", scanner, count),
+            ScanProgress::Failed { scanner, error } => write!(f, "✗ {}: {}", scanner, error),
+            ScanProgress::TimedOut { scanner } => write!(f, "⏱ {} timed out", scanner),
+        }
+    }
+}
+
+/// How one scanner fared in a profiled scan
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ScannerTiming {
+    pub scanner: &'static str,
+    /// `finished`, `failed` or `timed_out`
+    pub status: &'static str,
+    /// Projects found, when the scanner finished
+    pub projects: Option<usize>,
+    /// Time until the scanner reported back; unknown after a timeout
+    pub duration_ms: Option<f64>,
+}
+
+/// Timings of a full refresh, for `sw refresh --profile-json`
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ScanProfile {
+    pub scanners: Vec<ScannerTiming>,
+    /// Projects after merging every scanner's results
+    pub projects: usize,
+    pub cache_read_ms: f64,
+    pub cache_write_ms: f64,
+    pub total_ms: f64,
+}
+
+/// `duration` in (fractional) milliseconds
+pub fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+/// Indices of `names` sorted by their position in `configured`. Scanners the
+/// config doesn't mention keep their relative order after the listed ones.
+fn scanner_order<'a>(names: impl Iterator<Item = &'a str>, configured: &[String]) -> Vec<usize> {
+    let mut indexed: Vec<(usize, usize)> = names
+        .enumerate()
+        .map(|(index, name)| {
+            let rank = configured
+                .iter()
+                .position(|configured| configured == name)
+                .unwrap_or(configured.len());
+            (rank, index)
+        })
+        .collect();
+    indexed.sort();
+    indexed.into_iter().map(|(_, index)| index).collect()
+}
+
 pub struct ScanManager {
-    scanners: Vec<Box<dyn ProjectScanner + Send + Sync>>,
+    scanners: Vec<Arc<dyn ProjectScanner + Send + Sync>>,
+    /// Merge entries for the same path from different scanners
+    dedup: bool,
 }
 
 impl ScanManager {
     pub fn new() -> Self {
         Self {
             scanners: vec![
-                Box::new(local::LocalScanner),
-                Box::new(cursor::CursorScanner),
-                Box::new(github::GitHubScanner),
-                Box::new(gitlab::GitLabScanner),
+                Arc::new(local::IncrementalLocalScanner),
+                Arc::new(cursor::CursorScanner),
+                Arc::new(github::GitHubScanner),
+                Arc::new(gitlab::GitLabScanner),
             ],
+            dedup: true,
         }
     }
 
     #[cfg(test)]
     pub fn new_with_scanners(scanners: Vec<Box<dyn ProjectScanner + Send + Sync>>) -> Self {
-        Self { scanners }
+        Self {
+            scanners: scanners.into_iter().map(Arc::from).collect(),
+            dedup: true,
+        }
     }
 
-    pub fn scan_all_verbose(&self, config: &Config, verbose: bool) -> Result<ProjectList> {
-        let config = Arc::new(config.clone());
-        let mut handles = Vec::new();
-
-        let scanner_info: Vec<(String, String)> = self
-            .scanners
-            .iter()
-            .map(|scanner| {
-                (
-                    scanner.scanner_name().to_string(),
-                    scanner.scanner_name().to_string(),
-                )
-            })
-            .collect();
-
-        for (scanner_name, _) in scanner_info {
-            let config_clone = Arc::clone(&config);
-            let scanner_name_clone = scanner_name.clone();
+    /// Keep every scanner's entry for a path instead of merging them
+    pub fn keep_duplicates(mut self) -> Self {
+        self.dedup = false;
+        self
+    }
 
-            let handle = thread::spawn(move || {
-                let start_time = std::time::Instant::now();
+    /// Run all scanners in parallel and merge their projects, without
+    /// progress reports or timings
+    #[allow(dead_code)]
+    pub fn scan_all_verbose(&self, config: &Config, verbose: bool) -> Result<ProjectList> {
+        self.scan_all_profiled(config, verbose, |_| {})
+            .map(|(projects, _)| projects)
+    }
 
-                let result = match scanner_name_clone.as_str() {
-                    "local" => local::LocalScanner.scan(&config_clone),
-                    "cursor" => cursor::CursorScanner.scan(&config_clone),
-                    "github" => github::GitHubScanner.scan(&config_clone),
-                    "gitlab" => gitlab::GitLabScanner.scan(&config_clone),
-                    _ => Ok(ProjectList::new()),
-                };
+    /// Run all scanners in parallel, calling `on_progress` once per scanner
+    /// as each one finishes, fails or times out, and return the merged
+    /// projects with each scanner's timing in the configured order. A
+    /// scanner that hasn't reported back within `scanner_timeout_seconds` is
+    /// warned about and skipped; its thread is left to finish (or hang) on
+    /// its own.
+    pub fn scan_all_profiled(
+        &self,
+        config: &Config,
+        verbose: bool,
+        mut on_progress: impl FnMut(ScanProgress),
+    ) -> Result<(ProjectList, Vec<ScannerTiming>)> {
+        let config = Arc::new(config.clone());
+        let timeout = Duration::from_secs(config.scanner_timeout_seconds);
+        let (sender, receiver) = mpsc::channel();
 
-                let duration = start_time.elapsed();
-                (scanner_name_clone, result, duration)
+        let mut order = scanner_order(
+            self.scanners.iter().map(|scanner| scanner.scanner_name()),
+            &config.scanner_order,
+        );
+        order.retain(|&index| {
+            let name = self.scanners[index].scanner_name();
+            let disabled = config.is_scanner_disabled(name);
+            if disabled && verbose {
+                eprintln!("🔍 {} scanner: disabled in config", name);
+            }
+            !disabled
+        });
+
+        for &index in &order {
+            let scanner = Arc::clone(&self.scanners[index]);
+            let config = Arc::clone(&config);
+            let sender = sender.clone();
+
+            thread::spawn(move || {
+                let start_time = Instant::now();
+                let result = scanner.scan_verbose(&config, verbose);
+                // The collector may have given up on us already
+                let _ = sender.send((index, result, start_time.elapsed()));
             });
-
-            handles.push(handle);
         }
-
-        if self
-            .scanners
-            .iter()
-            .any(|s| !matches!(s.scanner_name(), "local" | "cursor" | "github" | "gitlab"))
-        {
-            return self.scan_all_sequential(&config, verbose);
-        }
-
-        let mut all_projects = ProjectList::new();
-
-        for handle in handles {
-            match handle.join() {
-                Ok((scanner_name, result, duration)) => match result {
-                    Ok(projects) => {
-                        let project_count = projects.len();
-
-                        for project in projects.projects() {
-                            all_projects.add_project(project.clone());
-                        }
-
-                        if verbose && (duration.as_millis() > 10 || project_count > 0) {
-                            eprintln!(
-                                "🔍 {} scanner: {} projects in {:.2?}",
-                                scanner_name, project_count, duration
-                            );
-                        }
-                    }
-                    Err(e) => {
-                        if verbose {
-                            eprintln!(
-                                "Warning: {} scanner failed in {:.2?}: {}",
-                                scanner_name, duration, e
-                            );
-                        } else {
-                            eprintln!("Warning: {} scanner failed: {}", scanner_name, e);
-                        }
-                    }
-                },
-                Err(_) => {
+        drop(sender);
+
+        let deadline = Instant::now() + timeout;
+        let mut results: Vec<Option<ProjectList>> = vec![None; self.scanners.len()];
+        let mut reported = vec![false; self.scanners.len()];
+        let mut timings: Vec<Option<ScannerTiming>> = vec![None; self.scanners.len()];
+
+        for _ in 0..order.len() {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            let (index, result, duration) = match receiver.recv_timeout(remaining) {
+                Ok(message) => message,
+                Err(RecvTimeoutError::Timeout) => break,
+                Err(RecvTimeoutError::Disconnected) => {
                     eprintln!("Warning: Scanner thread panicked");
+                    break;
                 }
-            }
-        }
-
-        all_projects.deduplicate();
-        all_projects.sort_by_last_modified();
-        Ok(all_projects)
-    }
+            };
+            reported[index] = true;
+            let scanner_name = self.scanners[index].scanner_name();
 
-    fn scan_all_sequential(&self, config: &Config, verbose: bool) -> Result<ProjectList> {
-        let mut all_projects = ProjectList::new();
-
-        for scanner in &self.scanners {
-            let scanner_start = std::time::Instant::now();
-            match scanner.scan(config) {
+            match result {
                 Ok(projects) => {
-                    let scanner_duration = scanner_start.elapsed();
                     let project_count = projects.len();
-
-                    for project in projects.projects() {
-                        all_projects.add_project(project.clone());
-                    }
-
-                    if verbose && (scanner_duration.as_millis() > 10 || project_count > 0) {
+                    if verbose && (duration.as_millis() > 10 || project_count > 0) {
                         eprintln!(
                             "🔍 {} scanner: {} projects in {:.2?}",
-                            scanner.scanner_name(),
-                            project_count,
-                            scanner_duration
+                            scanner_name, project_count, duration
                         );
                     }
+                    on_progress(ScanProgress::Finished {
+                        scanner: scanner_name,
+                        count: project_count,
+                    });
+                    timings[index] = Some(ScannerTiming {
+                        scanner: scanner_name,
+                        status: "finished",
+                        projects: Some(project_count),
+                        duration_ms: Some(millis(duration)),
+                    });
+                    results[index] = Some(projects);
                 }
                 Err(e) => {
-                    let scanner_duration = scanner_start.elapsed();
+                    on_progress(ScanProgress::Failed {
+                        scanner: scanner_name,
+                        error: e.to_string(),
+                    });
+                    timings[index] = Some(ScannerTiming {
+                        scanner: scanner_name,
+                        status: "failed",
+                        projects: None,
+                        duration_ms: Some(millis(duration)),
+                    });
                     if verbose {
                         eprintln!(
                             "Warning: {} scanner failed in {:.2?}: {}",
-                            scanner.scanner_name(),
-                            scanner_duration,
-                            e
+                            scanner_name, duration, e
                         );
                     } else {
-                        eprintln!("Warning: {} scanner failed: {}", scanner.scanner_name(), e);
+                        eprintln!("Warning: {} scanner failed: {}", scanner_name, e);
                     }
                 }
             }
         }
 
-        all_projects.deduplicate();
+        for &index in order.iter().filter(|&&index| !reported[index]) {
+            let scanner = &self.scanners[index];
+            on_progress(ScanProgress::TimedOut {
+                scanner: scanner.scanner_name(),
+            });
+            timings[index] = Some(ScannerTiming {
+                scanner: scanner.scanner_name(),
+                status: "timed_out",
+                projects: None,
+                duration_ms: None,
+            });
+            eprintln!(
+                "Warning: {} scanner timed out after {:.2?}",
+                scanner.scanner_name(),
+                timeout
+            );
+        }
+
+        // Merge in the configured order so deduplication and ties in the
+        // final sort don't depend on timing
+        let mut all_projects = ProjectList::new();
+        for projects in order.iter().filter_map(|&index| results[index].take()) {
+            for project in projects.projects() {
+                all_projects.add_project(project.clone());
+            }
+        }
+
+        if self.dedup {
+            all_projects.deduplicate_with_policy(config.dedup_policy);
+            if config.multi_clone == MultiClonePolicy::PreferNewest {
+                all_projects.collapse_multi_clones();
+            }
+        }
         all_projects.sort_by_last_modified();
-        Ok(all_projects)
+        let timings = order
+            .iter()
+            .filter_map(|&index| timings[index].take())
+            .collect();
+        Ok((all_projects, timings))
     }
 }
 
@@ -177,11 +388,16 @@ impl Default for ScanManager {
 mod tests {
     use super::*;
     use crate::models::Project;
+    use chrono::{TimeZone, Utc};
+    use std::sync::atomic::AtomicUsize;
 
     struct MockScanner {
         name: &'static str,
         projects: Vec<Project>,
         should_fail: bool,
+        delay: Duration,
+        /// How often `scan` was called; clone it before boxing the scanner
+        runs: Arc<AtomicUsize>,
     }
 
     unsafe impl Send for MockScanner {}
@@ -193,6 +409,8 @@ mod tests {
                 name,
                 projects,
                 should_fail: false,
+                delay: Duration::ZERO,
+                runs: Arc::default(),
             }
         }
 
@@ -201,12 +419,26 @@ mod tests {
                 name,
                 projects: vec![],
                 should_fail: true,
+                delay: Duration::ZERO,
+                runs: Arc::default(),
+            }
+        }
+
+        fn new_slow(name: &'static str, delay: Duration) -> Self {
+            Self {
+                name,
+                projects: vec![Project::new_local("slow".to_string(), "/slow")],
+                should_fail: false,
+                delay,
+                runs: Arc::default(),
             }
         }
     }
 
     impl ProjectScanner for MockScanner {
         fn scan(&self, _config: &Config) -> Result<ProjectList> {
+            self.runs.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            thread::sleep(self.delay);
             if self.should_fail {
                 anyhow::bail!("Mock scanner failure");
             }
@@ -262,4 +494,331 @@ mod tests {
         assert_eq!(result.len(), 1);
         assert_eq!(result.projects()[0].name, "project1");
     }
+
+    #[test]
+    fn test_slow_scanner_times_out() {
+        let fast_scanner = MockScanner::new(
+            "fast",
+            vec![Project::new_local("project1".to_string(), "/path1")],
+        );
+        let slow_scanner = MockScanner::new_slow("slow", Duration::from_secs(30));
+
+        let manager = ScanManager::new_with_scanners(vec![
+            Box::new(fast_scanner) as Box<dyn ProjectScanner + Send + Sync>,
+            Box::new(slow_scanner) as Box<dyn ProjectScanner + Send + Sync>,
+        ]);
+
+        let config = Config {
+            scanner_timeout_seconds: 1,
+            ..Config::default()
+        };
+        let start = Instant::now();
+        let result = manager.scan_all_verbose(&config, false).unwrap();
+
+        assert!(start.elapsed() < Duration::from_secs(5));
+        assert_eq!(result.len(), 1);
+        assert_eq!(result.projects()[0].name, "project1");
+    }
+
+    #[test]
+    fn test_render_clone_path() {
+        let home = dirs::home_dir().unwrap();
+
+        assert_eq!(
+            render_clone_path("~/src/{host}/{owner}/{repo}", "github.com", "octo", "cli").unwrap(),
+            home.join("src/github.com/octo/cli")
+        );
+        assert_eq!(
+            render_clone_path("code/{repo}", "github.com", "octo", "cli").unwrap(),
+            home.join("code/cli")
+        );
+        assert_eq!(
+            render_clone_path("/srv/{owner}-{repo}", "gitlab.com", "team", "api").unwrap(),
+            PathBuf::from("/srv/team-api")
+        );
+    }
+
+    #[test]
+    fn test_render_clone_path_without_placeholders() {
+        let home = dirs::home_dir().unwrap();
+
+        assert_eq!(
+            render_clone_path("~/checkouts", "github.com", "octo", "cli").unwrap(),
+            home.join("checkouts/cli")
+        );
+        assert_eq!(
+            render_clone_path("/srv/{owner}", "github.com", "octo", "cli").unwrap(),
+            PathBuf::from("/srv/octo/cli")
+        );
+    }
+
+    #[test]
+    fn test_repo_url_parts() {
+        assert_eq!(
+            repo_url_parts("https://github.com/octo/cli"),
+            Some(("github.com", "octo"))
+        );
+        assert_eq!(
+            repo_url_parts("https://gitlab.example.com/group/sub/api/"),
+            Some(("gitlab.example.com", "sub"))
+        );
+        assert_eq!(repo_url_parts("https://github.com"), None);
+    }
+
+    #[test]
+    fn test_scan_reports_progress_per_scanner() {
+        let good_scanner = MockScanner::new(
+            "good",
+            vec![Project::new_local("project1".to_string(), "/path1")],
+        );
+        let bad_scanner = MockScanner::new_failing("bad");
+        let slow_scanner = MockScanner::new_slow("slow", Duration::from_secs(30));
+
+        let manager = ScanManager::new_with_scanners(vec![
+            Box::new(good_scanner) as Box<dyn ProjectScanner + Send + Sync>,
+            Box::new(bad_scanner) as Box<dyn ProjectScanner + Send + Sync>,
+            Box::new(slow_scanner) as Box<dyn ProjectScanner + Send + Sync>,
+        ]);
+
+        let config = Config {
+            scanner_timeout_seconds: 1,
+            ..Config::default()
+        };
+        let mut progress = Vec::new();
+        manager
+            .scan_all_profiled(&config, false, |event| progress.push(event))
+            .unwrap();
+
+        assert_eq!(progress.len(), 3);
+        assert!(progress.contains(&ScanProgress::Finished {
+            scanner: "good",
+            count: 1
+        }));
+        assert!(progress.contains(&ScanProgress::Failed {
+            scanner: "bad",
+            error: "Mock scanner failure".to_string()
+        }));
+        assert_eq!(
+            progress.last(),
+            Some(&ScanProgress::TimedOut { scanner: "slow" })
+        );
+        assert_eq!(progress[0].to_string(), "✓ good (1)");
+    }
+
+    #[test]
+    fn test_scan_profile_from_scanner_timings() {
+        let manager = ScanManager::new_with_scanners(vec![
+            Box::new(MockScanner::new_slow("local", Duration::from_millis(50)))
+                as Box<dyn ProjectScanner + Send + Sync>,
+            Box::new(MockScanner::new_failing("github")) as Box<dyn ProjectScanner + Send + Sync>,
+        ]);
+
+        let (projects, scanners) = manager
+            .scan_all_profiled(&Config::default(), false, |_| {})
+            .unwrap();
+
+        assert_eq!(scanners.len(), 2);
+        assert_eq!(scanners[0].scanner, "local");
+        assert_eq!(scanners[0].status, "finished");
+        assert_eq!(scanners[0].projects, Some(1));
+        assert!(scanners[0].duration_ms.unwrap() >= 50.0);
+        assert_eq!(scanners[1].scanner, "github");
+        assert_eq!(scanners[1].status, "failed");
+        assert_eq!(scanners[1].projects, None);
+        assert!(scanners[1].duration_ms.is_some());
+
+        let profile = ScanProfile {
+            scanners,
+            projects: projects.len(),
+            cache_read_ms: 1.5,
+            cache_write_ms: 2.0,
+            total_ms: 60.0,
+        };
+        let json = serde_json::to_value(&profile).unwrap();
+        let mut keys: Vec<&str> = json
+            .as_object()
+            .unwrap()
+            .keys()
+            .map(String::as_str)
+            .collect();
+        keys.sort();
+        assert_eq!(
+            keys,
+            vec![
+                "cache_read_ms",
+                "cache_write_ms",
+                "projects",
+                "scanners",
+                "total_ms"
+            ]
+        );
+        assert_eq!(json["projects"], 1);
+        assert_eq!(json["scanners"][0]["scanner"], "local");
+        assert_eq!(json["scanners"][1]["status"], "failed");
+        assert!(json["scanners"][1]["projects"].is_null());
+    }
+
+    #[test]
+    fn test_disabled_scanner_never_runs() {
+        let local = MockScanner::new(
+            "local",
+            vec![Project::new_local("local".to_string(), "/local")],
+        );
+        let cursor = MockScanner::new(
+            "cursor",
+            vec![Project::new_local("cursor".to_string(), "/cursor")],
+        );
+        let local_runs = Arc::clone(&local.runs);
+        let cursor_runs = Arc::clone(&cursor.runs);
+        let manager = ScanManager::new_with_scanners(vec![
+            Box::new(local) as Box<dyn ProjectScanner + Send + Sync>,
+            Box::new(cursor) as Box<dyn ProjectScanner + Send + Sync>,
+        ]);
+
+        let config = Config {
+            disabled_scanners: vec!["Cursor".to_string()],
+            ..Config::default()
+        };
+        let mut progress = Vec::new();
+        let result = manager
+            .scan_all_profiled(&config, false, |event| progress.push(event))
+            .unwrap()
+            .0;
+
+        assert_eq!(cursor_runs.load(std::sync::atomic::Ordering::SeqCst), 0);
+        assert_eq!(local_runs.load(std::sync::atomic::Ordering::SeqCst), 1);
+        let names: Vec<&str> = result.projects().iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["local"]);
+        // A disabled scanner is neither reported as finished nor as timed out
+        assert_eq!(
+            progress,
+            vec![ScanProgress::Finished {
+                scanner: "local",
+                count: 1
+            }]
+        );
+    }
+
+    #[test]
+    fn test_multi_clone_policy() {
+        let clone = |path: &str, day: u32| {
+            Project::new_local("app".to_string(), path)
+                .with_remote_url(Some("git@github.com:me/app.git"))
+                .with_last_modified(Utc.with_ymd_and_hms(2024, 1, day, 0, 0, 0).unwrap())
+        };
+        let local = MockScanner::new("local", vec![clone("/work/app", 1), clone("/tmp/app", 9)]);
+        let github = MockScanner::new(
+            "github",
+            vec![Project::new_github(
+                "app".to_string(),
+                "/clones/app",
+                "https://github.com/me/app".to_string(),
+            )],
+        );
+        let manager = ScanManager::new_with_scanners(vec![
+            Box::new(local) as Box<dyn ProjectScanner + Send + Sync>,
+            Box::new(github) as Box<dyn ProjectScanner + Send + Sync>,
+        ]);
+
+        let all = manager.scan_all_verbose(&Config::default(), false).unwrap();
+        assert_eq!(all.len(), 3);
+
+        let config = Config {
+            multi_clone: MultiClonePolicy::PreferNewest,
+            ..Config::default()
+        };
+        let collapsed = manager.scan_all_verbose(&config, false).unwrap();
+        let paths: Vec<&Path> = collapsed
+            .projects()
+            .iter()
+            .map(|p| p.path.as_path())
+            .collect();
+        assert_eq!(paths, vec![Path::new("/tmp/app")]);
+    }
+
+    #[test]
+    fn test_skip_dedup_keeps_entries_from_every_source() {
+        let local = MockScanner::new(
+            "local",
+            vec![Project::new_local("app".to_string(), "/code/app")],
+        );
+        let github = MockScanner::new(
+            "github",
+            vec![Project::new_github(
+                "app".to_string(),
+                "/code/app",
+                "https://github.com/user/app".to_string(),
+            )],
+        );
+        let manager = ScanManager::new_with_scanners(vec![
+            Box::new(local) as Box<dyn ProjectScanner + Send + Sync>,
+            Box::new(github) as Box<dyn ProjectScanner + Send + Sync>,
+        ]);
+
+        let deduplicated = manager.scan_all_verbose(&Config::default(), false).unwrap();
+        assert_eq!(deduplicated.len(), 1);
+
+        let raw = manager
+            .keep_duplicates()
+            .scan_all_verbose(&Config::default(), false)
+            .unwrap();
+        let mut sources: Vec<String> = raw
+            .projects()
+            .iter()
+            .map(|project| String::from(project.source.clone()))
+            .collect();
+        sources.sort();
+        assert_eq!(sources, vec!["GitHub", "Local"]);
+    }
+
+    #[test]
+    fn test_scanner_order() {
+        let names = ["local", "cursor", "github", "gitlab", "custom"];
+        let configured = vec!["github".to_string(), "local".to_string()];
+
+        assert_eq!(
+            scanner_order(names.iter().copied(), &configured),
+            vec![2, 0, 1, 3, 4]
+        );
+        assert_eq!(
+            scanner_order(names.iter().copied(), &[]),
+            vec![0, 1, 2, 3, 4]
+        );
+    }
+
+    #[test]
+    fn test_merge_respects_scanner_order() {
+        let timestamp = chrono::Utc::now();
+        let first = MockScanner::new(
+            "first",
+            vec![Project::new_local("from-first".to_string(), "/first")
+                .with_last_modified(timestamp)],
+        );
+        let second = MockScanner::new(
+            "second",
+            vec![Project::new_local("from-second".to_string(), "/second")
+                .with_last_modified(timestamp)],
+        );
+
+        let manager = ScanManager::new_with_scanners(vec![
+            Box::new(first) as Box<dyn ProjectScanner + Send + Sync>,
+            Box::new(second) as Box<dyn ProjectScanner + Send + Sync>,
+        ]);
+
+        let config = Config {
+            scanner_order: vec!["second".to_string(), "first".to_string()],
+            ..Config::default()
+        };
+        let result = manager.scan_all_verbose(&config, false).unwrap();
+        let names: Vec<&str> = result.projects().iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["from-second", "from-first"]);
+
+        let config = Config {
+            scanner_order: vec!["first".to_string(), "second".to_string()],
+            ..Config::default()
+        };
+        let result = manager.scan_all_verbose(&config, false).unwrap();
+        let names: Vec<&str> = result.projects().iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["from-first", "from-second"]);
+    }
 }
@@ -0,0 +1,156 @@
+use crate::config::SearchCase;
+
+/// Points for each matched character
+const MATCH_SCORE: i64 = 16;
+/// Extra points when a character directly follows the previous match
+const CONTIGUOUS_BONUS: i64 = 24;
+/// Extra points when a match starts a word (start, or after `-_./ `)
+const WORD_START_BONUS: i64 = 8;
+
+/// Built-in subsequence matcher: every pattern character has to appear in
+/// order, and runs of adjacent characters score higher than scattered ones
+#[derive(Debug, Clone, Copy)]
+pub struct SimpleMatcher {
+    search_case: SearchCase,
+}
+
+impl SimpleMatcher {
+    pub fn new(search_case: SearchCase) -> Self {
+        Self { search_case }
+    }
+
+    pub fn fuzzy_match(&self, choice: &str, pattern: &str) -> Option<i64> {
+        let case_sensitive = match self.search_case {
+            SearchCase::Sensitive => true,
+            SearchCase::Insensitive => false,
+            SearchCase::Smart => pattern.chars().any(char::is_uppercase),
+        };
+        let fold = |c: char| {
+            if case_sensitive {
+                c
+            } else {
+                c.to_ascii_lowercase()
+            }
+        };
+
+        let choice: Vec<char> = choice.chars().collect();
+        let pattern: Vec<char> = pattern.chars().map(fold).collect();
+        let Some(&first) = pattern.first() else {
+            return Some(0);
+        };
+
+        // Try every place the first character occurs and keep the best run
+        (0..choice.len())
+            .filter(|&start| fold(choice[start]) == first)
+            .filter_map(|start| score_from(&choice, &pattern, start, fold))
+            .max()
+    }
+}
+
+fn score_from(
+    choice: &[char],
+    pattern: &[char],
+    start: usize,
+    fold: impl Fn(char) -> char,
+) -> Option<i64> {
+    let mut score = 0;
+    let mut previous: Option<usize> = None;
+    let mut position = start;
+
+    for &wanted in pattern {
+        let index = (position..choice.len()).find(|&i| fold(choice[i]) == wanted)?;
+
+        score += MATCH_SCORE;
+        if previous.is_some_and(|previous| previous + 1 == index) {
+            score += CONTIGUOUS_BONUS;
+        }
+        if is_word_start(choice, index) {
+            score += WORD_START_BONUS;
+        }
+
+        previous = Some(index);
+        position = index + 1;
+    }
+
+    Some(score)
+}
+
+/// Whether `choice[index]` begins a word: the first character, or one
+/// following `-`, `_`, `.`, `/` or a space
+pub fn is_word_start(choice: &[char], index: usize) -> bool {
+    index == 0 || matches!(choice[index - 1], '-' | '_' | '.' | '/' | ' ')
+}
+
+/// Whether `pattern` matches `choice` as a subsequence that begins at a word
+/// start. Case is ignored.
+pub fn matches_from_word_start(choice: &str, pattern: &str) -> bool {
+    let choice: Vec<char> = choice.chars().map(|c| c.to_ascii_lowercase()).collect();
+    let pattern: Vec<char> = pattern
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .map(|c| c.to_ascii_lowercase())
+        .collect();
+    let Some(&first) = pattern.first() else {
+        return false;
+    };
+
+    (0..choice.len())
+        .filter(|&start| choice[start] == first && is_word_start(&choice, start))
+        .any(|start| {
+            let mut rest = choice[start + 1..].iter();
+            pattern[1..].iter().all(|wanted| rest.any(|c| c == wanted))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_requires_subsequence() {
+        let matcher = SimpleMatcher::new(SearchCase::Smart);
+
+        assert!(matcher.fuzzy_match("switchr", "swr").is_some());
+        assert!(matcher.fuzzy_match("switchr", "rws").is_none());
+        assert!(matcher.fuzzy_match("switchr", "").is_some());
+    }
+
+    #[test]
+    fn test_contiguous_beats_scattered() {
+        let matcher = SimpleMatcher::new(SearchCase::Smart);
+
+        let contiguous = matcher.fuzzy_match("my-api-server", "api").unwrap();
+        let scattered = matcher.fuzzy_match("a-pretty-index", "api").unwrap();
+        assert!(contiguous > scattered);
+
+        // The best run wins even when an earlier, scattered one exists
+        let later_run = matcher.fuzzy_match("a_p_i-api", "api").unwrap();
+        assert_eq!(later_run, contiguous);
+    }
+
+    #[test]
+    fn test_matches_from_word_start() {
+        assert!(matches_from_word_start("payment-orders", "po"));
+        assert!(matches_from_word_start("my-api-server", "Api"));
+        assert!(matches_from_word_start("infra/ops_tool", "ot"));
+        assert!(!matches_from_word_start("wallpaper-tool", "po"));
+        assert!(!matches_from_word_start("payment-orders", "op"));
+        assert!(!matches_from_word_start("payment-orders", ""));
+    }
+
+    #[test]
+    fn test_case_handling() {
+        assert!(SimpleMatcher::new(SearchCase::Smart)
+            .fuzzy_match("WebApp", "webapp")
+            .is_some());
+        assert!(SimpleMatcher::new(SearchCase::Smart)
+            .fuzzy_match("webapp", "WebApp")
+            .is_none());
+        assert!(SimpleMatcher::new(SearchCase::Insensitive)
+            .fuzzy_match("webapp", "WebApp")
+            .is_some());
+        assert!(SimpleMatcher::new(SearchCase::Sensitive)
+            .fuzzy_match("WebApp", "webapp")
+            .is_none());
+    }
+}
@@ -1,7 +1,13 @@
-use crate::models::{Project, ProjectList};
-use anyhow::Result;
+use crate::config::{Config, MatcherKind, SearchCase, ThemeColors, ThemePreset};
+use crate::models::{abbreviate_home, Project, ProjectList, ProjectSource, Refresh};
+use crate::simple_matcher::{matches_from_word_start, SimpleMatcher};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEventKind},
+    event::{
+        self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEvent, KeyEventKind,
+        KeyModifiers,
+    },
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
@@ -11,11 +17,16 @@ use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout},
     style::{Color, Modifier, Style, Stylize},
     text::{Line, Span, Text},
-    widgets::{Block, BorderType, Borders, List, ListItem, Padding, Paragraph},
+    widgets::{Block, BorderType, Borders, List, ListItem, ListState, Padding, Paragraph},
     Frame, Terminal,
 };
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fs;
 use std::io;
+use std::path::{Path, PathBuf};
 use std::sync::mpsc::Receiver;
+use std::time::{Duration, Instant};
 
 const PRIMARY_COLOR: Color = Color::Rgb(99, 102, 241);
 const SECONDARY_COLOR: Color = Color::Rgb(139, 92, 246);
@@ -28,93 +39,531 @@ const TEXT_SECONDARY: Color = Color::Rgb(148, 163, 184);
 const TEXT_MUTED: Color = Color::Rgb(100, 116, 139);
 const ACCENT_COLOR: Color = Color::Rgb(20, 184, 166);
 
+/// Colors for each role in the TUI; `theme` in the config overrides the
+/// built-in defaults role by role
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Theme {
+    primary: Color,
+    secondary: Color,
+    success: Color,
+    warning: Color,
+    error: Color,
+    surface: Color,
+    text_primary: Color,
+    text_secondary: Color,
+    text_muted: Color,
+    accent: Color,
+    dirty: Color,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            primary: PRIMARY_COLOR,
+            secondary: SECONDARY_COLOR,
+            success: SUCCESS_COLOR,
+            warning: WARNING_COLOR,
+            error: ERROR_COLOR,
+            surface: SURFACE_COLOR,
+            text_primary: TEXT_PRIMARY,
+            text_secondary: TEXT_SECONDARY,
+            text_muted: TEXT_MUTED,
+            accent: ACCENT_COLOR,
+            dirty: WARNING_COLOR,
+        }
+    }
+}
+
+impl Theme {
+    /// Palette for light terminal backgrounds
+    fn light() -> Self {
+        Self {
+            primary: Color::Rgb(79, 70, 229),
+            secondary: Color::Rgb(124, 58, 237),
+            success: Color::Rgb(21, 128, 61),
+            warning: Color::Rgb(180, 83, 9),
+            error: Color::Rgb(185, 28, 28),
+            surface: Color::Rgb(241, 245, 249),
+            text_primary: Color::Rgb(15, 23, 42),
+            text_secondary: Color::Rgb(71, 85, 105),
+            text_muted: Color::Rgb(100, 116, 139),
+            accent: Color::Rgb(15, 118, 110),
+            dirty: Color::Rgb(180, 83, 9),
+        }
+    }
+
+    /// The configured preset (with `Auto` resolved from `$COLORFGBG`) and
+    /// its colors overridden by `theme`
+    fn from_config(config: &Config) -> Self {
+        let colorfgbg = std::env::var("COLORFGBG").ok();
+        let preset = resolve_preset(config.theme_preset, colorfgbg.as_deref());
+        Self::with_colors(preset, &config.theme)
+    }
+
+    /// `preset`'s palette with every role in `colors` that parses as a hex
+    /// color replaced
+    fn with_colors(preset: ThemePreset, colors: &ThemeColors) -> Self {
+        let defaults = match preset {
+            ThemePreset::Light => Self::light(),
+            ThemePreset::Dark | ThemePreset::Auto => Self::default(),
+        };
+        let pick = |configured: &Option<String>, default: Color| {
+            configured
+                .as_deref()
+                .and_then(parse_hex_color)
+                .unwrap_or(default)
+        };
+
+        Self {
+            primary: pick(&colors.primary, defaults.primary),
+            secondary: pick(&colors.secondary, defaults.secondary),
+            success: pick(&colors.success, defaults.success),
+            warning: pick(&colors.warning, defaults.warning),
+            error: pick(&colors.error, defaults.error),
+            surface: pick(&colors.surface, defaults.surface),
+            text_primary: pick(&colors.text_primary, defaults.text_primary),
+            text_secondary: pick(&colors.text_secondary, defaults.text_secondary),
+            text_muted: pick(&colors.text_muted, defaults.text_muted),
+            accent: pick(&colors.accent, defaults.accent),
+            dirty: pick(&colors.dirty, defaults.dirty),
+        }
+    }
+}
+
+/// `weight` scaled down by the project's age: all of it when modified just
+/// now, half after a week, a tenth after two months. Undated projects get
+/// nothing.
+fn recency_bonus(project: &Project, weight: i64, now: DateTime<Utc>) -> i64 {
+    let Some(modified) = project.last_modified else {
+        return 0;
+    };
+    let age_days = now.signed_duration_since(modified).num_seconds().max(0) as f64 / 86_400.0;
+    (weight as f64 / (1.0 + age_days / 7.0)).round() as i64
+}
+
+/// Run `task` on a rayon pool of `threads` threads (at least one), or on the
+/// current thread if the pool can't be built
+fn with_probe_pool<T: Send>(threads: usize, task: impl FnOnce() -> T + Send) -> T {
+    match rayon::ThreadPoolBuilder::new()
+        .num_threads(threads.max(1))
+        .build()
+    {
+        Ok(pool) => pool.install(task),
+        Err(_) => task(),
+    }
+}
+
+/// Whether each project's path exists, in project order
+fn paths_exist(projects: &[Project]) -> Vec<bool> {
+    projects
+        .par_iter()
+        .map(|project| project.path.exists())
+        .collect()
+}
+
+/// `Dark` or `Light` for `preset`. `Auto` looks at `COLORFGBG` ("fg;bg" as
+/// set by rxvt, Konsole and others): background colors 7 and 9-15 are
+/// light. Without the variable, or when it can't be read, `Auto` is dark.
+fn resolve_preset(preset: ThemePreset, colorfgbg: Option<&str>) -> ThemePreset {
+    if preset != ThemePreset::Auto {
+        return preset;
+    }
+
+    let background = colorfgbg
+        .and_then(|value| value.rsplit(';').next())
+        .and_then(|bg| bg.trim().parse::<u8>().ok());
+    match background {
+        Some(7 | 9..=15) => ThemePreset::Light,
+        _ => ThemePreset::Dark,
+    }
+}
+
+/// `#rrggbb` or `#rgb` (the `#` is optional) as an RGB color
+fn parse_hex_color(hex: &str) -> Option<Color> {
+    let digits = hex.trim().trim_start_matches('#');
+    if !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+
+    let channel = |range: std::ops::Range<usize>| u8::from_str_radix(&digits[range], 16).ok();
+    match digits.len() {
+        6 => Some(Color::Rgb(channel(0..2)?, channel(2..4)?, channel(4..6)?)),
+        // #abc is shorthand for #aabbcc
+        3 => Some(Color::Rgb(
+            channel(0..1)? * 17,
+            channel(1..2)? * 17,
+            channel(2..3)? * 17,
+        )),
+        _ => None,
+    }
+}
+
+/// The project picked in the TUI and how it should be opened
+#[derive(Debug, Clone)]
+pub struct Selection {
+    pub project: Project,
+    pub open_in_current_window: bool,
+}
+
+/// Query and selection saved when the TUI exits
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LastSession {
+    pub query: String,
+    pub selected_name: Option<String>,
+    pub selected_path: Option<PathBuf>,
+}
+
+impl LastSession {
+    pub fn load_from_path(path: &Path) -> Result<Option<Self>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read TUI session: {}", path.display()))?;
+        let session = serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse TUI session: {}", path.display()))?;
+        Ok(Some(session))
+    }
+
+    pub fn save_to_path(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+        }
+
+        let content = serde_json::to_string(self).context("Failed to serialize TUI session")?;
+        fs::write(path, content)
+            .with_context(|| format!("Failed to write TUI session: {}", path.display()))
+    }
+}
+
+/// Below this size the full layout (title, search box, list, status and help
+/// bars) no longer fits, so `draw` falls back to a compact view
+const MIN_FULL_LAYOUT_HEIGHT: u16 = 20;
+const MIN_FULL_LAYOUT_WIDTH: u16 = 40;
+
+/// Most rows shown in the projects list; further matches are counted only
+const MAX_DISPLAYED_PROJECTS: usize = 20;
+
+/// Added to name matches so they outrank attribute matches
+const NAME_MATCH_BONUS: i64 = 10_000;
+
+/// Quiet period after the last keystroke before the query is re-matched
+const SEARCH_DEBOUNCE: Duration = Duration::from_millis(30);
+
+/// A row in the projects list: either a source group header or a project
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum DisplayRow {
+    Header { source: ProjectSource, count: usize },
+    Project { filtered_index: usize },
+}
+
+fn source_style(source: &ProjectSource, theme: &Theme) -> (&'static str, Color, &'static str) {
+    match source {
+        ProjectSource::Local => ("📂", theme.success, "Local"),
+        ProjectSource::Cursor => ("🎯", theme.primary, "Cursor"),
+        ProjectSource::GitHub => ("🐙", theme.secondary, "GitHub"),
+        ProjectSource::GitLab => ("🦊", theme.accent, "GitLab"),
+        ProjectSource::Unknown(_) => ("❔", theme.text_muted, "Other"),
+    }
+}
+
+/// The fuzzy matcher selected by `config.matcher`
+enum Matcher {
+    Skim(Box<SkimMatcherV2>),
+    Simple(SimpleMatcher),
+}
+
+impl Matcher {
+    fn fuzzy_match(&self, choice: &str, pattern: &str) -> Option<i64> {
+        match self {
+            Matcher::Skim(matcher) => matcher.fuzzy_match(choice, pattern),
+            Matcher::Simple(matcher) => matcher.fuzzy_match(choice, pattern),
+        }
+    }
+}
+
+/// Indexes of the projects matching `query` with their scores, best first;
+/// equal scores keep list order
+fn rank_matches(
+    projects: &[Project],
+    query: &str,
+    matcher: &Matcher,
+    config: &Config,
+) -> Vec<(usize, i64)> {
+    let now = Utc::now();
+    let mut scored: Vec<(usize, i64)> = projects
+        .iter()
+        .enumerate()
+        .filter_map(|(i, project)| {
+            let score = match_score(matcher, project, query)?;
+            Some((
+                i,
+                score
+                    + boundary_bonus(project, query, config.boundary_bonus)
+                    + recency_bonus(project, config.recency_weight, now),
+            ))
+        })
+        .collect();
+
+    scored.sort_by_key(|&(_, score)| std::cmp::Reverse(score));
+    scored
+}
+
+/// The project the TUI would list first for `query`, if any matches
+pub fn best_match<'a>(
+    projects: &'a [Project],
+    query: &str,
+    config: &Config,
+) -> Option<&'a Project> {
+    let matcher = build_matcher(config.matcher, config.search_case);
+    rank_matches(projects, query, &matcher, config)
+        .first()
+        .map(|&(index, _)| &projects[index])
+}
+
+/// Whole-query matches on the name rank first; otherwise every
+/// whitespace-separated token has to match the project's search tokens
+/// (name, source, host and type), so `api github` narrows by both
+fn match_score(matcher: &Matcher, project: &Project, query: &str) -> Option<i64> {
+    if let Some(score) = matcher.fuzzy_match(&project.name, query) {
+        return Some(score + NAME_MATCH_BONUS);
+    }
+
+    let haystack = project.search_tokens();
+    query
+        .split_whitespace()
+        .map(|token| matcher.fuzzy_match(&haystack, token))
+        .sum()
+}
+
+/// `bonus` when `query` matches the name starting at a word boundary, so
+/// `po` prefers `payment-orders` over `wallpaper-tool`
+fn boundary_bonus(project: &Project, query: &str, bonus: i64) -> i64 {
+    if matches_from_word_start(project.shown_name(), query) {
+        bonus
+    } else {
+        0
+    }
+}
+
+fn build_matcher(kind: MatcherKind, search_case: SearchCase) -> Matcher {
+    match kind {
+        MatcherKind::Skim => {
+            let matcher = SkimMatcherV2::default();
+            Matcher::Skim(Box::new(match search_case {
+                SearchCase::Smart => matcher.smart_case(),
+                SearchCase::Insensitive => matcher.ignore_case(),
+                SearchCase::Sensitive => matcher.respect_case(),
+            }))
+        }
+        MatcherKind::Simple => Matcher::Simple(SimpleMatcher::new(search_case)),
+    }
+}
+
+/// Order of source groups in grouped mode
+fn source_rank(source: &ProjectSource) -> u8 {
+    match source {
+        ProjectSource::Local => 0,
+        ProjectSource::Cursor => 1,
+        ProjectSource::GitHub => 2,
+        ProjectSource::GitLab => 3,
+        ProjectSource::Unknown(_) => 4,
+    }
+}
+
 pub struct TuiApp {
     input: String,
+    // Cursor position in `input`, in characters
+    cursor_pos: usize,
     projects: Vec<Project>,
     filtered_projects: Vec<(usize, i64)>,
+    // Matches before truncation to MAX_DISPLAYED_PROJECTS
+    total_matches: usize,
+    // Matches per source before truncation, for the grouped headers
+    group_counts: Vec<(ProjectSource, usize)>,
     selected_index: usize,
-    matcher: SkimMatcherV2,
+    matcher: Matcher,
+    theme: Theme,
+    min_query_len: usize,
+    grouped: bool,
+    show_paths: bool,
     should_quit: bool,
     selected_project: Option<Project>,
+    open_in_current_window: bool,
 
     project_exists_cache: Vec<bool>,
 
+    // Display name overrides, indexed like `projects`
+    config: Config,
+    display_labels: Vec<Option<String>>,
+    rename_buffer: Option<String>,
+    notice: Option<String>,
+
+    // Project containing the directory sw was launched from
+    current_dir: Option<PathBuf>,
+    current_project: Option<usize>,
+
     github_status_cache: String,
     gitlab_status_cache: String,
 
     // Background refresh
-    update_receiver: Option<Receiver<ProjectList>>,
+    update_receiver: Option<Receiver<Refresh>>,
     is_refreshing: bool,
+
+    // Keystroke debouncing
+    search_pending: bool,
+    last_keystroke: Instant,
 }
 
 impl TuiApp {
     #[allow(dead_code)]
     pub fn new(projects: Vec<Project>) -> Self {
-        Self::new_with_receiver(projects, None)
+        Self::new_with_receiver(projects, None, &Config::default())
     }
 
     pub fn new_with_receiver(
         projects: Vec<Project>,
-        update_receiver: Option<Receiver<ProjectList>>,
+        update_receiver: Option<Receiver<Refresh>>,
+        config: &Config,
     ) -> Self {
-        let project_exists_cache: Vec<bool> = projects
-            .iter()
-            .map(|project| project.path.exists())
-            .collect();
-
-        let projects_clone = projects.clone();
-        let github_thread =
-            std::thread::spawn(move || Self::compute_github_status(&projects_clone));
-
-        let projects_clone = projects.clone();
-        let gitlab_thread =
-            std::thread::spawn(move || Self::compute_gitlab_status(&projects_clone));
-
-        let github_status_cache = github_thread.join().unwrap_or_else(|_| "error".to_string());
-        let gitlab_status_cache = gitlab_thread.join().unwrap_or_else(|_| "error".to_string());
+        // Existence checks and both CLI probes share one bounded pool
+        let (project_exists_cache, (github_status_cache, gitlab_status_cache)) =
+            with_probe_pool(config.tui_probe_threads, || {
+                rayon::join(
+                    || paths_exist(&projects),
+                    || {
+                        rayon::join(
+                            || Self::compute_github_status(&projects),
+                            || Self::compute_gitlab_status(&projects),
+                        )
+                    },
+                )
+            });
 
         let is_refreshing = update_receiver.is_some();
 
         let mut app = Self {
             input: String::new(),
+            cursor_pos: 0,
             filtered_projects: Vec::new(),
+            total_matches: 0,
+            group_counts: Vec::new(),
             selected_index: 0,
-            matcher: SkimMatcherV2::default(),
+            matcher: build_matcher(config.matcher, config.search_case),
+            theme: Theme::from_config(config),
+            min_query_len: config.min_query_len,
+            grouped: false,
+            show_paths: false,
             should_quit: false,
             selected_project: None,
+            open_in_current_window: config.open_in_current_window,
             projects,
             project_exists_cache,
+            config: config.clone(),
+            display_labels: Vec::new(),
+            rename_buffer: None,
+            notice: None,
+            current_dir: None,
+            current_project: None,
             github_status_cache,
             gitlab_status_cache,
             update_receiver,
             is_refreshing,
+            search_pending: false,
+            last_keystroke: Instant::now(),
         };
+        app.refresh_display_labels();
         app.update_filtered_projects();
+        if let Ok(cwd) = std::env::current_dir() {
+            app.set_current_dir(&cwd);
+        }
         app
     }
 
+    /// Mark the project containing `dir` as current, keep it listed and
+    /// select it
+    fn set_current_dir(&mut self, dir: &Path) {
+        self.current_dir = Some(dir.to_path_buf());
+        self.current_project = crate::models::find_project_containing(&self.projects, dir);
+        self.update_filtered_projects();
+
+        if let Some(current) = self.current_project {
+            if let Some(filtered_pos) = self
+                .filtered_projects
+                .iter()
+                .position(|(idx, _)| *idx == current)
+            {
+                self.selected_index = filtered_pos;
+            }
+        }
+    }
+
+    fn last_session(&self) -> LastSession {
+        let selected = self.get_selected_project();
+        LastSession {
+            query: self.input.clone(),
+            selected_name: selected.as_ref().map(|project| project.name.clone()),
+            selected_path: selected.map(|project| project.path),
+        }
+    }
+
+    /// Re-apply a saved query and reselect the saved project, matching by
+    /// path first and then by name. A project that no longer exists leaves
+    /// the default selection.
+    fn restore_session(&mut self, session: &LastSession) {
+        self.input = session.query.clone();
+        self.cursor_pos = self.input.chars().count();
+        self.update_filtered_projects();
+
+        let position = self
+            .filtered_projects
+            .iter()
+            .position(|(idx, _)| session.selected_path.as_ref() == Some(&self.projects[*idx].path));
+        let position = position.or_else(|| {
+            self.filtered_projects.iter().position(|(idx, _)| {
+                session.selected_name.as_ref() == Some(&self.projects[*idx].name)
+            })
+        });
+
+        self.selected_index = position.unwrap_or(0);
+    }
+
     #[allow(dead_code)]
     pub fn run_interactive<B: Backend>(
         projects: Vec<Project>,
+        config: &Config,
         terminal: &mut Terminal<B>,
-    ) -> Result<Option<Project>> {
-        Self::run_interactive_with_receiver(projects, None, terminal)
+    ) -> Result<Option<Selection>> {
+        Self::run_interactive_with_receiver(projects, None, config, terminal)
     }
 
     pub fn run_interactive_with_receiver<B: Backend>(
         projects: Vec<Project>,
-        update_receiver: Option<Receiver<ProjectList>>,
+        update_receiver: Option<Receiver<Refresh>>,
+        config: &Config,
         terminal: &mut Terminal<B>,
-    ) -> Result<Option<Project>> {
-        let mut app = TuiApp::new_with_receiver(projects, update_receiver);
+    ) -> Result<Option<Selection>> {
+        let mut app = TuiApp::new_with_receiver(projects, update_receiver, config);
+        let session_path = Config::tui_session_path().ok();
+
+        if config.restore_last_query {
+            if let Some(Ok(Some(session))) =
+                session_path.as_deref().map(LastSession::load_from_path)
+            {
+                app.restore_session(&session);
+            }
+        }
 
         loop {
+            app.apply_pending_search(Instant::now());
             terminal.draw(|f| app.draw(f))?;
 
             // Check for background updates
             if let Some(rx) = &app.update_receiver {
-                if let Ok(updated_projects) = rx.try_recv() {
-                    app.update_projects(updated_projects.projects().to_vec());
+                if let Ok(refresh) = rx.try_recv() {
+                    app.merge_refreshed(refresh);
                     app.is_refreshing = false;
                 }
             }
@@ -123,37 +572,7 @@ impl TuiApp {
             if event::poll(std::time::Duration::from_millis(50))? {
                 if let Event::Key(key) = event::read()? {
                     if key.kind == KeyEventKind::Press {
-                        match key.code {
-                            KeyCode::Char('q') => {
-                                app.should_quit = true;
-                            }
-                            KeyCode::Esc => {
-                                app.should_quit = true;
-                            }
-                            KeyCode::Enter => {
-                                if let Some(project) = app.get_selected_project() {
-                                    app.selected_project = Some(project);
-                                    app.should_quit = true;
-                                }
-                            }
-                            KeyCode::Char(c) => {
-                                app.input.push(c);
-                                app.update_filtered_projects();
-                                app.selected_index = 0;
-                            }
-                            KeyCode::Backspace => {
-                                app.input.pop();
-                                app.update_filtered_projects();
-                                app.selected_index = 0;
-                            }
-                            KeyCode::Up => {
-                                app.move_selection_up();
-                            }
-                            KeyCode::Down => {
-                                app.move_selection_down();
-                            }
-                            _ => {}
-                        }
+                        app.handle_key(key);
                     }
                 }
             }
@@ -163,33 +582,268 @@ impl TuiApp {
             }
         }
 
-        Ok(app.selected_project)
+        // Best effort: the terminal is still in the alternate screen, so a
+        // failure here has nowhere useful to be reported
+        if let Some(path) = session_path {
+            let _ = app.last_session().save_to_path(&path);
+        }
+
+        Ok(app.selected_project.map(|project| Selection {
+            project,
+            open_in_current_window: app.open_in_current_window,
+        }))
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) {
+        if self.rename_buffer.is_some() {
+            self.handle_rename_key(key);
+            return;
+        }
+
+        match key.code {
+            KeyCode::Char('g') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.flush_pending_search();
+                self.toggle_grouped();
+            }
+            KeyCode::Char('p') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.show_paths = !self.show_paths;
+            }
+            KeyCode::F(2) => {
+                self.flush_pending_search();
+                self.start_rename();
+            }
+            KeyCode::Char('q') => {
+                self.should_quit = true;
+            }
+            KeyCode::Esc => {
+                self.should_quit = true;
+            }
+            KeyCode::Enter => {
+                self.flush_pending_search();
+                // Shift/Alt+Enter flips the configured window behaviour
+                if key
+                    .modifiers
+                    .intersects(KeyModifiers::SHIFT | KeyModifiers::ALT)
+                {
+                    self.open_in_current_window = !self.open_in_current_window;
+                }
+                if let Some(project) = self.get_selected_project() {
+                    self.selected_project = Some(project);
+                    self.should_quit = true;
+                }
+            }
+            KeyCode::Char(c) => {
+                self.insert_at_cursor(c);
+                self.schedule_search();
+            }
+            KeyCode::Backspace => {
+                self.delete_before_cursor();
+                self.schedule_search();
+            }
+            KeyCode::Delete => {
+                self.delete_at_cursor();
+                self.schedule_search();
+            }
+            KeyCode::Left => {
+                self.cursor_pos = self.cursor().saturating_sub(1);
+            }
+            KeyCode::Right => {
+                self.cursor_pos = (self.cursor() + 1).min(self.input.chars().count());
+            }
+            KeyCode::Home => {
+                self.cursor_pos = 0;
+            }
+            KeyCode::End => {
+                self.cursor_pos = self.input.chars().count();
+            }
+            KeyCode::Up => {
+                self.move_selection_up();
+            }
+            KeyCode::Down => {
+                self.move_selection_down();
+            }
+            _ => {}
+        }
+    }
+
+    /// The cursor position, kept within `input` even if it was replaced
+    fn cursor(&self) -> usize {
+        self.cursor_pos.min(self.input.chars().count())
+    }
+
+    /// Byte offset in `input` of the character at `position`
+    fn input_offset(&self, position: usize) -> usize {
+        self.input
+            .char_indices()
+            .nth(position)
+            .map_or(self.input.len(), |(offset, _)| offset)
+    }
+
+    fn insert_at_cursor(&mut self, c: char) {
+        let cursor = self.cursor();
+        let offset = self.input_offset(cursor);
+        self.input.insert(offset, c);
+        self.cursor_pos = cursor + 1;
+    }
+
+    /// Backspace: remove the character left of the cursor
+    fn delete_before_cursor(&mut self) {
+        let cursor = self.cursor();
+        if cursor == 0 {
+            return;
+        }
+        let offset = self.input_offset(cursor - 1);
+        self.input.remove(offset);
+        self.cursor_pos = cursor - 1;
+    }
+
+    /// Delete: remove the character under the cursor
+    fn delete_at_cursor(&mut self) {
+        let cursor = self.cursor();
+        if cursor >= self.input.chars().count() {
+            return;
+        }
+        let offset = self.input_offset(cursor);
+        self.input.remove(offset);
+        self.cursor_pos = cursor;
+    }
+
+    fn handle_rename_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Esc => {
+                self.rename_buffer = None;
+            }
+            KeyCode::Enter => {
+                if let Some(name) = self.rename_buffer.take() {
+                    self.apply_rename(name.trim());
+                }
+            }
+            KeyCode::Char(c) => {
+                if let Some(buffer) = self.rename_buffer.as_mut() {
+                    buffer.push(c);
+                }
+            }
+            KeyCode::Backspace => {
+                if let Some(buffer) = self.rename_buffer.as_mut() {
+                    buffer.pop();
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn start_rename(&mut self) {
+        if let Some(project) = self.get_selected_project() {
+            let current = self
+                .config
+                .display_name_for(&project.path)
+                .unwrap_or(&project.name)
+                .to_string();
+            self.rename_buffer = Some(current);
+        }
+    }
+
+    /// Set (or clear, when empty) the display name of the selected project
+    /// and persist it to the config file
+    fn apply_rename(&mut self, name: &str) {
+        let Some(project) = self.get_selected_project() else {
+            return;
+        };
+
+        self.config.set_display_name(&project.path, name);
+        self.refresh_display_labels();
+
+        let persisted = Config::load().and_then(|mut on_disk| {
+            on_disk.set_display_name(&project.path, name);
+            on_disk.save()
+        });
+        if let Err(e) = persisted {
+            self.notice = Some(format!("Failed to save display name: {}", e));
+        }
+    }
+
+    fn refresh_display_labels(&mut self) {
+        self.display_labels = self
+            .projects
+            .iter()
+            .map(|project| {
+                self.config
+                    .display_name_for(&project.path)
+                    .map(str::to_string)
+            })
+            .collect();
+    }
+
+    /// Name shown for a project; matching uses `Project::name` and the
+    /// search tokens
+    fn display_label(&self, project_index: usize) -> &str {
+        self.display_labels
+            .get(project_index)
+            .and_then(|label| label.as_deref())
+            .unwrap_or(self.projects[project_index].shown_name())
+    }
+
+    /// Defer re-matching until the current burst of keystrokes settles
+    fn schedule_search(&mut self) {
+        self.search_pending = true;
+        self.last_keystroke = Instant::now();
+    }
+
+    /// Run a deferred search once the debounce window has elapsed
+    fn apply_pending_search(&mut self, now: Instant) {
+        if self.search_pending && now.duration_since(self.last_keystroke) >= SEARCH_DEBOUNCE {
+            self.flush_pending_search();
+        }
+    }
+
+    fn flush_pending_search(&mut self) {
+        if self.search_pending {
+            self.search_pending = false;
+            self.update_filtered_projects();
+            self.selected_index = 0;
+        }
     }
 
     fn update_filtered_projects(&mut self) {
-        if self.input.is_empty() {
-            self.filtered_projects = self
-                .projects
-                .iter()
-                .enumerate()
-                .map(|(i, _)| (i, 100))
-                .take(20)
-                .collect();
-        } else {
-            let mut scored: Vec<(usize, i64)> = self
-                .projects
-                .iter()
-                .enumerate()
-                .filter_map(|(i, project)| {
-                    self.matcher
-                        .fuzzy_match(&project.name, &self.input)
-                        .map(|score| (i, score))
-                })
-                .collect();
+        let matches: Vec<(usize, i64)> =
+            if self.input.is_empty() || self.input.chars().count() < self.min_query_len {
+                (0..self.projects.len()).map(|i| (i, 100)).collect()
+            } else {
+                rank_matches(&self.projects, &self.input, &self.matcher, &self.config)
+            };
+
+        self.total_matches = matches.len();
+        self.group_counts.clear();
+        if self.grouped {
+            for (index, _) in &matches {
+                let source = &self.projects[*index].source;
+                match self.group_counts.iter_mut().find(|(s, _)| s == source) {
+                    Some((_, count)) => *count += 1,
+                    None => self.group_counts.push((source.clone(), 1)),
+                }
+            }
+        }
+        self.filtered_projects = matches
+            .iter()
+            .take(MAX_DISPLAYED_PROJECTS)
+            .copied()
+            .collect();
 
-            scored.sort_by(|a, b| b.1.cmp(&a.1));
+        // The project sw was launched from stays listed even when it ranks
+        // below the cut, in place of the last row
+        if let Some(current) = self.current_project {
+            if !self.filtered_projects.iter().any(|(i, _)| *i == current) {
+                if let Some(&entry) = matches.iter().find(|(i, _)| *i == current) {
+                    self.filtered_projects.truncate(MAX_DISPLAYED_PROJECTS - 1);
+                    self.filtered_projects.push(entry);
+                }
+            }
+        }
 
-            self.filtered_projects = scored.into_iter().take(20).collect();
+        if self.grouped {
+            let projects = &self.projects;
+            self.filtered_projects
+                .sort_by_key(|(index, _)| source_rank(&projects[*index].source));
         }
 
         if self.selected_index >= self.filtered_projects.len() {
@@ -197,6 +851,65 @@ impl TuiApp {
         }
     }
 
+    /// Footer for when more projects match than the list shows
+    fn truncation_notice(&self) -> Option<String> {
+        (self.total_matches > self.filtered_projects.len()).then(|| {
+            format!(
+                " showing {} of {} matches — refine your search ",
+                self.filtered_projects.len(),
+                self.total_matches
+            )
+        })
+    }
+
+    fn toggle_grouped(&mut self) {
+        let selected_project = self.get_selected_project();
+
+        self.grouped = !self.grouped;
+        self.update_filtered_projects();
+
+        if let Some(prev_selected) = selected_project {
+            if let Some(filtered_pos) = self
+                .filtered_projects
+                .iter()
+                .position(|(idx, _)| self.projects[*idx].path == prev_selected.path)
+            {
+                self.selected_index = filtered_pos;
+            }
+        }
+    }
+
+    /// Rows to render. In grouped mode a non-selectable header precedes each
+    /// source group; selection always indexes into `filtered_projects`.
+    fn display_rows(&self) -> Vec<DisplayRow> {
+        let mut rows = Vec::with_capacity(self.filtered_projects.len());
+
+        for (filtered_index, (project_index, _)) in self.filtered_projects.iter().enumerate() {
+            if self.grouped {
+                let source = &self.projects[*project_index].source;
+                let starts_group = filtered_index == 0
+                    || self.projects[self.filtered_projects[filtered_index - 1].0].source
+                        != *source;
+
+                if starts_group {
+                    let count = self
+                        .group_counts
+                        .iter()
+                        .find(|(group, _)| group == source)
+                        .map_or(0, |(_, count)| *count);
+                    rows.push(DisplayRow::Header {
+                        source: source.clone(),
+                        count,
+                    });
+                }
+            }
+
+            rows.push(DisplayRow::Project { filtered_index });
+        }
+
+        rows
+    }
+
     fn move_selection_up(&mut self) {
         if self.selected_index > 0 {
             self.selected_index -= 1;
@@ -264,17 +977,38 @@ impl TuiApp {
         }
     }
 
+    /// Fold a background refresh into the projects on screen, one entry per
+    /// path, preferring sources earlier in `scanner_order`. Entries from
+    /// sources that were rescanned are replaced wholesale, so deleted repos
+    /// drop out; sources whose scanner failed keep what was shown.
+    fn merge_refreshed(&mut self, refresh: Refresh) {
+        let mut merged = ProjectList::from_projects(
+            self.projects
+                .iter()
+                .filter(|project| !refresh.scanned_sources.contains(&project.source))
+                .cloned()
+                .collect(),
+        );
+        merged.merge(refresh.projects, &self.config.source_priority());
+        merged.qualify_colliding_names();
+        merged.sort_by_last_modified();
+        self.update_projects(merged.projects().to_vec());
+    }
+
     fn update_projects(&mut self, new_projects: Vec<Project>) {
         // Save current selection state
         let selected_project = self.get_selected_project();
 
         // Update projects and caches
         self.projects = new_projects;
-        self.project_exists_cache = self
-            .projects
-            .iter()
-            .map(|project| project.path.exists())
-            .collect();
+        self.project_exists_cache = with_probe_pool(self.config.tui_probe_threads, || {
+            paths_exist(&self.projects)
+        });
+        self.refresh_display_labels();
+        self.current_project = self
+            .current_dir
+            .as_deref()
+            .and_then(|dir| crate::models::find_project_containing(&self.projects, dir));
 
         // Update status caches in background
         let projects_clone = self.projects.clone();
@@ -304,50 +1038,243 @@ impl TuiApp {
         }
     }
 
-    fn draw(&self, f: &mut Frame) {
-        let main_chunks = Layout::default()
-            .direction(Direction::Vertical)
-            .margin(1)
-            .constraints([
-                Constraint::Length(1),
-                Constraint::Length(3),
-                Constraint::Length(1),
-                Constraint::Min(10),
-                Constraint::Length(1),
-                Constraint::Length(3),
-                Constraint::Length(4),
-            ])
-            .split(f.area());
+    fn project_line(&self, project_index: usize, is_selected: bool) -> Line<'_> {
+        let project = &self.projects[project_index];
 
-        let title = Paragraph::new(" Project Switcher")
-            .style(
-                Style::default()
-                    .fg(PRIMARY_COLOR)
-                    .add_modifier(Modifier::BOLD),
-            )
-            .alignment(Alignment::Center);
-        f.render_widget(title, main_chunks[0]);
+        let (source_icon, source_color, source_label) = source_style(&project.source, &self.theme);
 
-        let search_placeholder = if self.input.is_empty() {
-            "Type to search projects..."
+        let status_indicator = if project.dirty == Some(true) {
+            (
+                self.config.dirty_marker.as_str(),
+                self.theme.dirty,
+                "Uncommitted changes",
+            )
+        } else if project.source == crate::models::ProjectSource::GitHub
+            || project.source == crate::models::ProjectSource::GitLab
+        {
+            if self.project_exists_cache[project_index] {
+                ("✓", self.theme.success, "Cloned")
+            } else {
+                ("⚡", self.theme.warning, "Remote")
+            }
         } else {
-            ""
+            ("●", self.theme.success, "Available")
         };
 
-        let search_content = if self.input.is_empty() {
-            Text::from(vec![
-                Line::from(vec![Span::styled("", Style::default())]),
+        let time_str = project
+            .time_label(self.config.relative_times, "%m/%d %H:%M")
+            .map(|time| format!(" • {}", time))
+            .unwrap_or_default();
+
+        let type_icon = self
+            .config
+            .type_icon(project)
+            .map(|icon| format!("{} ", icon))
+            .unwrap_or_default();
+
+        let mut line_spans = vec![
+            Span::styled("  ", Style::default()),
+            Span::styled(source_icon, Style::default().fg(source_color)),
+            Span::styled("  ", Style::default()),
+        ];
+
+        // Archived projects stay listed but fade into the background
+        let label_style = if project.archived {
+            Style::default()
+                .fg(self.theme.text_muted)
+                .add_modifier(Modifier::DIM)
+        } else {
+            Style::default().fg(self.theme.text_primary)
+        };
+
+        if is_selected {
+            line_spans.extend(vec![
+                Span::styled(
+                    "▶ ",
+                    Style::default()
+                        .fg(self.theme.accent)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(type_icon, Style::default()),
+                Span::styled(
+                    self.display_label(project_index),
+                    label_style.add_modifier(Modifier::BOLD),
+                ),
+            ]);
+        } else {
+            line_spans.extend(vec![
+                Span::styled("  ", Style::default()),
+                Span::styled(type_icon, Style::default()),
+                Span::styled(self.display_label(project_index), label_style),
+            ]);
+        }
+
+        if self.show_paths {
+            line_spans.push(Span::styled(
+                format!(
+                    "  {}",
+                    abbreviate_home(&project.path, dirs::home_dir().as_deref())
+                ),
+                Style::default().fg(self.theme.text_muted),
+            ));
+        }
+
+        line_spans.extend(vec![
+            Span::styled(" ", Style::default()),
+            Span::styled(status_indicator.0, Style::default().fg(status_indicator.1)),
+            Span::styled(time_str, Style::default().fg(self.theme.text_secondary)),
+        ]);
+
+        if self.current_project == Some(project_index) {
+            line_spans.push(Span::styled(
+                " (current)",
+                Style::default()
+                    .fg(self.theme.accent)
+                    .add_modifier(Modifier::ITALIC),
+            ));
+        }
+
+        if is_selected {
+            line_spans.extend(vec![
+                Span::styled(" ", Style::default()),
+                Span::styled(
+                    format!("[{}]", source_label),
+                    Style::default()
+                        .fg(source_color)
+                        .add_modifier(Modifier::ITALIC),
+                ),
+            ]);
+        }
+
+        Line::from(line_spans)
+    }
+
+    fn list_items(&self) -> Vec<ListItem<'_>> {
+        self.display_rows()
+            .into_iter()
+            .map(|row| match row {
+                DisplayRow::Header { source, count } => {
+                    let (source_icon, source_color, source_label) =
+                        source_style(&source, &self.theme);
+                    ListItem::new(Line::from(vec![
+                        Span::styled(format!(" {} ", source_icon), Style::default()),
+                        Span::styled(
+                            format!("{} ({})", source_label, count),
+                            Style::default()
+                                .fg(source_color)
+                                .add_modifier(Modifier::BOLD),
+                        ),
+                    ]))
+                }
+                DisplayRow::Project { filtered_index } => {
+                    let project_index = self.filtered_projects[filtered_index].0;
+                    let is_selected = filtered_index == self.selected_index;
+
+                    let item_style = if is_selected {
+                        Style::default()
+                            .bg(self.theme.surface)
+                            .fg(self.theme.text_primary)
+                    } else {
+                        Style::default()
+                    };
+
+                    ListItem::new(self.project_line(project_index, is_selected)).style(item_style)
+                }
+            })
+            .collect()
+    }
+
+    /// Bare search line plus list for terminals too small for the full layout
+    fn draw_compact(&self, f: &mut Frame) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(1), Constraint::Min(0)])
+            .split(f.area());
+
+        let prompt = match self.rename_buffer {
+            Some(ref buffer) => Line::from(vec![
+                Span::styled("Rename: ", Style::default().fg(self.theme.accent)),
+                Span::styled(buffer, Style::default().fg(self.theme.text_primary)),
+            ]),
+            None => Line::from(vec![
+                Span::styled("> ", Style::default().fg(self.theme.accent)),
+                Span::styled(&self.input, Style::default().fg(self.theme.text_primary)),
+            ]),
+        };
+        f.render_widget(Paragraph::new(prompt), chunks[0]);
+
+        let selected_row = self.display_rows().iter().position(|row| {
+            matches!(row, DisplayRow::Project { filtered_index } if *filtered_index == self.selected_index)
+        });
+        let mut state = ListState::default().with_selected(selected_row);
+        f.render_stateful_widget(List::new(self.list_items()), chunks[1], &mut state);
+    }
+
+    fn draw(&self, f: &mut Frame) {
+        let area = f.area();
+        if area.height < MIN_FULL_LAYOUT_HEIGHT || area.width < MIN_FULL_LAYOUT_WIDTH {
+            self.draw_compact(f);
+            return;
+        }
+
+        let main_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .margin(1)
+            .constraints([
+                Constraint::Length(1),
+                Constraint::Length(3),
+                Constraint::Length(1),
+                Constraint::Min(10),
+                Constraint::Length(1),
+                Constraint::Length(3),
+                Constraint::Length(4),
+            ])
+            .split(f.area());
+
+        let title = Paragraph::new(" Project Switcher")
+            .style(
+                Style::default()
+                    .fg(self.theme.primary)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .alignment(Alignment::Center);
+        f.render_widget(title, main_chunks[0]);
+
+        let search_placeholder = if self.input.is_empty() {
+            "Type to search projects..."
+        } else {
+            ""
+        };
+
+        let search_content = if let Some(ref buffer) = self.rename_buffer {
+            Text::from(vec![
+                Line::from(vec![
+                    Span::styled("✏️  Rename: ", Style::default().fg(self.theme.accent)),
+                    Span::styled(buffer, Style::default().fg(self.theme.text_primary)),
+                    Span::styled("│", Style::default().fg(self.theme.primary).slow_blink()),
+                ]),
+                Line::from(vec![Span::styled(
+                    "Enter to save, empty to reset, Esc to cancel",
+                    Style::default().fg(self.theme.text_muted).italic(),
+                )]),
+            ])
+        } else if self.input.is_empty() {
+            Text::from(vec![
+                Line::from(vec![Span::styled("", Style::default())]),
                 Line::from(vec![Span::styled(
                     search_placeholder,
-                    Style::default().fg(TEXT_MUTED).italic(),
+                    Style::default().fg(self.theme.text_muted).italic(),
                 )]),
             ])
         } else {
+            let (before_cursor, after_cursor) =
+                self.input.split_at(self.input_offset(self.cursor()));
             Text::from(vec![
                 Line::from(vec![
-                    Span::styled("🔍 ", Style::default().fg(ACCENT_COLOR)),
-                    Span::styled(&self.input, Style::default().fg(TEXT_PRIMARY)),
-                    Span::styled("│", Style::default().fg(PRIMARY_COLOR).slow_blink()),
+                    Span::styled("🔍 ", Style::default().fg(self.theme.accent)),
+                    Span::styled(before_cursor, Style::default().fg(self.theme.text_primary)),
+                    Span::styled("│", Style::default().fg(self.theme.primary).slow_blink()),
+                    Span::styled(after_cursor, Style::default().fg(self.theme.text_primary)),
                 ]),
                 Line::from(vec![Span::styled("", Style::default())]),
             ])
@@ -358,113 +1285,26 @@ impl TuiApp {
                 .borders(Borders::ALL)
                 .border_type(BorderType::Rounded)
                 .border_style(Style::default().fg(if self.input.is_empty() {
-                    TEXT_MUTED
+                    self.theme.text_muted
                 } else {
-                    PRIMARY_COLOR
+                    self.theme.primary
                 }))
                 .title(Line::from(vec![
                     Span::styled(" ", Style::default()),
                     Span::styled(
                         "Search",
                         Style::default()
-                            .fg(TEXT_PRIMARY)
+                            .fg(self.theme.text_primary)
                             .add_modifier(Modifier::BOLD),
                     ),
                     Span::styled(" ", Style::default()),
                 ]))
-                .title_style(Style::default().fg(TEXT_PRIMARY))
+                .title_style(Style::default().fg(self.theme.text_primary))
                 .padding(Padding::horizontal(2)),
         );
         f.render_widget(search_box, main_chunks[1]);
 
-        let items: Vec<ListItem> = self
-            .filtered_projects
-            .iter()
-            .enumerate()
-            .map(|(i, (project_index, _score))| {
-                let project = &self.projects[*project_index];
-                let is_selected = i == self.selected_index;
-
-                let (source_icon, source_color, source_label) = match project.source {
-                    crate::models::ProjectSource::Local => ("📂", SUCCESS_COLOR, "Local"),
-                    crate::models::ProjectSource::Cursor => ("🎯", PRIMARY_COLOR, "Cursor"),
-                    crate::models::ProjectSource::GitHub => ("🐙", SECONDARY_COLOR, "GitHub"),
-                    crate::models::ProjectSource::GitLab => ("🦊", ACCENT_COLOR, "GitLab"),
-                };
-
-                let status_indicator = if project.source == crate::models::ProjectSource::GitHub
-                    || project.source == crate::models::ProjectSource::GitLab
-                {
-                    if self.project_exists_cache[*project_index] {
-                        ("✓", SUCCESS_COLOR, "Cloned")
-                    } else {
-                        ("⚡", WARNING_COLOR, "Remote")
-                    }
-                } else {
-                    ("●", SUCCESS_COLOR, "Available")
-                };
-
-                let time_str = if let Some(timestamp) = project.last_modified {
-                    format!(" • {}", timestamp.format("%m/%d %H:%M"))
-                } else {
-                    String::new()
-                };
-
-                let mut line_spans = vec![
-                    Span::styled("  ", Style::default()),
-                    Span::styled(source_icon, Style::default().fg(source_color)),
-                    Span::styled("  ", Style::default()),
-                ];
-
-                if is_selected {
-                    line_spans.extend(vec![
-                        Span::styled(
-                            "▶ ",
-                            Style::default()
-                                .fg(ACCENT_COLOR)
-                                .add_modifier(Modifier::BOLD),
-                        ),
-                        Span::styled(
-                            &project.name,
-                            Style::default()
-                                .fg(TEXT_PRIMARY)
-                                .add_modifier(Modifier::BOLD),
-                        ),
-                    ]);
-                } else {
-                    line_spans.extend(vec![
-                        Span::styled("  ", Style::default()),
-                        Span::styled(&project.name, Style::default().fg(TEXT_PRIMARY)),
-                    ]);
-                }
-
-                line_spans.extend(vec![
-                    Span::styled(" ", Style::default()),
-                    Span::styled(status_indicator.0, Style::default().fg(status_indicator.1)),
-                    Span::styled(time_str, Style::default().fg(TEXT_SECONDARY)),
-                ]);
-
-                if is_selected {
-                    line_spans.extend(vec![
-                        Span::styled(" ", Style::default()),
-                        Span::styled(
-                            format!("[{}]", source_label),
-                            Style::default()
-                                .fg(source_color)
-                                .add_modifier(Modifier::ITALIC),
-                        ),
-                    ]);
-                }
-
-                let item_style = if is_selected {
-                    Style::default().bg(SURFACE_COLOR).fg(TEXT_PRIMARY)
-                } else {
-                    Style::default()
-                };
-
-                ListItem::new(Line::from(line_spans)).style(item_style)
-            })
-            .collect();
+        let items = self.list_items();
 
         let projects_title = format!(
             " Projects ({}/{}) ",
@@ -472,75 +1312,102 @@ impl TuiApp {
             self.projects.len()
         );
 
-        let projects_list = List::new(items).block(
-            Block::default()
-                .borders(Borders::ALL)
-                .border_type(BorderType::Rounded)
-                .border_style(Style::default().fg(TEXT_MUTED))
-                .title(Line::from(vec![Span::styled(
-                    projects_title,
-                    Style::default()
-                        .fg(TEXT_PRIMARY)
-                        .add_modifier(Modifier::BOLD),
-                )]))
-                .padding(Padding::horizontal(1)),
-        );
+        let mut projects_block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(self.theme.text_muted))
+            .title(Line::from(vec![Span::styled(
+                projects_title,
+                Style::default()
+                    .fg(self.theme.text_primary)
+                    .add_modifier(Modifier::BOLD),
+            )]))
+            .padding(Padding::horizontal(1));
+
+        if let Some(notice) = self.truncation_notice() {
+            projects_block = projects_block.title_bottom(
+                Line::from(Span::styled(
+                    notice,
+                    Style::default().fg(self.theme.warning),
+                ))
+                .right_aligned(),
+            );
+        }
+
+        let projects_list = List::new(items).block(projects_block);
 
         f.render_widget(projects_list, main_chunks[3]);
 
         let github_status = self.get_github_status();
         let github_status_color = if github_status.contains("✅") {
-            SUCCESS_COLOR
+            self.theme.success
         } else if github_status.contains("❌") {
-            ERROR_COLOR
+            self.theme.error
         } else {
-            WARNING_COLOR
+            self.theme.warning
         };
 
         let gitlab_status = self.get_gitlab_status();
         let gitlab_status_color = if gitlab_status.contains("✅") {
-            SUCCESS_COLOR
+            self.theme.success
         } else if gitlab_status.contains("❌") {
-            ERROR_COLOR
+            self.theme.error
         } else {
-            WARNING_COLOR
+            self.theme.warning
         };
 
         let mut status_spans = vec![
-            Span::styled("🐙 GitHub: ", Style::default().fg(TEXT_SECONDARY)),
+            Span::styled(
+                "🐙 GitHub: ",
+                Style::default().fg(self.theme.text_secondary),
+            ),
             Span::styled(github_status, Style::default().fg(github_status_color)),
-            Span::styled("  │  ", Style::default().fg(TEXT_MUTED)),
-            Span::styled("🦊 GitLab: ", Style::default().fg(TEXT_SECONDARY)),
+            Span::styled("  │  ", Style::default().fg(self.theme.text_muted)),
+            Span::styled(
+                "🦊 GitLab: ",
+                Style::default().fg(self.theme.text_secondary),
+            ),
             Span::styled(gitlab_status, Style::default().fg(gitlab_status_color)),
-            Span::styled("  │  ", Style::default().fg(TEXT_MUTED)),
-            Span::styled("📊 Total: ", Style::default().fg(TEXT_SECONDARY)),
+            Span::styled("  │  ", Style::default().fg(self.theme.text_muted)),
+            Span::styled("📊 Total: ", Style::default().fg(self.theme.text_secondary)),
             Span::styled(
                 format!("{}", self.projects.len()),
                 Style::default()
-                    .fg(ACCENT_COLOR)
+                    .fg(self.theme.accent)
                     .add_modifier(Modifier::BOLD),
             ),
-            Span::styled("  │  ", Style::default().fg(TEXT_MUTED)),
-            Span::styled("🔍 Shown: ", Style::default().fg(TEXT_SECONDARY)),
+            Span::styled("  │  ", Style::default().fg(self.theme.text_muted)),
+            Span::styled("🔍 Shown: ", Style::default().fg(self.theme.text_secondary)),
             Span::styled(
                 format!("{}", self.filtered_projects.len()),
                 Style::default()
-                    .fg(PRIMARY_COLOR)
+                    .fg(self.theme.primary)
                     .add_modifier(Modifier::BOLD),
             ),
         ];
 
         // Add refresh indicator if refreshing
         if self.is_refreshing {
-            status_spans.push(Span::styled("  │  ", Style::default().fg(TEXT_MUTED)));
+            status_spans.push(Span::styled(
+                "  │  ",
+                Style::default().fg(self.theme.text_muted),
+            ));
             status_spans.push(Span::styled(
                 "🔄 Refreshing...",
                 Style::default()
-                    .fg(ACCENT_COLOR)
+                    .fg(self.theme.accent)
                     .add_modifier(Modifier::BOLD),
             ));
         }
 
+        if let Some(ref notice) = self.notice {
+            status_spans.push(Span::styled(
+                "  │  ",
+                Style::default().fg(self.theme.text_muted),
+            ));
+            status_spans.push(Span::styled(notice, Style::default().fg(self.theme.error)));
+        }
+
         let status_content = Text::from(vec![Line::from(status_spans)]);
 
         let status_bar = Paragraph::new(status_content)
@@ -548,9 +1415,9 @@ impl TuiApp {
                 Block::default()
                     .borders(Borders::ALL)
                     .border_type(BorderType::Rounded)
-                    .border_style(Style::default().fg(TEXT_MUTED))
+                    .border_style(Style::default().fg(self.theme.text_muted))
                     .title(" Status ")
-                    .title_style(Style::default().fg(TEXT_SECONDARY))
+                    .title_style(Style::default().fg(self.theme.text_secondary))
                     .padding(Padding::horizontal(2)),
             )
             .alignment(Alignment::Center);
@@ -560,24 +1427,58 @@ impl TuiApp {
             Span::styled(
                 "↑↓",
                 Style::default()
-                    .fg(ACCENT_COLOR)
+                    .fg(self.theme.accent)
                     .add_modifier(Modifier::BOLD),
             ),
-            Span::styled(" Navigate  ", Style::default().fg(TEXT_SECONDARY)),
+            Span::styled(
+                " Navigate  ",
+                Style::default().fg(self.theme.text_secondary),
+            ),
             Span::styled(
                 "Enter",
                 Style::default()
-                    .fg(SUCCESS_COLOR)
+                    .fg(self.theme.success)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(" Select  ", Style::default().fg(self.theme.text_secondary)),
+            Span::styled(
+                "⇧/Alt+Enter",
+                Style::default()
+                    .fg(self.theme.success)
                     .add_modifier(Modifier::BOLD),
             ),
-            Span::styled(" Select  ", Style::default().fg(TEXT_SECONDARY)),
+            Span::styled(
+                " Add to window  ",
+                Style::default().fg(self.theme.text_secondary),
+            ),
+            Span::styled(
+                "^G",
+                Style::default()
+                    .fg(self.theme.primary)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(" Group  ", Style::default().fg(self.theme.text_secondary)),
+            Span::styled(
+                "^P",
+                Style::default()
+                    .fg(self.theme.primary)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(" Paths  ", Style::default().fg(self.theme.text_secondary)),
+            Span::styled(
+                "F2",
+                Style::default()
+                    .fg(self.theme.primary)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(" Rename  ", Style::default().fg(self.theme.text_secondary)),
             Span::styled(
                 "Esc/q",
                 Style::default()
-                    .fg(ERROR_COLOR)
+                    .fg(self.theme.error)
                     .add_modifier(Modifier::BOLD),
             ),
-            Span::styled(" Quit", Style::default().fg(TEXT_SECONDARY)),
+            Span::styled(" Quit", Style::default().fg(self.theme.text_secondary)),
         ])]);
 
         let help_box = Paragraph::new(help_content)
@@ -585,9 +1486,9 @@ impl TuiApp {
                 Block::default()
                     .borders(Borders::ALL)
                     .border_type(BorderType::Rounded)
-                    .border_style(Style::default().fg(TEXT_MUTED))
+                    .border_style(Style::default().fg(self.theme.text_muted))
                     .title(" Help ")
-                    .title_style(Style::default().fg(TEXT_SECONDARY))
+                    .title_style(Style::default().fg(self.theme.text_secondary))
                     .padding(Padding::horizontal(2)),
             )
             .alignment(Alignment::Center);
@@ -596,31 +1497,111 @@ impl TuiApp {
 }
 
 #[allow(dead_code)]
-pub fn run_interactive_mode(projects: Vec<Project>) -> Result<Option<Project>> {
-    run_interactive_mode_with_receiver(projects, None)
+pub fn run_interactive_mode(projects: Vec<Project>, config: &Config) -> Result<Option<Selection>> {
+    run_interactive_mode_with_receiver(projects, None, config)
 }
 
 pub fn run_interactive_mode_with_receiver(
     projects: Vec<Project>,
-    update_receiver: Option<Receiver<ProjectList>>,
-) -> Result<Option<Project>> {
+    update_receiver: Option<Receiver<Refresh>>,
+    config: &Config,
+) -> Result<Option<Selection>> {
     enable_raw_mode()?;
+    // Declared first so it drops last, after the terminal is restored
+    #[cfg(unix)]
+    let _signals = SignalGuard::activate()?;
+    let _guard = TerminalGuard::new(restore_terminal);
+
     let mut stdout = io::stdout();
     execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
-    let result = TuiApp::run_interactive_with_receiver(projects, update_receiver, &mut terminal);
+    TuiApp::run_interactive_with_receiver(projects, update_receiver, config, &mut terminal)
+}
+
+/// Runs `restore` when dropped, so the terminal is put back on every exit
+/// path out of the TUI, including errors and unwinding panics
+struct TerminalGuard<F: FnMut()> {
+    restore: F,
+}
+
+impl<F: FnMut()> TerminalGuard<F> {
+    fn new(restore: F) -> Self {
+        Self { restore }
+    }
+}
+
+impl<F: FnMut()> Drop for TerminalGuard<F> {
+    fn drop(&mut self) {
+        (self.restore)();
+    }
+}
 
-    disable_raw_mode()?;
-    execute!(
-        terminal.backend_mut(),
+fn restore_terminal() {
+    let _ = disable_raw_mode();
+    let _ = execute!(
+        io::stdout(),
         LeaveAlternateScreen,
-        DisableMouseCapture
-    )?;
-    terminal.show_cursor()?;
+        DisableMouseCapture,
+        crossterm::cursor::Show
+    );
+}
+
+/// Whether the TUI owns the terminal, so a signal has to restore it
+#[cfg(unix)]
+static TUI_ACTIVE: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+
+/// Marks the TUI active while alive. Drop guards don't run when a signal
+/// terminates the process, so a watcher thread restores the terminal first.
+/// The handlers stay registered for the rest of the process, because
+/// unregistering them doesn't bring the default dispositions back; once the
+/// TUI is gone they act like the defaults, so Ctrl-C still stops a clone or
+/// a waiting editor afterwards.
+#[cfg(unix)]
+struct SignalGuard;
+
+#[cfg(unix)]
+impl SignalGuard {
+    fn activate() -> Result<Self> {
+        install_signal_watcher()?;
+        TUI_ACTIVE.store(true, std::sync::atomic::Ordering::SeqCst);
+        Ok(Self)
+    }
+}
 
-    result
+#[cfg(unix)]
+impl Drop for SignalGuard {
+    fn drop(&mut self) {
+        TUI_ACTIVE.store(false, std::sync::atomic::Ordering::SeqCst);
+    }
+}
+
+/// Register the SIGINT, SIGTERM and SIGHUP watcher once per process
+#[cfg(unix)]
+fn install_signal_watcher() -> Result<()> {
+    use signal_hook::consts::{SIGHUP, SIGINT, SIGTERM};
+    static INSTALLED: std::sync::Mutex<bool> = std::sync::Mutex::new(false);
+
+    let mut installed = INSTALLED.lock().unwrap_or_else(|e| e.into_inner());
+    if *installed {
+        return Ok(());
+    }
+    let mut signals = signal_hook::iterator::Signals::new([SIGINT, SIGTERM, SIGHUP])
+        .context("Failed to install signal handlers")?;
+    *installed = true;
+
+    std::thread::spawn(move || {
+        for signal in signals.forever() {
+            if TUI_ACTIVE.load(std::sync::atomic::Ordering::SeqCst) {
+                restore_terminal();
+                std::process::exit(128 + signal);
+            }
+            let _ = signal_hook::low_level::emulate_default_handler(signal);
+        }
+    });
+
+    Ok(())
 }
 
 #[cfg(test)]
@@ -639,6 +1620,12 @@ mod tests {
                 last_modified: Some(Utc::now()),
                 github_url: None,
                 gitlab_url: None,
+                host: None,
+                project_type: None,
+                size_bytes: None,
+                qualified_name: None,
+                dirty: None,
+                archived: false,
             },
             Project {
                 name: "cool-app".to_string(),
@@ -647,6 +1634,12 @@ mod tests {
                 last_modified: Some(Utc::now()),
                 github_url: None,
                 gitlab_url: None,
+                host: None,
+                project_type: None,
+                size_bytes: None,
+                qualified_name: None,
+                dirty: None,
+                archived: false,
             },
             Project {
                 name: "my-website".to_string(),
@@ -655,6 +1648,12 @@ mod tests {
                 last_modified: Some(Utc::now()),
                 github_url: None,
                 gitlab_url: None,
+                host: None,
+                project_type: None,
+                size_bytes: None,
+                qualified_name: None,
+                dirty: None,
+                archived: false,
             },
             Project {
                 name: "switchr".to_string(),
@@ -663,10 +1662,57 @@ mod tests {
                 last_modified: Some(Utc::now()),
                 github_url: None,
                 gitlab_url: None,
+                host: None,
+                project_type: None,
+                size_bytes: None,
+                qualified_name: None,
+                dirty: None,
+                archived: false,
             },
         ]
     }
 
+    #[cfg(unix)]
+    #[test]
+    fn test_sigint_terminates_after_tui_returns() {
+        use signal_hook::consts::SIGINT;
+        use std::os::unix::process::ExitStatusExt;
+
+        const TEST: &str = "tui::tests::test_sigint_terminates_after_tui_returns";
+        if std::env::var_os("SW_SIGNAL_TEST_CHILD").is_some() {
+            drop(SignalGuard::activate().unwrap());
+            signal_hook::low_level::raise(SIGINT).unwrap();
+            std::thread::sleep(std::time::Duration::from_secs(10));
+            std::process::exit(0);
+        }
+
+        let status = std::process::Command::new(std::env::current_exe().unwrap())
+            .args(["--exact", TEST, "--test-threads=1"])
+            .env("SW_SIGNAL_TEST_CHILD", "1")
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .status()
+            .unwrap();
+        assert_eq!(status.signal(), Some(SIGINT));
+    }
+
+    #[test]
+    fn test_terminal_guard_restores_on_drop() {
+        let restored = std::cell::Cell::new(0);
+        {
+            let _guard = TerminalGuard::new(|| restored.set(restored.get() + 1));
+            assert_eq!(restored.get(), 0);
+        }
+        assert_eq!(restored.get(), 1);
+
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            let _guard = TerminalGuard::new(|| restored.set(restored.get() + 1));
+            panic!("TUI failed");
+        }));
+        assert!(result.is_err());
+        assert_eq!(restored.get(), 2);
+    }
+
     #[test]
     fn test_new_tui_app() {
         let projects = create_test_projects();
@@ -679,6 +1725,59 @@ mod tests {
         assert!(app.selected_project.is_none());
     }
 
+    #[test]
+    fn test_background_refresh_replaces_rescanned_sources() {
+        let mut projects = create_test_projects();
+        projects.push(Project::new_github(
+            "remote-only".to_string(),
+            "/gh/remote-only",
+            "https://github.com/user/remote-only".to_string(),
+        ));
+        let mut app = TuiApp::new(projects);
+
+        // Only the local scanner finished; cool-app and my-website are gone
+        app.merge_refreshed(Refresh {
+            projects: ProjectList::from_projects(vec![
+                Project::new_local("fresh".to_string(), "/path/to/fresh")
+                    .with_last_modified(Utc::now()),
+                Project::new_local("switchr".to_string(), "/path/to/switchr"),
+                Project::new_local("awesome-project".to_string(), "/path/to/awesome-project"),
+            ]),
+            scanned_sources: vec![ProjectSource::Local],
+        });
+
+        let mut names: Vec<&str> = app.projects.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names[0], "fresh");
+        names.sort();
+        assert_eq!(
+            names,
+            vec!["awesome-project", "fresh", "remote-only", "switchr"]
+        );
+    }
+
+    #[test]
+    fn test_background_refresh_merges_by_source_priority() {
+        let mut app = TuiApp::new(create_test_projects());
+
+        app.merge_refreshed(Refresh {
+            projects: ProjectList::from_projects(vec![Project::new_github(
+                "switchr".to_string(),
+                "/path/to/switchr",
+                "https://github.com/user/switchr".to_string(),
+            )]),
+            scanned_sources: vec![ProjectSource::GitHub],
+        });
+
+        assert_eq!(app.projects.len(), 4);
+        let switchr: Vec<_> = app
+            .projects
+            .iter()
+            .filter(|project| project.name == "switchr")
+            .collect();
+        assert_eq!(switchr.len(), 1);
+        assert_eq!(switchr[0].source, ProjectSource::Local);
+    }
+
     #[test]
     fn test_initial_filtered_projects() {
         let projects = create_test_projects();
@@ -825,6 +1924,12 @@ mod tests {
                 last_modified: Some(Utc::now()),
                 github_url: None,
                 gitlab_url: None,
+                host: None,
+                project_type: None,
+                size_bytes: None,
+                qualified_name: None,
+                dirty: None,
+                archived: false,
             });
         }
 
@@ -832,4 +1937,812 @@ mod tests {
 
         assert_eq!(app.filtered_projects.len(), 20);
     }
+
+    #[test]
+    fn test_query_below_min_length_shows_default_list() {
+        let projects = create_test_projects();
+        let config = Config {
+            min_query_len: 3,
+            ..Config::default()
+        };
+        let mut app = TuiApp::new_with_receiver(projects, None, &config);
+
+        app.input = "sw".to_string();
+        app.update_filtered_projects();
+
+        assert_eq!(app.filtered_projects.len(), 4);
+        assert_eq!(app.filtered_projects[0].0, 0);
+
+        app.input = "swi".to_string();
+        app.update_filtered_projects();
+
+        assert_eq!(app.filtered_projects.len(), 1);
+        assert_eq!(app.filtered_projects[0].0, 3);
+    }
+
+    #[test]
+    fn test_keystroke_burst_is_debounced() {
+        let projects = create_test_projects();
+        let mut app = TuiApp::new(projects);
+
+        for c in "switchr".chars() {
+            app.input.push(c);
+            app.schedule_search();
+            app.apply_pending_search(app.last_keystroke);
+        }
+
+        assert!(app.search_pending);
+        assert_eq!(app.filtered_projects.len(), 4);
+
+        app.apply_pending_search(app.last_keystroke + SEARCH_DEBOUNCE);
+
+        assert!(!app.search_pending);
+        assert_eq!(app.filtered_projects.len(), 1);
+        assert_eq!(app.filtered_projects[0].0, 3);
+    }
+
+    fn create_mixed_source_projects() -> Vec<Project> {
+        vec![
+            Project::new_github(
+                "gh-one".to_string(),
+                "/remote/gh-one",
+                "https://github.com/user/gh-one".to_string(),
+            ),
+            Project::new_local("local-one".to_string(), "/path/to/local-one"),
+            Project::new_github(
+                "gh-two".to_string(),
+                "/remote/gh-two",
+                "https://github.com/user/gh-two".to_string(),
+            ),
+            Project::new_local("local-two".to_string(), "/path/to/local-two"),
+            Project::new_cursor("cursor-one".to_string(), "/path/to/cursor-one"),
+        ]
+    }
+
+    #[test]
+    fn test_grouped_headers_show_group_counts() {
+        let mut app = TuiApp::new(create_mixed_source_projects());
+        app.toggle_grouped();
+
+        let headers: Vec<(ProjectSource, usize)> = app
+            .display_rows()
+            .into_iter()
+            .filter_map(|row| match row {
+                DisplayRow::Header { source, count } => Some((source, count)),
+                DisplayRow::Project { .. } => None,
+            })
+            .collect();
+
+        assert_eq!(
+            headers,
+            vec![
+                (ProjectSource::Local, 2),
+                (ProjectSource::Cursor, 1),
+                (ProjectSource::GitHub, 2),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_grouped_header_counts_include_truncated_matches() {
+        let projects: Vec<Project> = (0..30)
+            .map(|i| Project::new_local(format!("local-{}", i), format!("/code/local-{}", i)))
+            .chain((0..5).map(|i| {
+                Project::new_github(
+                    format!("remote-{}", i),
+                    format!("/gh/remote-{}", i),
+                    format!("https://github.com/user/remote-{}", i),
+                )
+            }))
+            .collect();
+        let mut app = TuiApp::new(projects);
+        app.toggle_grouped();
+
+        let headers: Vec<(ProjectSource, usize)> = app
+            .display_rows()
+            .into_iter()
+            .filter_map(|row| match row {
+                DisplayRow::Header { source, count } => Some((source, count)),
+                DisplayRow::Project { .. } => None,
+            })
+            .collect();
+
+        assert_eq!(app.filtered_projects.len(), MAX_DISPLAYED_PROJECTS);
+        assert_eq!(headers, vec![(ProjectSource::Local, 30)]);
+    }
+
+    #[test]
+    fn test_grouped_navigation_skips_headers() {
+        let mut app = TuiApp::new(create_mixed_source_projects());
+        app.toggle_grouped();
+        app.selected_index = 0;
+
+        let rows = app.display_rows();
+        assert!(matches!(rows[0], DisplayRow::Header { .. }));
+
+        app.move_selection_down();
+        app.move_selection_down();
+        assert_eq!(app.get_selected_project().unwrap().name, "cursor-one");
+
+        let selected_row = rows
+            .iter()
+            .position(|row| *row == DisplayRow::Project { filtered_index: 2 })
+            .unwrap();
+        assert!(matches!(rows[selected_row - 1], DisplayRow::Header { .. }));
+
+        app.move_selection_down();
+        assert_eq!(
+            app.get_selected_project().unwrap().source,
+            ProjectSource::GitHub
+        );
+    }
+
+    #[test]
+    fn test_grouped_filtering_stays_within_groups() {
+        let mut app = TuiApp::new(create_mixed_source_projects());
+        app.toggle_grouped();
+
+        app.input = "one".to_string();
+        app.update_filtered_projects();
+
+        let sources: Vec<ProjectSource> = app
+            .filtered_projects
+            .iter()
+            .map(|(i, _)| app.projects[*i].source.clone())
+            .collect();
+        assert_eq!(
+            sources,
+            vec![
+                ProjectSource::Local,
+                ProjectSource::Cursor,
+                ProjectSource::GitHub
+            ]
+        );
+    }
+
+    fn line_text(line: &Line) -> String {
+        line.spans
+            .iter()
+            .map(|span| span.content.as_ref())
+            .collect()
+    }
+
+    #[test]
+    fn test_truncation_notice_reports_total_matches() {
+        let projects: Vec<Project> = (0..87)
+            .map(|i| Project::new_local(format!("service-{}", i), format!("/path/service-{}", i)))
+            .chain(std::iter::once(Project::new_local(
+                "website".to_string(),
+                "/path/website",
+            )))
+            .collect();
+        let mut app = TuiApp::new(projects);
+
+        app.input = "service".to_string();
+        app.update_filtered_projects();
+        assert_eq!(app.filtered_projects.len(), MAX_DISPLAYED_PROJECTS);
+        assert_eq!(app.total_matches, 87);
+        assert_eq!(
+            app.truncation_notice().as_deref(),
+            Some(" showing 20 of 87 matches — refine your search ")
+        );
+        assert!(render_to_buffer(&app, 100, 40).contains("showing 20 of 87 matches"));
+
+        app.input = "website".to_string();
+        app.update_filtered_projects();
+        assert_eq!(app.total_matches, 1);
+        assert!(app.truncation_notice().is_none());
+    }
+
+    #[test]
+    fn test_type_icon_precedes_name() {
+        let rust_project = Project::new_local("crate".to_string(), "/path/to/crate")
+            .with_project_type(Some("rust".to_string()));
+        let plain_project = Project::new_local("notes".to_string(), "/path/to/notes");
+        let app = TuiApp::new(vec![rust_project, plain_project]);
+
+        assert!(line_text(&app.project_line(0, true)).contains("▶ 🦀 crate"));
+        assert!(line_text(&app.project_line(0, false)).contains("🦀 crate"));
+        assert!(!line_text(&app.project_line(1, false)).contains("🦀"));
+    }
+
+    #[test]
+    fn test_recency_weight_favors_recent_near_matches() {
+        let now = Utc::now();
+        let projects = vec![
+            Project::new_local("payments".to_string(), "/path/payments")
+                .with_last_modified(now - chrono::Duration::days(400)),
+            Project::new_local("payment-svc".to_string(), "/path/payment-svc")
+                .with_last_modified(now - chrono::Duration::days(1)),
+        ];
+
+        let ranked = |recency_weight: i64| {
+            let config = Config {
+                recency_weight,
+                ..Config::default()
+            };
+            let mut app = TuiApp::new_with_receiver(projects.clone(), None, &config);
+            app.input = "payments".to_string();
+            app.update_filtered_projects();
+            matched_names(&app)
+                .into_iter()
+                .map(str::to_string)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(ranked(0), vec!["payments", "payment-svc"]);
+        assert_eq!(ranked(20), vec!["payment-svc", "payments"]);
+
+        // An empty query keeps the list's recency order
+        let config = Config::default();
+        let app = TuiApp::new_with_receiver(projects.clone(), None, &config);
+        assert_eq!(matched_names(&app), vec!["payments", "payment-svc"]);
+    }
+
+    #[test]
+    fn test_recency_bonus_decays_with_age() {
+        let now = Utc::now();
+        let aged = |days: i64| {
+            Project::new_local("p".to_string(), "/p")
+                .with_last_modified(now - chrono::Duration::days(days))
+        };
+        assert_eq!(recency_bonus(&aged(0), 20, now), 20);
+        assert_eq!(recency_bonus(&aged(7), 20, now), 10);
+        assert_eq!(recency_bonus(&aged(63), 20, now), 2);
+        assert_eq!(recency_bonus(&aged(7), 0, now), 0);
+        assert_eq!(
+            recency_bonus(&Project::new_local("p".to_string(), "/p"), 20, now),
+            0
+        );
+    }
+
+    #[test]
+    fn test_boundary_match_ranks_first() {
+        let projects = vec![
+            Project::new_local("wallpaper-tool".to_string(), "/path/wallpaper-tool"),
+            Project::new_local("payment-orders".to_string(), "/path/payment-orders"),
+        ];
+
+        for matcher in [MatcherKind::Skim, MatcherKind::Simple] {
+            let config = Config {
+                matcher,
+                ..Config::default()
+            };
+            let mut app = TuiApp::new_with_receiver(projects.clone(), None, &config);
+            app.input = "po".to_string();
+            app.update_filtered_projects();
+            assert_eq!(
+                matched_names(&app),
+                vec!["payment-orders", "wallpaper-tool"]
+            );
+
+            let unboosted = Config {
+                boundary_bonus: 0,
+                ..config.clone()
+            };
+            let mut plain = TuiApp::new_with_receiver(projects.clone(), None, &unboosted);
+            plain.input = "po".to_string();
+            plain.update_filtered_projects();
+
+            let score = |app: &TuiApp, name: &str| {
+                app.filtered_projects
+                    .iter()
+                    .find(|(index, _)| app.projects[*index].name == name)
+                    .map(|(_, score)| *score)
+                    .unwrap()
+            };
+            assert_eq!(
+                score(&app, "payment-orders") - score(&plain, "payment-orders"),
+                config.boundary_bonus
+            );
+            assert_eq!(
+                score(&app, "wallpaper-tool"),
+                score(&plain, "wallpaper-tool")
+            );
+        }
+    }
+
+    #[test]
+    fn test_theme_preset_selection() {
+        assert_eq!(
+            resolve_preset(ThemePreset::Dark, Some("0;15")),
+            ThemePreset::Dark
+        );
+        assert_eq!(
+            resolve_preset(ThemePreset::Light, Some("15;0")),
+            ThemePreset::Light
+        );
+        assert_eq!(
+            Theme::with_colors(ThemePreset::Light, &ThemeColors::default()),
+            Theme::light()
+        );
+        assert_ne!(Theme::light(), Theme::default());
+    }
+
+    #[test]
+    fn test_auto_preset_reads_colorfgbg() {
+        let auto = |value| resolve_preset(ThemePreset::Auto, value);
+
+        assert_eq!(auto(Some("0;15")), ThemePreset::Light);
+        assert_eq!(auto(Some("0;7")), ThemePreset::Light);
+        assert_eq!(auto(Some("0;default;15")), ThemePreset::Light);
+        assert_eq!(auto(Some("15;0")), ThemePreset::Dark);
+        assert_eq!(auto(Some("7;8")), ThemePreset::Dark);
+        assert_eq!(auto(Some("15;default")), ThemePreset::Dark);
+        assert_eq!(auto(Some("")), ThemePreset::Dark);
+        assert_eq!(auto(None), ThemePreset::Dark);
+    }
+
+    #[test]
+    fn test_parse_hex_color() {
+        assert_eq!(parse_hex_color("#6366f1"), Some(Color::Rgb(99, 102, 241)));
+        assert_eq!(parse_hex_color("FFFFFF"), Some(Color::Rgb(255, 255, 255)));
+        assert_eq!(parse_hex_color(" #0a0 "), Some(Color::Rgb(0, 170, 0)));
+
+        assert_eq!(parse_hex_color("#12345"), None);
+        assert_eq!(parse_hex_color("#gggggg"), None);
+        assert_eq!(parse_hex_color("#ü1234"), None);
+        assert_eq!(parse_hex_color(""), None);
+    }
+
+    #[test]
+    fn test_theme_falls_back_per_role() {
+        let colors = ThemeColors {
+            primary: Some("#000000".to_string()),
+            accent: Some("teal".to_string()),
+            ..ThemeColors::default()
+        };
+        let theme = Theme::with_colors(ThemePreset::Dark, &colors);
+
+        assert_eq!(theme.primary, Color::Rgb(0, 0, 0));
+        assert_eq!(theme.accent, ACCENT_COLOR);
+        assert_eq!(theme.text_muted, TEXT_MUTED);
+        assert_eq!(
+            Theme::with_colors(ThemePreset::Dark, &ThemeColors::default()),
+            Theme::default()
+        );
+
+        // Overrides apply on top of the light palette too
+        let light = Theme::with_colors(ThemePreset::Light, &colors);
+        assert_eq!(light.primary, Color::Rgb(0, 0, 0));
+        assert_eq!(light.accent, Theme::light().accent);
+
+        let config = Config {
+            theme: colors,
+            ..Config::default()
+        };
+        let app = TuiApp::new_with_receiver(create_test_projects(), None, &config);
+        assert_eq!(app.theme.primary, Color::Rgb(0, 0, 0));
+    }
+
+    #[test]
+    fn test_existence_checks_independent_of_thread_count() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let projects: Vec<Project> = (0..40)
+            .map(|i| {
+                let path = temp_dir.path().join(format!("project-{}", i));
+                if i % 3 == 0 {
+                    fs::create_dir(&path).unwrap();
+                }
+                Project::new_local(format!("project-{}", i), path)
+            })
+            .collect();
+        let expected: Vec<bool> = (0..40).map(|i| i % 3 == 0).collect();
+
+        for threads in [0, 1, 3, 16] {
+            assert_eq!(
+                with_probe_pool(threads, || paths_exist(&projects)),
+                expected,
+                "{} threads",
+                threads
+            );
+
+            let config = Config {
+                tui_probe_threads: threads,
+                ..Config::default()
+            };
+            let app = TuiApp::new_with_receiver(projects.clone(), None, &config);
+            assert_eq!(app.project_exists_cache, expected);
+        }
+    }
+
+    #[test]
+    fn test_archived_projects_are_dimmed() {
+        let mut projects = create_test_projects();
+        projects[1].archived = true;
+        let app = TuiApp::new_with_receiver(projects, None, &Config::default());
+
+        let dimmed = |index: usize, selected: bool| {
+            let label = app.display_label(index);
+            app.project_line(index, selected)
+                .spans
+                .iter()
+                .find(|span| span.content == label)
+                .unwrap()
+                .style
+                .add_modifier
+                .contains(Modifier::DIM)
+        };
+        assert!(!dimmed(0, false));
+        assert!(dimmed(1, false));
+        assert!(dimmed(1, true));
+    }
+
+    #[test]
+    fn test_dirty_projects_show_the_marker() {
+        let mut projects = create_test_projects();
+        projects[0].dirty = Some(true);
+        projects[1].dirty = Some(false);
+        let config = Config {
+            dirty_marker: "*".to_string(),
+            ..Config::default()
+        };
+        let app = TuiApp::new_with_receiver(projects, None, &config);
+
+        let spans = |index: usize| -> Vec<(String, Option<Color>)> {
+            app.project_line(index, false)
+                .spans
+                .iter()
+                .map(|span| (span.content.to_string(), span.style.fg))
+                .collect()
+        };
+        assert!(spans(0).contains(&("*".to_string(), Some(app.theme.dirty))));
+        assert!(!spans(1).iter().any(|(text, _)| text == "*"));
+        assert!(!spans(2).iter().any(|(text, _)| text == "*"));
+    }
+
+    #[test]
+    fn test_simple_matcher_ranks_contiguous_first() {
+        let config = Config {
+            matcher: MatcherKind::Simple,
+            ..Config::default()
+        };
+        let projects = vec![
+            Project::new_local("a-pretty-index".to_string(), "/path/a-pretty-index"),
+            Project::new_local("my-api-server".to_string(), "/path/my-api-server"),
+            Project::new_local("website".to_string(), "/path/website"),
+        ];
+        let mut app = TuiApp::new_with_receiver(projects, None, &config);
+
+        app.input = "api".to_string();
+        app.update_filtered_projects();
+
+        assert_eq!(matched_names(&app), vec!["my-api-server", "a-pretty-index"]);
+    }
+
+    fn type_keys(app: &mut TuiApp, codes: &[KeyCode]) {
+        for &code in codes {
+            app.handle_key(KeyEvent::new(code, KeyModifiers::NONE));
+        }
+    }
+
+    #[test]
+    fn test_insert_at_cursor() {
+        let mut app = TuiApp::new(create_test_projects());
+        type_keys(
+            &mut app,
+            &[KeyCode::Char('s'), KeyCode::Char('i'), KeyCode::Char('t')],
+        );
+        assert_eq!((app.input.as_str(), app.cursor()), ("sit", 3));
+
+        // Fix the typo in the middle: "sit" -> "swit"
+        type_keys(
+            &mut app,
+            &[KeyCode::Left, KeyCode::Left, KeyCode::Char('w')],
+        );
+        assert_eq!((app.input.as_str(), app.cursor()), ("swit", 2));
+
+        type_keys(&mut app, &[KeyCode::Home, KeyCode::Char('>')]);
+        assert_eq!((app.input.as_str(), app.cursor()), (">swit", 1));
+
+        type_keys(&mut app, &[KeyCode::End, KeyCode::Char('c')]);
+        assert_eq!((app.input.as_str(), app.cursor()), (">switc", 6));
+
+        // The cursor stops at both ends
+        type_keys(&mut app, &[KeyCode::Right, KeyCode::Right]);
+        assert_eq!(app.cursor(), 6);
+        type_keys(&mut app, &[KeyCode::Home, KeyCode::Left]);
+        assert_eq!(app.cursor(), 0);
+    }
+
+    #[test]
+    fn test_delete_at_cursor() {
+        let mut app = TuiApp::new(create_test_projects());
+        for c in "swiftcch".chars() {
+            type_keys(&mut app, &[KeyCode::Char(c)]);
+        }
+
+        // Backspace removes left of the cursor, Delete the character under it
+        type_keys(&mut app, &[KeyCode::Left; 4]);
+        type_keys(&mut app, &[KeyCode::Backspace]);
+        assert_eq!((app.input.as_str(), app.cursor()), ("switcch", 3));
+        type_keys(&mut app, &[KeyCode::Right, KeyCode::Delete]);
+        assert_eq!((app.input.as_str(), app.cursor()), ("switch", 4));
+
+        type_keys(&mut app, &[KeyCode::Home, KeyCode::Backspace]);
+        assert_eq!((app.input.as_str(), app.cursor()), ("switch", 0));
+        type_keys(&mut app, &[KeyCode::End, KeyCode::Delete]);
+        assert_eq!((app.input.as_str(), app.cursor()), ("switch", 6));
+
+        // Multi-byte characters are removed whole
+        let mut app = TuiApp::new(create_test_projects());
+        for c in "añb".chars() {
+            type_keys(&mut app, &[KeyCode::Char(c)]);
+        }
+        type_keys(&mut app, &[KeyCode::Left, KeyCode::Backspace]);
+        assert_eq!((app.input.as_str(), app.cursor()), ("ab", 1));
+    }
+
+    #[test]
+    fn test_show_paths_toggle_adds_path_to_rows() {
+        let mut app = TuiApp::new(create_test_projects());
+        assert!(!line_text(&app.project_line(0, false)).contains("/path/to/"));
+
+        app.handle_key(KeyEvent::new(KeyCode::Char('p'), KeyModifiers::CONTROL));
+        assert!(app.show_paths);
+        assert!(app.input.is_empty());
+        let line = line_text(&app.project_line(0, false));
+        assert!(line.contains(&app.projects[0].path.display().to_string()));
+
+        app.handle_key(KeyEvent::new(KeyCode::Char('p'), KeyModifiers::CONTROL));
+        assert!(!line_text(&app.project_line(0, false)).contains("/path/to/"));
+    }
+
+    #[test]
+    fn test_abbreviate_home() {
+        let home = Path::new("/home/user");
+        assert_eq!(
+            abbreviate_home(Path::new("/home/user/code/sw"), Some(home)),
+            "~/code/sw"
+        );
+        assert_eq!(abbreviate_home(home, Some(home)), "~");
+        assert_eq!(abbreviate_home(Path::new("/opt/sw"), Some(home)), "/opt/sw");
+        assert_eq!(abbreviate_home(Path::new("/opt/sw"), None), "/opt/sw");
+    }
+
+    #[test]
+    fn test_current_dir_selects_and_marks_project() {
+        let mut app = TuiApp::new(create_test_projects());
+
+        app.set_current_dir(Path::new("/path/to/my-website/src/components"));
+
+        assert_eq!(app.current_project, Some(2));
+        assert_eq!(app.get_selected_project().unwrap().name, "my-website");
+        assert!(line_text(&app.project_line(2, true)).contains("(current)"));
+        assert!(!line_text(&app.project_line(1, false)).contains("(current)"));
+    }
+
+    #[test]
+    fn test_current_dir_selects_project_beyond_the_list_limit() {
+        let projects: Vec<Project> = (0..50)
+            .map(|i| Project::new_local(format!("service-{}", i), format!("/code/service-{}", i)))
+            .collect();
+        let mut app = TuiApp::new(projects);
+
+        app.set_current_dir(Path::new("/code/service-42/src"));
+
+        assert_eq!(app.filtered_projects.len(), MAX_DISPLAYED_PROJECTS);
+        assert_eq!(app.get_selected_project().unwrap().name, "service-42");
+
+        // It only stays listed while it matches the query
+        app.input = "service-4".to_string();
+        app.update_filtered_projects();
+        assert!(matched_names(&app).contains(&"service-42"));
+        app.input = "service-1".to_string();
+        app.update_filtered_projects();
+        assert!(!matched_names(&app).contains(&"service-42"));
+    }
+
+    fn matched_names(app: &TuiApp) -> Vec<&str> {
+        app.filtered_projects
+            .iter()
+            .map(|(i, _)| app.projects[*i].name.as_str())
+            .collect()
+    }
+
+    fn create_case_projects() -> Vec<Project> {
+        vec![
+            Project::new_local("Api-Server".to_string(), "/path/to/Api-Server"),
+            Project::new_local("api-client".to_string(), "/path/to/api-client"),
+        ]
+    }
+
+    #[test]
+    fn test_case_insensitive_search() {
+        let config = Config {
+            search_case: SearchCase::Insensitive,
+            ..Config::default()
+        };
+        let mut app = TuiApp::new_with_receiver(create_case_projects(), None, &config);
+
+        app.input = "API".to_string();
+        app.update_filtered_projects();
+
+        assert_eq!(app.filtered_projects.len(), 2);
+    }
+
+    #[test]
+    fn test_case_sensitive_search() {
+        let config = Config {
+            search_case: SearchCase::Sensitive,
+            ..Config::default()
+        };
+        let mut app = TuiApp::new_with_receiver(create_case_projects(), None, &config);
+
+        app.input = "api".to_string();
+        app.update_filtered_projects();
+        assert_eq!(matched_names(&app), vec!["api-client"]);
+
+        app.input = "Api".to_string();
+        app.update_filtered_projects();
+        assert_eq!(matched_names(&app), vec!["Api-Server"]);
+    }
+
+    #[test]
+    fn test_smart_case_search() {
+        let config = Config {
+            search_case: SearchCase::Smart,
+            ..Config::default()
+        };
+        let mut app = TuiApp::new_with_receiver(create_case_projects(), None, &config);
+
+        app.input = "api".to_string();
+        app.update_filtered_projects();
+        assert_eq!(app.filtered_projects.len(), 2);
+
+        app.input = "Api".to_string();
+        app.update_filtered_projects();
+        assert_eq!(matched_names(&app), vec!["Api-Server"]);
+    }
+
+    #[test]
+    fn test_display_name_shown_but_matching_uses_project_name() {
+        let mut config = Config::default();
+        config.set_display_name(Path::new("/path/to/cool-app"), "Billing Dashboard");
+        let mut app = TuiApp::new_with_receiver(create_test_projects(), None, &config);
+
+        let line = line_text(&app.project_line(1, false));
+        assert!(line.contains("Billing Dashboard"));
+        assert!(!line.contains("cool-app"));
+
+        app.input = "cool".to_string();
+        app.update_filtered_projects();
+        assert_eq!(matched_names(&app), vec!["cool-app"]);
+
+        app.input = "billing".to_string();
+        app.update_filtered_projects();
+        assert!(app.filtered_projects.is_empty());
+    }
+
+    fn render_to_buffer(app: &TuiApp, width: u16, height: u16) -> String {
+        let mut terminal =
+            Terminal::new(ratatui::backend::TestBackend::new(width, height)).unwrap();
+        terminal.draw(|f| app.draw(f)).unwrap();
+        let buffer = terminal.backend().buffer();
+        buffer.content().iter().map(|cell| cell.symbol()).collect()
+    }
+
+    #[test]
+    fn test_tiny_terminal_uses_compact_layout() {
+        let mut app = TuiApp::new(create_test_projects());
+        app.input = "web".to_string();
+        app.update_filtered_projects();
+
+        for (width, height) in [(80, 5), (20, 30), (1, 1), (10, 2)] {
+            render_to_buffer(&app, width, height);
+        }
+
+        let compact = render_to_buffer(&app, 60, 5);
+        assert!(compact.contains("> web"));
+        assert!(compact.contains("my-website"));
+        assert!(!compact.contains("Project Switcher"));
+
+        let full = render_to_buffer(&app, 100, 40);
+        assert!(full.contains("Project Switcher"));
+    }
+
+    #[test]
+    fn test_modified_enter_flips_open_in_current_window() {
+        let mut app = TuiApp::new(create_test_projects());
+        app.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::SHIFT));
+        assert!(app.open_in_current_window);
+        assert!(app.selected_project.is_some());
+
+        let config = Config {
+            open_in_current_window: true,
+            ..Config::default()
+        };
+        let mut app = TuiApp::new_with_receiver(create_test_projects(), None, &config);
+        app.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert!(app.open_in_current_window);
+    }
+
+    #[test]
+    fn test_multi_attribute_query() {
+        let projects = vec![
+            Project::new_local("api-server".to_string(), "/path/to/api-server"),
+            Project::new_github(
+                "api-gateway".to_string(),
+                "/path/to/api-gateway",
+                "https://github.com/user/api-gateway".to_string(),
+            ),
+            Project::new_github(
+                "dotfiles".to_string(),
+                "/path/to/dotfiles",
+                "https://github.com/user/dotfiles".to_string(),
+            ),
+        ];
+        let mut app = TuiApp::new(projects);
+
+        app.input = "api github".to_string();
+        app.update_filtered_projects();
+        assert_eq!(matched_names(&app), vec!["api-gateway"]);
+
+        app.input = "github".to_string();
+        app.update_filtered_projects();
+        let mut names = matched_names(&app);
+        names.sort();
+        assert_eq!(names, vec!["api-gateway", "dotfiles"]);
+    }
+
+    #[test]
+    fn test_name_matches_rank_above_attribute_matches() {
+        let projects = vec![
+            Project::new_github(
+                "dotfiles".to_string(),
+                "/path/to/dotfiles",
+                "https://github.com/user/dotfiles".to_string(),
+            ),
+            Project::new_local("github-actions".to_string(), "/path/to/github-actions"),
+        ];
+        let mut app = TuiApp::new(projects);
+
+        app.input = "github".to_string();
+        app.update_filtered_projects();
+        assert_eq!(matched_names(&app), vec!["github-actions", "dotfiles"]);
+    }
+
+    #[test]
+    fn test_last_session_round_trip() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("data/tui_session.json");
+
+        let mut app = TuiApp::new(create_test_projects());
+        app.input = "app".to_string();
+        app.update_filtered_projects();
+        let session = app.last_session();
+        assert_eq!(session.selected_name.as_deref(), Some("cool-app"));
+
+        session.save_to_path(&path).unwrap();
+        let loaded = LastSession::load_from_path(&path).unwrap().unwrap();
+        assert_eq!(loaded, session);
+
+        let mut app = TuiApp::new(create_test_projects());
+        app.restore_session(&loaded);
+        assert_eq!(app.input, "app");
+        assert_eq!(app.get_selected_project().unwrap().name, "cool-app");
+    }
+
+    #[test]
+    fn test_restore_session_with_missing_project() {
+        let session = LastSession {
+            query: String::new(),
+            selected_name: Some("deleted-project".to_string()),
+            selected_path: Some(PathBuf::from("/path/to/deleted-project")),
+        };
+
+        let mut app = TuiApp::new(create_test_projects());
+        app.restore_session(&session);
+
+        assert_eq!(app.selected_index, 0);
+        assert!(app.get_selected_project().is_some());
+    }
+
+    #[test]
+    fn test_last_session_missing_file() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("tui_session.json");
+
+        assert!(LastSession::load_from_path(&path).unwrap().is_none());
+    }
 }
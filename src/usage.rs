@@ -0,0 +1,151 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::{self, OpenOptions};
+use std::path::{Path, PathBuf};
+
+use crate::cache::atomic_write;
+use crate::config::Config;
+
+/// How often and how recently a project was opened
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UsageEntry {
+    pub count: u64,
+    pub last_opened: DateTime<Utc>,
+}
+
+/// Open counts per project path, stored as JSON in the data directory
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UsageLog {
+    pub projects: BTreeMap<PathBuf, UsageEntry>,
+}
+
+impl UsageLog {
+    pub fn load_from_path(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read usage log: {}", path.display()))?;
+        serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse usage log: {}", path.display()))
+    }
+
+    #[allow(dead_code)]
+    pub fn count(&self, project_path: &Path) -> u64 {
+        self.projects
+            .get(project_path)
+            .map_or(0, |entry| entry.count)
+    }
+
+    /// The project opened most recently, if any
+    pub fn last_opened(&self) -> Option<&Path> {
+        self.projects
+            .iter()
+            .max_by_key(|(_, entry)| entry.last_opened)
+            .map(|(path, _)| path.as_path())
+    }
+
+    /// Count an open of `project_path` in the default usage log
+    pub fn record_open(project_path: &Path) -> Result<()> {
+        Self::record_open_at(&Config::usage_log_path()?, project_path, Utc::now())
+    }
+
+    /// Count an open of `project_path` in the log at `log_path`. The
+    /// read-modify-write runs under an exclusive lock on a sidecar file so
+    /// concurrent `sw` invocations don't drop each other's increments.
+    pub fn record_open_at(log_path: &Path, project_path: &Path, now: DateTime<Utc>) -> Result<()> {
+        if let Some(parent) = log_path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+        }
+
+        let lock_path = log_path.with_extension("lock");
+        let lock_file = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(&lock_path)
+            .with_context(|| format!("Failed to open usage lock: {}", lock_path.display()))?;
+        lock_file
+            .lock()
+            .with_context(|| format!("Failed to lock usage log: {}", lock_path.display()))?;
+
+        let mut log = Self::load_from_path(log_path)?;
+        let entry = log
+            .projects
+            .entry(project_path.to_path_buf())
+            .or_insert(UsageEntry {
+                count: 0,
+                last_opened: now,
+            });
+        entry.count += 1;
+        entry.last_opened = now;
+
+        let data = serde_json::to_vec_pretty(&log).context("Failed to serialize usage log")?;
+        atomic_write(log_path, &data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::thread;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_record_open_counts_and_timestamps() {
+        let temp_dir = TempDir::new().unwrap();
+        let log_path = temp_dir.path().join("data").join("usage.json");
+        let project = Path::new("/code/sw");
+
+        let first = Utc::now() - chrono::Duration::hours(1);
+        let second = Utc::now();
+        UsageLog::record_open_at(&log_path, project, first).unwrap();
+        UsageLog::record_open_at(&log_path, project, second).unwrap();
+
+        let log = UsageLog::load_from_path(&log_path).unwrap();
+        assert_eq!(log.count(project), 2);
+        assert_eq!(log.projects[project].last_opened, second);
+        assert_eq!(log.count(Path::new("/code/other")), 0);
+
+        UsageLog::record_open_at(&log_path, Path::new("/code/other"), first).unwrap();
+        let log = UsageLog::load_from_path(&log_path).unwrap();
+        assert_eq!(log.last_opened(), Some(project));
+    }
+
+    #[test]
+    fn test_concurrent_records_are_not_lost() {
+        const THREADS: usize = 8;
+        const OPENS_PER_THREAD: usize = 25;
+
+        let temp_dir = TempDir::new().unwrap();
+        let log_path = Arc::new(temp_dir.path().join("usage.json"));
+
+        let handles: Vec<_> = (0..THREADS)
+            .map(|i| {
+                let log_path = Arc::clone(&log_path);
+                thread::spawn(move || {
+                    let project = PathBuf::from(format!("/code/project-{}", i % 2));
+                    for _ in 0..OPENS_PER_THREAD {
+                        UsageLog::record_open_at(&log_path, &project, Utc::now()).unwrap();
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        let log = UsageLog::load_from_path(&log_path).unwrap();
+        let total: u64 = log.projects.values().map(|entry| entry.count).sum();
+        assert_eq!(total, (THREADS * OPENS_PER_THREAD) as u64);
+        assert_eq!(
+            log.count(Path::new("/code/project-0")),
+            (THREADS / 2 * OPENS_PER_THREAD) as u64
+        );
+    }
+}
@@ -2,6 +2,35 @@ use assert_cmd::Command;
 use predicates::prelude::*;
 use tempfile::TempDir;
 
+/// `sw` isolated in `temp`: home, cache, config and data dirs all live
+/// there, and `config` (on top of test defaults that scan `temp/projects`
+/// and open with `true`) is written to `temp/config.json` and passed with
+/// `--config`
+fn sw_command(temp: &TempDir, config: serde_json::Value) -> Command {
+    let mut settings = serde_json::json!({
+        "editor_command": "true",
+        "project_dirs": [temp.path().join("projects")],
+        "github_username": null,
+        "gitlab_username": null,
+        "cache_ttl_seconds": 60
+    });
+    if let (Some(settings), serde_json::Value::Object(config)) = (settings.as_object_mut(), config)
+    {
+        settings.extend(config);
+    }
+    let config_path = temp.path().join("config.json");
+    std::fs::write(&config_path, settings.to_string()).unwrap();
+
+    let mut cmd = Command::cargo_bin("sw").unwrap();
+    cmd.env("HOME", temp.path())
+        .env("XDG_CACHE_HOME", temp.path().join(".cache"))
+        .env("XDG_CONFIG_HOME", temp.path().join(".config"))
+        .env("SW_DATA_DIR", temp.path().join("data"))
+        .arg("--config")
+        .arg(config_path);
+    cmd
+}
+
 #[test]
 fn test_cli_help() {
     let mut cmd = Command::cargo_bin("sw").unwrap();
@@ -179,6 +208,45 @@ fn test_refresh_subcommand() {
         .stdout(predicate::str::contains("Cache refreshed! Found"));
 }
 
+#[test]
+fn test_refresh_reports_each_source() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let mut cmd = sw_command(&temp_dir, serde_json::json!({}));
+    cmd.arg("refresh");
+
+    let output = cmd.assert().success().get_output().stdout.clone();
+    let stdout = String::from_utf8_lossy(&output);
+
+    for source in ["local", "cursor", "github", "gitlab"] {
+        assert!(
+            stdout
+                .lines()
+                .any(|line| line.contains(&format!("✓ {} (", source))),
+            "missing progress line for {}: {}",
+            source,
+            stdout
+        );
+    }
+}
+
+#[test]
+fn test_config_flag_selects_config_file() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut cmd = sw_command(
+        &temp_dir,
+        serde_json::json!({
+            "editor_command": "custom-editor --wait",
+            "project_dirs": []
+        }),
+    );
+    cmd.arg("config");
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Editor: custom-editor --wait"));
+}
+
 #[test]
 fn test_config_file_creation() {
     let temp_dir = TempDir::new().unwrap();
@@ -237,6 +305,150 @@ fn test_cursor_scanner_integration() {
     );
 }
 
+#[test]
+fn test_simple_mode_opens_selected_project() {
+    let temp_dir = TempDir::new().unwrap();
+    let projects_dir = temp_dir.path().join("projects");
+    for name in ["alpha", "beta"] {
+        std::fs::create_dir_all(projects_dir.join(name).join(".git")).unwrap();
+    }
+
+    let mut cmd = sw_command(
+        &temp_dir,
+        serde_json::json!({ "editor_command": "echo", "project_dirs": [projects_dir] }),
+    );
+    cmd.arg("--simple");
+    cmd.write_stdin("2\n");
+
+    let output = cmd.assert().success().get_output().stdout.clone();
+    let stdout = String::from_utf8_lossy(&output);
+
+    let second = stdout
+        .lines()
+        .find(|line| line.trim_start().starts_with("2)"))
+        .expect("numbered list should have a second entry");
+    let name = if second.contains("alpha") {
+        "alpha"
+    } else {
+        "beta"
+    };
+    assert!(
+        stdout.contains(&format!("Opened project: {}", name)),
+        "{}",
+        stdout
+    );
+}
+
+#[test]
+fn test_exact_rejects_substring_match() {
+    let temp_dir = TempDir::new().unwrap();
+    let projects_dir = temp_dir.path().join("projects");
+    std::fs::create_dir_all(projects_dir.join("zapplication").join(".git")).unwrap();
+
+    let mut cmd = sw_command(
+        &temp_dir,
+        serde_json::json!({ "editor_command": "echo", "project_dirs": [projects_dir] }),
+    );
+    cmd.args(["--exact", "app"]);
+
+    cmd.assert()
+        .failure()
+        .stdout(predicate::str::contains("No exact match for project 'app'"));
+}
+
+#[test]
+fn test_open_with_overrides_configured_editor() {
+    let temp_dir = TempDir::new().unwrap();
+    let projects_dir = temp_dir.path().join("projects");
+    std::fs::create_dir_all(projects_dir.join("alpha").join(".git")).unwrap();
+
+    let run = |extra: &[&str]| {
+        let mut cmd = sw_command(
+            &temp_dir,
+            serde_json::json!({ "editor_command": "false", "project_dirs": [projects_dir] }),
+        );
+        cmd.args(extra).arg("alpha");
+        cmd.assert()
+    };
+
+    run(&[]).failure();
+    run(&["--open-with", "echo"])
+        .success()
+        .stdout(predicate::str::contains("Opened project: alpha"));
+
+    let saved = std::fs::read_to_string(temp_dir.path().join("config.json")).unwrap();
+    assert!(saved.contains("\"false\""), "{}", saved);
+}
+
+#[test]
+fn test_env_editor_sentinel_follows_editor_variable() {
+    let temp_dir = TempDir::new().unwrap();
+    let projects_dir = temp_dir.path().join("projects");
+    std::fs::create_dir_all(projects_dir.join("alpha").join(".git")).unwrap();
+
+    let run = |editor: &str| {
+        let mut cmd = sw_command(
+            &temp_dir,
+            serde_json::json!({ "editor_command": "$EDITOR", "project_dirs": [projects_dir] }),
+        );
+        cmd.env("EDITOR", editor).env_remove("VISUAL").arg("alpha");
+        cmd.assert()
+    };
+
+    run("false")
+        .failure()
+        .stderr(predicate::str::contains("Editor command failed"));
+    run("echo")
+        .success()
+        .stdout(predicate::str::contains("Opened project: alpha"));
+}
+
+#[test]
+fn test_extra_dirs_neither_use_nor_fill_the_cache() {
+    let temp_dir = TempDir::new().unwrap();
+    let projects_dir = temp_dir.path().join("projects");
+    let extra_dir = temp_dir.path().join("extra");
+    std::fs::create_dir_all(projects_dir.join("alpha").join(".git")).unwrap();
+    std::fs::create_dir_all(extra_dir.join("scratchpad").join(".git")).unwrap();
+
+    let list = |extra: Option<&std::path::Path>| {
+        let mut cmd = sw_command(
+            &temp_dir,
+            serde_json::json!({ "editor_command": "echo", "project_dirs": [projects_dir] }),
+        );
+        cmd.env_remove("SW_EXTRA_DIRS");
+        if let Some(extra) = extra {
+            cmd.env("SW_EXTRA_DIRS", extra);
+        }
+        cmd.arg("list");
+        let output = cmd.assert().success().get_output().stdout.clone();
+        String::from_utf8_lossy(&output).into_owned()
+    };
+
+    assert!(list(Some(&extra_dir)).contains("scratchpad"));
+    let cached = list(None);
+    assert!(cached.contains("alpha"), "{}", cached);
+    assert!(!cached.contains("scratchpad"), "{}", cached);
+    assert!(list(Some(&extra_dir)).contains("scratchpad"));
+}
+
+#[test]
+fn test_quiet_open_prints_no_confirmation() {
+    let temp_dir = TempDir::new().unwrap();
+    let projects_dir = temp_dir.path().join("projects");
+    std::fs::create_dir_all(projects_dir.join("alpha").join(".git")).unwrap();
+
+    let mut cmd = sw_command(
+        &temp_dir,
+        serde_json::json!({ "editor_command": "true", "project_dirs": [projects_dir] }),
+    );
+    cmd.arg("alpha").arg("--quiet");
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Opened project").not());
+}
+
 #[test]
 fn test_fzf_mode_implementation() {
     let mut cmd = Command::cargo_bin("sw").unwrap();
@@ -292,6 +504,27 @@ fn test_first_time_setup_logic_isolated() {
     );
 }
 
+#[test]
+fn test_first_run_without_tty_writes_default_config() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = temp_dir.path().join("sw").join("config.json");
+
+    let mut cmd = Command::cargo_bin("sw").unwrap();
+    cmd.env("HOME", temp_dir.path())
+        .env("XDG_CACHE_HOME", temp_dir.path().join("cache"))
+        .arg("--config")
+        .arg(&config_path)
+        .arg("config");
+
+    cmd.assert()
+        .success()
+        .stderr(predicate::str::contains(format!(
+            "Created default configuration at {}",
+            config_path.display()
+        )));
+    assert!(config_path.exists());
+}
+
 #[test]
 fn test_config_is_first_time_run_detection() {
     use sw::config::Config;
@@ -310,6 +543,7 @@ fn test_config_should_prompt_github_setup() {
         github_username: None,
         gitlab_username: None,
         cache_ttl_seconds: 1800,
+        ..Config::default()
     };
     assert!(config_without_github.should_prompt_github_setup());
 
@@ -319,6 +553,7 @@ fn test_config_should_prompt_github_setup() {
         github_username: Some("testuser".to_string()),
         gitlab_username: None,
         cache_ttl_seconds: 1800,
+        ..Config::default()
     };
     assert!(!config_with_github.should_prompt_github_setup());
 }
@@ -333,6 +568,7 @@ fn test_github_setup_prompting_logic() {
         github_username: None,
         gitlab_username: None,
         cache_ttl_seconds: 1800,
+        ..Config::default()
     };
     assert!(config_without_github.should_prompt_github_setup());
 
@@ -342,6 +578,7 @@ fn test_github_setup_prompting_logic() {
         github_username: Some("testuser".to_string()),
         gitlab_username: None,
         cache_ttl_seconds: 1800,
+        ..Config::default()
     };
     assert!(!config_with_github.should_prompt_github_setup());
 }